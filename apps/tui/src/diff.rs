@@ -0,0 +1,448 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::{EventKind, Ledger, Task};
+
+/// Events of the same task and type that moved by at most this much are
+/// reported as retimed rather than as one removal and one addition.
+const RETIME_WINDOW_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl Change {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Change::Added => "added",
+            Change::Removed => "removed",
+            Change::Modified => "modified",
+        }
+    }
+}
+
+/// One field that differs, rendered as text (`None` when unset).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// A project, category or task present on one side only or with changed
+/// fields. `label` is the name in the newer ledger when it exists there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityChange {
+    pub change: Change,
+    pub id: String,
+    pub label: String,
+    pub fields: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventChange {
+    Added,
+    Removed,
+    Retimed,
+    NoteChanged,
+}
+
+impl EventChange {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventChange::Added => "added",
+            EventChange::Removed => "removed",
+            EventChange::Retimed => "retimed",
+            EventChange::NoteChanged => "note changed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSide {
+    pub timestamp: DateTime<Utc>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventDiff {
+    pub change: EventChange,
+    pub task_id: String,
+    /// `start` or `stop`.
+    pub kind: &'static str,
+    pub before: Option<EventSide>,
+    pub after: Option<EventSide>,
+}
+
+impl EventDiff {
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.after
+            .as_ref()
+            .or(self.before.as_ref())
+            .map(|side| side.timestamp)
+            .unwrap_or_default()
+    }
+}
+
+/// Semantic differences between two versions of a ledger.
+#[derive(Debug, Clone, Default)]
+pub struct LedgerDiff {
+    pub settings: Vec<FieldChange>,
+    pub projects: Vec<EntityChange>,
+    pub categories: Vec<EntityChange>,
+    pub tasks: Vec<EntityChange>,
+    pub events: Vec<EventDiff>,
+}
+
+impl LedgerDiff {
+    pub fn is_empty(&self) -> bool {
+        self.settings.is_empty()
+            && self.projects.is_empty()
+            && self.categories.is_empty()
+            && self.tasks.is_empty()
+            && self.events.is_empty()
+    }
+}
+
+/// Compares header settings and entities by ID, field by field, and events
+/// as a multiset. Unmatched events of the same task and type are paired up
+/// as note changes (same time) or retimings (nearest time within a day).
+pub fn diff_ledgers(before: &Ledger, after: &Ledger) -> LedgerDiff {
+    let header = |ledger: &Ledger| {
+        [
+            (
+                "day_start_offset_hours",
+                Some(ledger.header.day_start_offset_hours.to_string()),
+            ),
+            ("work_day_start", ledger.header.work_day_start.clone()),
+            ("work_day_end", ledger.header.work_day_end.clone()),
+        ]
+    };
+    let settings = field_changes(&header(before), &header(after));
+
+    let projects = diff_entities(
+        &before.header.projects,
+        &after.header.projects,
+        |project| project.id.clone(),
+        |project| project.name.clone(),
+        |project| {
+            [
+                ("name", Some(project.name.clone())),
+                ("color", project.color.clone()),
+                ("archived", Some(project.archived.to_string())),
+            ]
+        },
+    );
+    let categories = diff_entities(
+        &before.header.categories,
+        &after.header.categories,
+        |category| category.id.clone(),
+        |category| category.name.clone(),
+        |category| {
+            [
+                ("name", Some(category.name.clone())),
+                ("description", category.description.clone()),
+                ("archived", Some(category.archived.to_string())),
+            ]
+        },
+    );
+    let tasks = diff_entities_with(
+        &before.header.tasks,
+        &after.header.tasks,
+        |task| task.id.clone(),
+        |task| task.short_description(),
+        |task| task_fields(before, task),
+        |task| task_fields(after, task),
+    );
+
+    LedgerDiff {
+        settings,
+        projects,
+        categories,
+        tasks,
+        events: diff_events(before, after),
+    }
+}
+
+/// Task fields with the project and category shown by name.
+fn task_fields(ledger: &Ledger, task: &Task) -> [(&'static str, Option<String>); 4] {
+    let project = ledger
+        .project(&task.project_id)
+        .map_or_else(|| task.project_id.clone(), |project| project.name.clone());
+    let category = task.category_id.as_ref().map(|id| {
+        ledger
+            .category(id)
+            .map_or_else(|| id.clone(), |category| category.name.clone())
+    });
+    [
+        ("project", Some(project)),
+        ("category", category),
+        ("description", Some(task.description.clone())),
+        ("archived", Some(task.archived.to_string())),
+    ]
+}
+
+fn field_changes<const N: usize>(
+    before: &[(&'static str, Option<String>); N],
+    after: &[(&'static str, Option<String>); N],
+) -> Vec<FieldChange> {
+    before
+        .iter()
+        .zip(after)
+        .filter(|((_, left), (_, right))| left != right)
+        .map(|((field, left), (_, right))| FieldChange {
+            field,
+            before: left.clone(),
+            after: right.clone(),
+        })
+        .collect()
+}
+
+fn diff_entities<T, const N: usize>(
+    before: &[T],
+    after: &[T],
+    id: impl Fn(&T) -> String,
+    label: impl Fn(&T) -> String,
+    fields: impl Fn(&T) -> [(&'static str, Option<String>); N],
+) -> Vec<EntityChange> {
+    diff_entities_with(before, after, id, label, &fields, &fields)
+}
+
+/// Like [`diff_entities`], with fields read against each side's own
+/// ledger.
+fn diff_entities_with<T, const N: usize>(
+    before: &[T],
+    after: &[T],
+    id: impl Fn(&T) -> String,
+    label: impl Fn(&T) -> String,
+    before_fields: impl Fn(&T) -> [(&'static str, Option<String>); N],
+    after_fields: impl Fn(&T) -> [(&'static str, Option<String>); N],
+) -> Vec<EntityChange> {
+    let before_by_id = before
+        .iter()
+        .map(|entity| (id(entity), entity))
+        .collect::<HashMap<_, _>>();
+    let after_ids = after.iter().map(&id).collect::<Vec<_>>();
+
+    let mut changes = Vec::new();
+    for entity in before {
+        let entity_id = id(entity);
+        if !after_ids.contains(&entity_id) {
+            changes.push(EntityChange {
+                change: Change::Removed,
+                id: entity_id,
+                label: label(entity),
+                fields: Vec::new(),
+            });
+        }
+    }
+    for entity in after {
+        let entity_id = id(entity);
+        match before_by_id.get(&entity_id) {
+            None => changes.push(EntityChange {
+                change: Change::Added,
+                id: entity_id,
+                label: label(entity),
+                fields: Vec::new(),
+            }),
+            Some(old) => {
+                let fields = field_changes(&before_fields(old), &after_fields(entity));
+                if !fields.is_empty() {
+                    changes.push(EntityChange {
+                        change: Change::Modified,
+                        id: entity_id,
+                        label: label(entity),
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+    changes
+}
+
+type EventKey = (String, &'static str, DateTime<Utc>, Option<String>);
+
+fn event_keys(ledger: &Ledger) -> Vec<EventKey> {
+    ledger
+        .events
+        .iter()
+        .map(|event| match &event.kind {
+            EventKind::Start { task_id, note } => {
+                (task_id.clone(), "start", event.timestamp, note.clone())
+            }
+            EventKind::Stop { task_id, note } => {
+                (task_id.clone(), "stop", event.timestamp, note.clone())
+            }
+        })
+        .collect()
+}
+
+fn diff_events(before: &Ledger, after: &Ledger) -> Vec<EventDiff> {
+    // Identical events on both sides cancel out, duplicates included.
+    let mut unmatched_after = HashMap::<EventKey, usize>::new();
+    for key in event_keys(after) {
+        *unmatched_after.entry(key).or_default() += 1;
+    }
+    let mut removed = Vec::new();
+    for key in event_keys(before) {
+        match unmatched_after.get_mut(&key) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => removed.push(key),
+        }
+    }
+    let mut added = event_keys(after)
+        .into_iter()
+        .filter(|key| match unmatched_after.get_mut(key) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        })
+        .map(Some)
+        .collect::<Vec<_>>();
+
+    let side = |key: &EventKey| EventSide {
+        timestamp: key.2,
+        note: key.3.clone(),
+    };
+    let mut changes = Vec::new();
+    for old in removed {
+        let candidate = added
+            .iter()
+            .enumerate()
+            .filter_map(|(index, new)| new.as_ref().map(|new| (index, new)))
+            .filter(|(_, new)| new.0 == old.0 && new.1 == old.1)
+            .map(|(index, new)| (index, (new.2 - old.2).abs()))
+            .filter(|(_, distance)| *distance <= Duration::hours(RETIME_WINDOW_HOURS))
+            .min_by_key(|(_, distance)| *distance);
+        let Some((index, distance)) = candidate else {
+            changes.push(EventDiff {
+                change: EventChange::Removed,
+                task_id: old.0.clone(),
+                kind: old.1,
+                before: Some(side(&old)),
+                after: None,
+            });
+            continue;
+        };
+        let new = added[index].take().expect("candidate is unmatched");
+        changes.push(EventDiff {
+            change: if distance.is_zero() {
+                EventChange::NoteChanged
+            } else {
+                EventChange::Retimed
+            },
+            task_id: old.0.clone(),
+            kind: old.1,
+            before: Some(side(&old)),
+            after: Some(side(&new)),
+        });
+    }
+    changes.extend(added.into_iter().flatten().map(|new| EventDiff {
+        change: EventChange::Added,
+        task_id: new.0.clone(),
+        kind: new.1,
+        before: None,
+        after: Some(side(&new)),
+    }));
+
+    changes.sort_by_key(|change| change.timestamp());
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{Change, EventChange, diff_ledgers};
+    use crate::domain::{EventKind, Ledger};
+
+    #[test]
+    fn reports_entity_fields_and_event_changes() {
+        let at =
+            |hour: u32, minute: u32| Utc.with_ymd_and_hms(2026, 3, 2, hour, minute, 0).unwrap();
+        let now = at(23, 0);
+        let mut before = Ledger::new();
+        let work = before.add_project("Work".to_string(), None);
+        let home = before.add_project("Home".to_string(), None);
+        let design = before
+            .add_task(work.clone(), None, "Design".to_string())
+            .expect("task should be created");
+        before
+            .log_session(&design, at(9, 0), at(10, 0), Some("draft".to_string()), now)
+            .expect("session should be logged");
+        before
+            .log_session(&design, at(11, 0), at(12, 0), None, now)
+            .expect("session should be logged");
+        before
+            .log_session(&design, at(14, 0), at(15, 0), None, now)
+            .expect("session should be logged");
+
+        let mut after = before.clone();
+        after.header.day_start_offset_hours = 4;
+        after.header.projects.retain(|project| project.id != home);
+        after.header.projects[0].color = Some("blue".to_string());
+        let review = after
+            .add_task(work, None, "Review".to_string())
+            .expect("task should be created");
+        after.header.tasks[0].description = "Design\nwith notes".to_string();
+        // Retime the first stop, change the second start's note, drop the
+        // last session and add another one.
+        for event in &mut after.events {
+            match &mut event.kind {
+                EventKind::Stop { .. } if event.timestamp == at(10, 0) => {
+                    event.timestamp = at(10, 15);
+                }
+                EventKind::Start { note, .. } if event.timestamp == at(11, 0) => {
+                    *note = Some("follow-up".to_string());
+                }
+                _ => {}
+            }
+        }
+        after.events.retain(|event| event.timestamp < at(14, 0));
+        after
+            .log_session(&review, at(16, 0), at(17, 0), None, now)
+            .expect("session should be logged");
+
+        let diff = diff_ledgers(&before, &after);
+        assert_eq!(diff.settings.len(), 1);
+        assert_eq!(diff.settings[0].after.as_deref(), Some("4"));
+
+        let projects = diff
+            .projects
+            .iter()
+            .map(|change| (change.change, change.label.as_str(), change.fields.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            projects,
+            [(Change::Removed, "Home", 0), (Change::Modified, "Work", 1)]
+        );
+        assert_eq!(diff.tasks.len(), 2);
+        assert_eq!(diff.tasks[0].fields[0].field, "description");
+        assert_eq!(diff.tasks[1].change, Change::Added);
+
+        let events = diff
+            .events
+            .iter()
+            .map(|change| (change.change, change.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            [
+                (EventChange::Retimed, "stop"),
+                (EventChange::NoteChanged, "start"),
+                (EventChange::Removed, "start"),
+                (EventChange::Removed, "stop"),
+                (EventChange::Added, "start"),
+                (EventChange::Added, "stop"),
+            ]
+        );
+        assert!(diff_ledgers(&after, &after).is_empty());
+    }
+}
@@ -1,10 +1,16 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
-use chrono::{DateTime, Duration, Local, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono::{
+    DateTime, Duration, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
 use rand::{Rng, distributions::Alphanumeric, thread_rng};
 use serde::{Deserialize, Serialize};
 
 const ID_LEN: usize = 8;
+const DEFAULT_WORK_DAY_START: (u32, u32) = (9, 0);
+const DEFAULT_WORK_DAY_END: (u32, u32) = (17, 0);
+/// Largest gap `merge_sessions` absorbs, for sessions typed to the minute.
+const MERGE_GAP_TOLERANCE: Duration = Duration::minutes(1);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
@@ -89,6 +95,35 @@ impl TimeEvent {
             },
         }
     }
+
+    /// Sort key for replaying the log: by timestamp, with stops ahead of
+    /// starts at the same instant so back-to-back sessions don't overlap.
+    /// Sort stably on it so ties keep their file order.
+    pub fn replay_key(&self) -> (DateTime<Utc>, bool) {
+        (self.timestamp, matches!(self.kind, EventKind::Start { .. }))
+    }
+}
+
+/// One change for a `SessionBatch`, checked like the method named in its
+/// doc.
+#[derive(Debug, Clone)]
+pub enum SessionChange {
+    /// A finished session, like `log_session`.
+    Log {
+        start: DateTime<Utc>,
+        stop: DateTime<Utc>,
+        note: Option<String>,
+    },
+    /// A session that keeps running, like `start_task`.
+    Start {
+        start: DateTime<Utc>,
+        note: Option<String>,
+    },
+    /// Ends the running session, like `stop_task`.
+    Stop {
+        at: DateTime<Utc>,
+        note: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -97,6 +132,10 @@ pub struct LedgerHeader {
     pub created_at: DateTime<Utc>,
     #[serde(default)]
     pub day_start_offset_hours: i32,
+    #[serde(default)]
+    pub work_day_start: Option<String>,
+    #[serde(default)]
+    pub work_day_end: Option<String>,
     pub projects: Vec<Project>,
     pub tasks: Vec<Task>,
     pub categories: Vec<Category>,
@@ -108,6 +147,8 @@ impl LedgerHeader {
             schema_version: 1,
             created_at: Utc::now(),
             day_start_offset_hours: 0,
+            work_day_start: None,
+            work_day_end: None,
             projects: Vec::new(),
             tasks: Vec::new(),
             categories: Vec::new(),
@@ -141,6 +182,32 @@ impl Ledger {
         ledger_day_bounds_utc(day, self.day_start_offset())
     }
 
+    /// Local working hours used to find untracked gaps. Missing or malformed
+    /// header values fall back to 09:00-17:00.
+    pub fn working_hours(&self) -> (NaiveTime, NaiveTime) {
+        let parse = |value: &Option<String>, (hour, minute): (u32, u32)| {
+            value
+                .as_deref()
+                .and_then(|value| NaiveTime::parse_from_str(value, "%H:%M").ok())
+                .unwrap_or_else(|| {
+                    NaiveTime::from_hms_opt(hour, minute, 0).expect("default time is valid")
+                })
+        };
+        (
+            parse(&self.header.work_day_start, DEFAULT_WORK_DAY_START),
+            parse(&self.header.work_day_end, DEFAULT_WORK_DAY_END),
+        )
+    }
+
+    pub fn set_working_hours(&mut self, start: NaiveTime, end: NaiveTime) -> Result<(), String> {
+        if start == end {
+            return Err("working hours must not start and end at the same time".to_string());
+        }
+        self.header.work_day_start = Some(start.format("%H:%M").to_string());
+        self.header.work_day_end = Some(end.format("%H:%M").to_string());
+        Ok(())
+    }
+
     pub fn project(&self, id: &str) -> Option<&Project> {
         self.header.projects.iter().find(|project| project.id == id)
     }
@@ -156,6 +223,57 @@ impl Ledger {
             .find(|category| category.id == id)
     }
 
+    /// Tasks matching `query`, best tier first: exact ID, ID prefix, exact
+    /// title, words found in "project + description", then a fuzzy
+    /// subsequence match. Only the first tier with hits is returned, and name
+    /// based tiers skip archived tasks.
+    pub fn match_tasks(&self, query: &str) -> Vec<String> {
+        rank_matches(
+            query,
+            self.header.tasks.iter().map(|task| {
+                let project_name = self
+                    .project(&task.project_id)
+                    .map(|project| project.name.as_str())
+                    .unwrap_or_default();
+                MatchCandidate {
+                    id: &task.id,
+                    name: task.short_description(),
+                    haystack: format!("{project_name} {}", task.description),
+                    archived: task.archived,
+                }
+            }),
+        )
+    }
+
+    /// Projects matching `query`, ranked like [`Ledger::match_tasks`].
+    pub fn match_projects(&self, query: &str) -> Vec<String> {
+        rank_matches(
+            query,
+            self.header.projects.iter().map(|project| MatchCandidate {
+                id: &project.id,
+                name: project.name.clone(),
+                haystack: project.name.clone(),
+                archived: project.archived,
+            }),
+        )
+    }
+
+    /// Categories matching `query`, ranked like [`Ledger::match_tasks`].
+    pub fn match_categories(&self, query: &str) -> Vec<String> {
+        rank_matches(
+            query,
+            self.header
+                .categories
+                .iter()
+                .map(|category| MatchCandidate {
+                    id: &category.id,
+                    name: category.name.clone(),
+                    haystack: category.name.clone(),
+                    archived: category.archived,
+                }),
+        )
+    }
+
     pub fn add_project(&mut self, name: String, color: Option<String>) -> String {
         let id = generate_id();
         self.header.projects.push(Project {
@@ -188,10 +306,10 @@ impl Ledger {
             return Err(format!("project not found: {project_id}"));
         }
 
-        if let Some(category_id) = &category_id {
-            if self.category(category_id).is_none() {
-                return Err(format!("category not found: {category_id}"));
-            }
+        if let Some(category_id) = &category_id
+            && self.category(category_id).is_none()
+        {
+            return Err(format!("category not found: {category_id}"));
         }
 
         let id = generate_id();
@@ -250,53 +368,564 @@ impl Ledger {
         Ok(())
     }
 
+    /// Copies projects, categories and tasks from `source` with their IDs
+    /// kept, so sessions in both ledgers refer to the same entities. Copying
+    /// a project brings its non-archived tasks, and every copied task brings
+    /// its project and category. An empty selection copies all non-archived
+    /// entities. Entities whose ID already exists here are left as they are.
+    pub fn copy_entities_from(
+        &mut self,
+        source: &Ledger,
+        selection: &EntitySelection,
+    ) -> Result<CopyReport, String> {
+        for id in &selection.projects {
+            source
+                .project(id)
+                .ok_or_else(|| format!("project not found: {id}"))?;
+        }
+        for id in &selection.categories {
+            source
+                .category(id)
+                .ok_or_else(|| format!("category not found: {id}"))?;
+        }
+        for id in &selection.tasks {
+            source
+                .task(id)
+                .ok_or_else(|| format!("task not found: {id}"))?;
+        }
+
+        let copy_all = selection.is_empty();
+        let tasks = source
+            .header
+            .tasks
+            .iter()
+            .filter(|task| {
+                if copy_all {
+                    !task.archived
+                } else {
+                    selection.tasks.contains(&task.id)
+                        || (!task.archived && selection.projects.contains(&task.project_id))
+                }
+            })
+            .collect::<Vec<_>>();
+        let wants_project = |project: &Project| {
+            (if copy_all {
+                !project.archived
+            } else {
+                selection.projects.contains(&project.id)
+            }) || tasks.iter().any(|task| task.project_id == project.id)
+        };
+        let wants_category = |category: &Category| {
+            (if copy_all {
+                !category.archived
+            } else {
+                selection.categories.contains(&category.id)
+            }) || tasks
+                .iter()
+                .any(|task| task.category_id.as_deref() == Some(category.id.as_str()))
+        };
+
+        let mut report = CopyReport::default();
+        for project in source.header.projects.iter().filter(|p| wants_project(p)) {
+            if self.project(&project.id).is_some() {
+                report.already_present += 1;
+            } else {
+                self.header.projects.push(project.clone());
+                report.projects.push(project.name.clone());
+            }
+        }
+        for category in source
+            .header
+            .categories
+            .iter()
+            .filter(|c| wants_category(c))
+        {
+            if self.category(&category.id).is_some() {
+                report.already_present += 1;
+            } else {
+                self.header.categories.push(category.clone());
+                report.categories.push(category.name.clone());
+            }
+        }
+        for task in tasks {
+            if self.task(&task.id).is_some() {
+                report.already_present += 1;
+                continue;
+            }
+            self.header.tasks.push(task.clone());
+            let project = source
+                .project(&task.project_id)
+                .map(|project| project.name.as_str())
+                .unwrap_or_default();
+            report
+                .tasks
+                .push(format!("{project} · {}", task.short_description()));
+        }
+
+        Ok(report)
+    }
+
+    /// Moves every closed session that started before `cutoff` into a new
+    /// ledger, which gets the tasks those sessions use (with their projects
+    /// and categories) and this ledger's day settings. Sessions that cross
+    /// the cutoff are split there, each part keeping its notes. Running
+    /// sessions and all entities stay here.
+    pub fn split_off_before(
+        &mut self,
+        cutoff: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<(Ledger, ArchiveReport), String> {
+        let mut archive = Ledger::new();
+        archive.header.day_start_offset_hours = self.header.day_start_offset_hours;
+        archive.header.work_day_start = self.header.work_day_start.clone();
+        archive.header.work_day_end = self.header.work_day_end.clone();
+
+        let mut report = ArchiveReport::default();
+        let mut moved = vec![false; self.events.len()];
+        let mut kept_events = self.events.clone();
+        let mut archived_events = Vec::new();
+        for session in self.sessions(now) {
+            let Some(stop_index) = session.stop_event_index else {
+                continue;
+            };
+            if session.start >= cutoff {
+                continue;
+            }
+            let start_event = self.events[session.start_event_index].clone();
+            if session.stop <= cutoff {
+                moved[session.start_event_index] = true;
+                moved[stop_index] = true;
+                archived_events.push((session.start_event_index, start_event));
+                archived_events.push((stop_index, self.events[stop_index].clone()));
+                report.sessions_moved += 1;
+            } else {
+                archived_events.push((session.start_event_index, start_event));
+                archived_events.push((
+                    stop_index,
+                    TimeEvent::stop(session.task_id.clone(), cutoff, None),
+                ));
+                kept_events[session.start_event_index].timestamp = cutoff;
+                report.sessions_split += 1;
+            }
+        }
+
+        let selection = EntitySelection {
+            tasks: archived_events
+                .iter()
+                .filter_map(|(_, event)| match &event.kind {
+                    EventKind::Start { task_id, .. } => Some(task_id.clone()),
+                    EventKind::Stop { .. } => None,
+                })
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+            ..EntitySelection::default()
+        };
+        archive.copy_entities_from(self, &selection)?;
+
+        // Keep the original event order in both files.
+        archived_events.sort_by_key(|(index, _)| *index);
+        archive.events = archived_events
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect();
+        self.events = kept_events
+            .into_iter()
+            .zip(moved)
+            .filter(|(_, moved)| !moved)
+            .map(|(event, _)| event)
+            .collect();
+        Ok((archive, report))
+    }
+
+    /// Adds the sessions of `other` for combined reporting across ledgers.
+    /// Entities are joined by ID, else by name (tasks by project and first
+    /// description line), else added. Sessions already present with the
+    /// same task, start and end are skipped, so overlapping copies of a
+    /// ledger count once. Day settings stay those of `self`.
+    pub fn merge_from(&mut self, other: &Ledger, now: DateTime<Utc>) {
+        let mut project_ids = HashMap::new();
+        for project in &other.header.projects {
+            let id = self
+                .project(&project.id)
+                .or_else(|| {
+                    self.header
+                        .projects
+                        .iter()
+                        .find(|existing| existing.name.eq_ignore_ascii_case(&project.name))
+                })
+                .map(|existing| existing.id.clone())
+                .unwrap_or_else(|| {
+                    self.header.projects.push(project.clone());
+                    project.id.clone()
+                });
+            project_ids.insert(project.id.as_str(), id);
+        }
+
+        let mut category_ids = HashMap::new();
+        for category in &other.header.categories {
+            let id = self
+                .category(&category.id)
+                .or_else(|| {
+                    self.header
+                        .categories
+                        .iter()
+                        .find(|existing| existing.name.eq_ignore_ascii_case(&category.name))
+                })
+                .map(|existing| existing.id.clone())
+                .unwrap_or_else(|| {
+                    self.header.categories.push(category.clone());
+                    category.id.clone()
+                });
+            category_ids.insert(category.id.as_str(), id);
+        }
+
+        let mut task_ids = HashMap::new();
+        for task in &other.header.tasks {
+            let project_id = project_ids
+                .get(task.project_id.as_str())
+                .cloned()
+                .unwrap_or_else(|| task.project_id.clone());
+            let title = task.short_description();
+            let id = self
+                .task(&task.id)
+                .or_else(|| {
+                    self.header.tasks.iter().find(|existing| {
+                        existing.project_id == project_id
+                            && existing.short_description().eq_ignore_ascii_case(&title)
+                    })
+                })
+                .map(|existing| existing.id.clone())
+                .unwrap_or_else(|| {
+                    let category_id = task.category_id.as_ref().map(|id| {
+                        category_ids
+                            .get(id.as_str())
+                            .cloned()
+                            .unwrap_or_else(|| id.clone())
+                    });
+                    self.header.tasks.push(Task {
+                        project_id,
+                        category_id,
+                        ..task.clone()
+                    });
+                    task.id.clone()
+                });
+            task_ids.insert(task.id.as_str(), id);
+        }
+
+        let mut existing = self
+            .sessions(now)
+            .into_iter()
+            .map(|session| {
+                let stop = (!session.is_running()).then_some(session.stop);
+                (session.task_id, session.start, stop)
+            })
+            .collect::<HashSet<_>>();
+        for session in other.sessions(now) {
+            let stop = (!session.is_running()).then_some(session.stop);
+            let task_id = task_ids
+                .get(session.task_id.as_str())
+                .cloned()
+                .unwrap_or(session.task_id);
+            if !existing.insert((task_id.clone(), session.start, stop)) {
+                continue;
+            }
+            self.events.push(TimeEvent::start(
+                task_id.clone(),
+                session.start,
+                session.note,
+            ));
+            if let Some(stop) = stop {
+                self.events.push(TimeEvent::stop(task_id, stop, None));
+            }
+        }
+    }
+
+    /// Starts `task_id` at `timestamp`, which may be in the past but not
+    /// later than `now`.
     pub fn start_task(
         &mut self,
         task_id: &str,
         timestamp: DateTime<Utc>,
         note: Option<String>,
+        now: DateTime<Utc>,
     ) -> Result<(), String> {
-        let task = self
-            .task(task_id)
-            .ok_or_else(|| format!("task not found: {task_id}"))?;
+        let change = SessionChange::Start {
+            start: timestamp,
+            note,
+        };
+        self.record_session(task_id, change, now)
+    }
+
+    pub fn stop_task(
+        &mut self,
+        task_id: &str,
+        timestamp: DateTime<Utc>,
+        note: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let change = SessionChange::Stop {
+            at: timestamp,
+            note,
+        };
+        self.record_session(task_id, change, now)
+    }
 
-        if task.archived {
-            return Err(format!("task is archived: {task_id}"));
+    /// Stops every running task at `timestamp` and returns their IDs, oldest
+    /// session first.
+    pub fn stop_all(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<String>, String> {
+        let running = self
+            .status(timestamp)
+            .running
+            .into_iter()
+            .map(|session| session.task_id)
+            .collect::<Vec<_>>();
+        let mut updated = self.clone();
+        for task_id in &running {
+            updated.stop_task(task_id, timestamp, None, now)?;
         }
+        *self = updated;
+        Ok(running)
+    }
 
-        let snapshot = self.snapshot(timestamp);
-        if snapshot.active_tasks.contains_key(task_id) {
-            return Err(format!("task already running: {task_id}"));
+    /// Stops everything else that is running and starts `task_id` at the
+    /// same instant. A task that is already running keeps its session.
+    /// Returns the IDs of the stopped tasks.
+    pub fn switch_to(
+        &mut self,
+        task_id: &str,
+        timestamp: DateTime<Utc>,
+        note: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<String>, String> {
+        let mut updated = self.clone();
+        let mut stopped = Vec::new();
+        let mut already_running = false;
+        for session in self.status(timestamp).running {
+            if session.task_id == task_id {
+                already_running = true;
+                continue;
+            }
+            updated.stop_task(&session.task_id, timestamp, None, now)?;
+            stopped.push(session.task_id);
         }
+        if !already_running {
+            updated.start_task(task_id, timestamp, note, now)?;
+        }
+        *self = updated;
+        Ok(stopped)
+    }
 
-        self.events
-            .push(TimeEvent::start(task_id.to_string(), timestamp, note));
-        Ok(())
+    /// The task whose session ended most recently and is not running again,
+    /// with the note of that session, for `resume`.
+    pub fn last_stopped_session(&self) -> Option<(String, Option<String>)> {
+        let mut order = (0..self.events.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&index| self.events[index].replay_key());
+
+        let mut open_notes: HashMap<&str, Option<&String>> = HashMap::new();
+        let mut stopped = Vec::new();
+        for index in order {
+            match &self.events[index].kind {
+                EventKind::Start { task_id, note } => {
+                    open_notes.insert(task_id, note.as_ref());
+                }
+                EventKind::Stop { task_id, .. } => {
+                    if let Some(note) = open_notes.remove(task_id.as_str()) {
+                        stopped.push((task_id.as_str(), note));
+                    }
+                }
+            }
+        }
+        // Whatever is still open at the end is running right now.
+        stopped
+            .into_iter()
+            .rev()
+            .find(|(task_id, _)| !open_notes.contains_key(task_id))
+            .map(|(task_id, note)| (task_id.to_string(), note.cloned()))
     }
 
-    pub fn stop_task(
+    /// Records a finished session retroactively. Unlike `start_task` this
+    /// works while the task is running later on, as long as the new session
+    /// does not overlap another session of the same task.
+    pub fn log_session(
         &mut self,
         task_id: &str,
-        timestamp: DateTime<Utc>,
+        start: DateTime<Utc>,
+        stop: DateTime<Utc>,
         note: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let change = SessionChange::Log { start, stop, note };
+        self.record_session(task_id, change, now)
+    }
+
+    fn record_session(
+        &mut self,
+        task_id: &str,
+        change: SessionChange,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let mut batch = SessionBatch::new(self);
+        batch.record(self, task_id, change, now)?;
+        batch.commit(self);
+        Ok(())
+    }
+
+    /// Returns the index of the stop event that closes the session opened by
+    /// `start_index`, or `None` while that session is still running.
+    pub fn session_stop_index(&self, start_index: usize) -> Option<usize> {
+        let task_id = match self.events.get(start_index).map(|event| &event.kind) {
+            Some(EventKind::Start { task_id, .. }) => task_id,
+            _ => return None,
+        };
+        let order = task_event_order(&self.events, task_id);
+        let position = order.iter().position(|index| *index == start_index)?;
+        order
+            .get(position + 1)
+            .copied()
+            .filter(|index| matches!(self.events[*index].kind, EventKind::Stop { .. }))
+    }
+
+    /// Splits the session opened by `start_index` at `at`. The first half
+    /// keeps the original start event and note. The second half runs from
+    /// `at` to the original stop (keeping the stop note), or stays running,
+    /// and is booked on `second_task_id` when given. A same-task split copies
+    /// the start note so both halves keep their context.
+    pub fn split_session(
+        &mut self,
+        start_index: usize,
+        at: DateTime<Utc>,
+        second_task_id: Option<&str>,
+    ) -> Result<(), String> {
+        let (task_id, note) = match self.events.get(start_index).map(|event| &event.kind) {
+            Some(EventKind::Start { task_id, note }) => (task_id.clone(), note.clone()),
+            _ => return Err("session start event not found".to_string()),
+        };
+        if at <= self.events[start_index].timestamp {
+            return Err("split time must be after the session start".to_string());
+        }
+        let stop_index = self.session_stop_index(start_index);
+        if let Some(stop_index) = stop_index
+            && at >= self.events[stop_index].timestamp
+        {
+            return Err("split time must be before the session end".to_string());
+        }
+
+        let second_task_id = second_task_id.unwrap_or(&task_id).to_string();
+        let second_task = self
+            .task(&second_task_id)
+            .ok_or_else(|| format!("task not found: {second_task_id}"))?;
+        if second_task.archived && second_task_id != task_id {
+            return Err(format!("task is archived: {second_task_id}"));
+        }
+        let second_note = if second_task_id == task_id {
+            note
+        } else {
+            None
+        };
+
+        let mut events = self.events.clone();
+        if let Some(stop_index) = stop_index
+            && let EventKind::Stop {
+                task_id: stop_task_id,
+                ..
+            } = &mut events[stop_index].kind
+        {
+            *stop_task_id = second_task_id.clone();
+        }
+        events.push(TimeEvent::stop(task_id.clone(), at, None));
+        events.push(TimeEvent::start(second_task_id.clone(), at, second_note));
+
+        let mut changed = vec![events.len() - 2, events.len() - 1];
+        changed.extend(stop_index);
+        ensure_alternating_around(&events, &task_id, &changed)?;
+        ensure_alternating_around(&events, &second_task_id, &changed)?;
+        self.events = events;
+        Ok(())
+    }
+
+    /// Fuses two consecutive sessions of the same task into one that runs
+    /// from the earlier start to the later stop. Start notes and stop notes
+    /// of both sessions are joined, so nothing typed into either is lost.
+    /// Sessions more than `MERGE_GAP_TOLERANCE` apart are refused, since the
+    /// merge would book the gap between them as tracked time.
+    pub fn merge_sessions(
+        &mut self,
+        first_start_index: usize,
+        second_start_index: usize,
     ) -> Result<(), String> {
-        if self.task(task_id).is_none() {
-            return Err(format!("task not found: {task_id}"));
+        let task_id = match (
+            self.events.get(first_start_index).map(|event| &event.kind),
+            self.events.get(second_start_index).map(|event| &event.kind),
+        ) {
+            (
+                Some(EventKind::Start { task_id: first, .. }),
+                Some(EventKind::Start {
+                    task_id: second, ..
+                }),
+            ) if first == second && first_start_index != second_start_index => first.clone(),
+            (Some(EventKind::Start { .. }), Some(EventKind::Start { .. })) => {
+                return Err("only two sessions of the same task can be merged".to_string());
+            }
+            _ => return Err("session start event not found".to_string()),
+        };
+
+        let order = task_event_order(&self.events, &task_id);
+        let position_of = |index: usize| order.iter().position(|candidate| *candidate == index);
+        let (Some(first_position), Some(second_position)) = (
+            position_of(first_start_index),
+            position_of(second_start_index),
+        ) else {
+            return Err("session start event not found".to_string());
+        };
+        let (earlier_position, later_position) = if first_position < second_position {
+            (first_position, second_position)
+        } else {
+            (second_position, first_position)
+        };
+        if later_position != earlier_position + 2 {
+            return Err("sessions must be consecutive sessions of the task".to_string());
+        }
+
+        let earlier_start = order[earlier_position];
+        let earlier_stop = order[earlier_position + 1];
+        let later_start = order[later_position];
+        let later_stop = self.session_stop_index(later_start);
+        let gap = self.events[later_start].timestamp - self.events[earlier_stop].timestamp;
+        if gap > MERGE_GAP_TOLERANCE {
+            return Err(format!(
+                "sessions are {} apart; only adjacent sessions can be merged",
+                format_duration(gap)
+            ));
         }
 
-        let snapshot = self.snapshot(timestamp);
-        if !snapshot.active_tasks.contains_key(task_id) {
-            return Err(format!("task is not running: {task_id}"));
+        let earlier_stop_note = event_note(&self.events[earlier_stop]);
+        let later_start_note = event_note(&self.events[later_start]);
+        let mut start_note = join_notes(event_note(&self.events[earlier_start]), later_start_note);
+        match later_stop {
+            Some(later_stop) => {
+                let stop_note = join_notes(earlier_stop_note, event_note(&self.events[later_stop]));
+                set_event_note(&mut self.events[later_stop], stop_note);
+            }
+            None => start_note = join_notes(start_note, earlier_stop_note),
         }
+        set_event_note(&mut self.events[earlier_start], start_note);
 
-        self.events
-            .push(TimeEvent::stop(task_id.to_string(), timestamp, note));
+        let mut removed = [earlier_stop, later_start];
+        removed.sort_unstable_by(|left, right| right.cmp(left));
+        for index in removed {
+            self.events.remove(index);
+        }
         Ok(())
     }
 
     pub fn snapshot(&self, now: DateTime<Utc>) -> LedgerSnapshot {
         let mut events = self.events.clone();
-        events.sort_by_key(|event| event.timestamp);
+        events.sort_by_key(TimeEvent::replay_key);
 
         let mut active_tasks: HashMap<String, ActiveSession> = HashMap::new();
         let mut daily_task_totals: BTreeMap<NaiveDate, HashMap<String, Duration>> = BTreeMap::new();
@@ -341,6 +970,303 @@ impl Ledger {
             daily_task_totals,
         }
     }
+
+    /// Running sessions (oldest first) and the time tracked so far in the
+    /// ledger day containing `now`, summed per task like `totals_for_day`.
+    pub fn status(&self, now: DateTime<Utc>) -> LedgerStatus {
+        let (day_start, day_end) = self.day_bounds_utc(self.day_for_timestamp(now));
+        let window_end = day_end.min(now);
+        let overlap = |start: DateTime<Utc>, stop: DateTime<Utc>| {
+            (stop.min(window_end) - start.max(day_start)).max(Duration::zero())
+        };
+
+        let mut events = self.events.iter().collect::<Vec<_>>();
+        events.sort_by_key(|event| event.replay_key());
+
+        let mut active: HashMap<&str, (DateTime<Utc>, Option<&String>)> = HashMap::new();
+        let mut today_total = Duration::zero();
+        for event in events {
+            match &event.kind {
+                EventKind::Start { task_id, note } => {
+                    active.insert(task_id, (event.timestamp, note.as_ref()));
+                }
+                EventKind::Stop { task_id, .. } => {
+                    if let Some((started_at, _)) = active.remove(task_id.as_str()) {
+                        today_total += overlap(started_at, event.timestamp);
+                    }
+                }
+            }
+        }
+
+        let mut running = active
+            .into_iter()
+            .map(|(task_id, (started_at, note))| {
+                today_total += overlap(started_at, now);
+                RunningSession {
+                    task_id: task_id.to_string(),
+                    started_at,
+                    note: note.cloned(),
+                }
+            })
+            .collect::<Vec<_>>();
+        running.sort_by(|left, right| {
+            left.started_at
+                .cmp(&right.started_at)
+                .then_with(|| left.task_id.cmp(&right.task_id))
+        });
+        LedgerStatus {
+            running,
+            today_total,
+        }
+    }
+
+    /// Pairs start/stop events per task into sessions, replaying in
+    /// `TimeEvent::replay_key` order. A stop without a matching start is ignored, a second
+    /// start replaces the open one, and tasks still running end at `now`.
+    pub fn sessions(&self, now: DateTime<Utc>) -> Vec<Session> {
+        let mut indexed_events = self.events.iter().enumerate().collect::<Vec<_>>();
+        indexed_events.sort_by_key(|(_, event)| event.replay_key());
+
+        let mut active: HashMap<&str, (DateTime<Utc>, Option<&String>, usize)> = HashMap::new();
+        let mut sessions = Vec::new();
+
+        for (index, event) in indexed_events {
+            match &event.kind {
+                EventKind::Start { task_id, note } => {
+                    active.insert(task_id, (event.timestamp, note.as_ref(), index));
+                }
+                EventKind::Stop { task_id, .. } => {
+                    if let Some((start, note, start_event_index)) = active.remove(task_id.as_str())
+                        && event.timestamp > start
+                    {
+                        sessions.push(Session {
+                            task_id: task_id.clone(),
+                            start,
+                            stop: event.timestamp,
+                            note: note.cloned(),
+                            start_event_index,
+                            stop_event_index: Some(index),
+                        });
+                    }
+                }
+            }
+        }
+
+        for (task_id, (start, note, start_event_index)) in active {
+            if now > start {
+                sessions.push(Session {
+                    task_id: task_id.to_string(),
+                    start,
+                    stop: now,
+                    note: note.cloned(),
+                    start_event_index,
+                    stop_event_index: None,
+                });
+            }
+        }
+
+        sessions
+    }
+}
+
+/// Checks many session changes against one ledger, e.g. for an import.
+/// Every task's history is sorted once up front and each accepted change is
+/// merged into it, so a change is validated against the ledger plus the
+/// changes accepted before it without cloning or re-sorting the events.
+/// Nothing reaches the ledger until `commit`.
+pub struct SessionBatch {
+    timelines: HashMap<String, Vec<(DateTime<Utc>, bool)>>,
+    added: Vec<TimeEvent>,
+}
+
+impl SessionBatch {
+    pub fn new(ledger: &Ledger) -> Self {
+        let mut timelines = HashMap::<String, Vec<(DateTime<Utc>, bool)>>::new();
+        for event in &ledger.events {
+            let (EventKind::Start { task_id, .. } | EventKind::Stop { task_id, .. }) = &event.kind;
+            timelines
+                .entry(task_id.clone())
+                .or_default()
+                .push(event.replay_key());
+        }
+        for timeline in timelines.values_mut() {
+            timeline.sort_unstable();
+        }
+        Self {
+            timelines,
+            added: Vec::new(),
+        }
+    }
+
+    /// Queues `change` with the checks and errors of `start_task`,
+    /// `stop_task` or `log_session`; a rejected change leaves no trace.
+    pub fn record(
+        &mut self,
+        ledger: &Ledger,
+        task_id: &str,
+        change: SessionChange,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let timeline = self.timelines.entry(task_id.to_string()).or_default();
+        let events = Self::check(ledger, task_id, change, timeline, now)?;
+        self.added.extend(events);
+        Ok(())
+    }
+
+    /// Appends the accepted changes to `ledger`, which must be the ledger
+    /// the batch was created from.
+    pub fn commit(self, ledger: &mut Ledger) {
+        ledger.events.extend(self.added);
+    }
+
+    /// Validates `change` and returns its events. `timeline` holds the
+    /// task's replay keys in order and gains the new ones on success.
+    fn check(
+        ledger: &Ledger,
+        task_id: &str,
+        change: SessionChange,
+        timeline: &mut Vec<(DateTime<Utc>, bool)>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<TimeEvent>, String> {
+        let task = ledger
+            .task(task_id)
+            .ok_or_else(|| format!("task not found: {task_id}"))?;
+        // Replay lets the task's last event decide whether it is running.
+        let running = timeline.last().is_some_and(|(_, is_start)| *is_start);
+        let events = match change {
+            SessionChange::Start { start, note } => {
+                if task.archived {
+                    return Err(format!("task is archived: {task_id}"));
+                }
+                ensure_not_in_future(start, now, "start")?;
+                if running {
+                    return Err(format!("task already running: {task_id}"));
+                }
+                vec![TimeEvent::start(task_id, start, note)]
+            }
+            SessionChange::Stop { at, note } => {
+                ensure_not_in_future(at, now, "stop")?;
+                if !running {
+                    return Err(format!("task is not running: {task_id}"));
+                }
+                vec![TimeEvent::stop(task_id, at, note)]
+            }
+            SessionChange::Log { start, stop, note } => {
+                if task.archived {
+                    return Err(format!("task is archived: {task_id}"));
+                }
+                if stop <= start {
+                    return Err("session end must be after its start".to_string());
+                }
+                ensure_not_in_future(stop, now, "session end")?;
+                vec![
+                    TimeEvent::start(task_id, start, note),
+                    TimeEvent::stop(task_id, stop, None),
+                ]
+            }
+        };
+
+        // New events replay after existing ones with the same key, and the
+        // events of one change are in order, so earlier positions stay put.
+        let positions = events
+            .iter()
+            .map(|event| {
+                let key = event.replay_key();
+                let position = timeline.partition_point(|entry| *entry <= key);
+                timeline.insert(position, key);
+                position
+            })
+            .collect::<Vec<_>>();
+        let alternates = positions.iter().all(|&position| {
+            let is_start = timeline[position].1;
+            [position.checked_sub(1), Some(position + 1)]
+                .into_iter()
+                .flatten()
+                .filter_map(|neighbour| timeline.get(neighbour))
+                .all(|(_, neighbour_is_start)| *neighbour_is_start != is_start)
+        });
+        if !alternates {
+            for position in positions.into_iter().rev() {
+                timeline.remove(position);
+            }
+            return Err(format!(
+                "change would overlap another session of task {task_id}"
+            ));
+        }
+        Ok(events)
+    }
+}
+
+/// One tracked interval. `stop_event_index` is `None` while the session is
+/// still running, in which case `stop` is the time it was collected at.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub task_id: String,
+    pub start: DateTime<Utc>,
+    pub stop: DateTime<Utc>,
+    pub note: Option<String>,
+    pub start_event_index: usize,
+    pub stop_event_index: Option<usize>,
+}
+
+impl Session {
+    pub fn is_running(&self) -> bool {
+        self.stop_event_index.is_none()
+    }
+}
+
+/// Entity IDs to copy between ledgers; empty copies every non-archived
+/// entity.
+#[derive(Debug, Clone, Default)]
+pub struct EntitySelection {
+    pub projects: Vec<String>,
+    pub categories: Vec<String>,
+    pub tasks: Vec<String>,
+}
+
+impl EntitySelection {
+    pub fn is_empty(&self) -> bool {
+        self.projects.is_empty() && self.categories.is_empty() && self.tasks.is_empty()
+    }
+}
+
+/// Names of the entities [`Ledger::copy_entities_from`] added, and how many
+/// were already there.
+#[derive(Debug, Clone, Default)]
+pub struct CopyReport {
+    pub projects: Vec<String>,
+    pub categories: Vec<String>,
+    pub tasks: Vec<String>,
+    pub already_present: usize,
+}
+
+impl CopyReport {
+    pub fn copied(&self) -> usize {
+        self.projects.len() + self.categories.len() + self.tasks.len()
+    }
+}
+
+/// How many sessions [`Ledger::split_off_before`] moved whole and how many
+/// it split at the cutoff.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ArchiveReport {
+    pub sessions_moved: usize,
+    pub sessions_split: usize,
+}
+
+/// What is running right now and how much was tracked in the current ledger
+/// day. Cheaper than a full [`LedgerSnapshot`], for status bars that poll.
+#[derive(Debug, Clone)]
+pub struct LedgerStatus {
+    pub running: Vec<RunningSession>,
+    pub today_total: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct RunningSession {
+    pub task_id: String,
+    pub started_at: DateTime<Utc>,
+    pub note: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -449,6 +1375,135 @@ fn local_naive_to_utc_resolved(naive: NaiveDateTime) -> DateTime<Utc> {
     panic!("local day boundary does not exist");
 }
 
+/// Indices of one task's events in replay order (timestamp, then position).
+fn task_event_order(events: &[TimeEvent], task_id: &str) -> Vec<usize> {
+    let mut order = events
+        .iter()
+        .enumerate()
+        .filter(|(_, event)| match &event.kind {
+            EventKind::Start { task_id: id, .. } | EventKind::Stop { task_id: id, .. } => {
+                id == task_id
+            }
+        })
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    order.sort_by_key(|index| events[*index].replay_key());
+    order
+}
+
+struct MatchCandidate<'a> {
+    id: &'a str,
+    name: String,
+    haystack: String,
+    archived: bool,
+}
+
+fn rank_matches<'a>(
+    query: &str,
+    candidates: impl Iterator<Item = MatchCandidate<'a>>,
+) -> Vec<String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let lowered = query.to_lowercase();
+    let words = lowered.split_whitespace().collect::<Vec<_>>();
+    let mut tiers: [Vec<String>; 5] = Default::default();
+    for candidate in candidates {
+        let name = candidate.name.to_lowercase();
+        let haystack = candidate.haystack.to_lowercase();
+        let tier = if candidate.id == query {
+            0
+        } else if candidate.id.starts_with(query) {
+            1
+        } else if candidate.archived {
+            continue;
+        } else if name == lowered {
+            2
+        } else if words.iter().all(|word| haystack.contains(word)) {
+            3
+        } else if is_subsequence(&lowered.replace(' ', ""), &haystack) {
+            4
+        } else {
+            continue;
+        };
+        tiers[tier].push(candidate.id.to_string());
+    }
+    tiers
+        .into_iter()
+        .find(|tier| !tier.is_empty())
+        .unwrap_or_default()
+}
+
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle.chars().all(|wanted| haystack.any(|ch| ch == wanted))
+}
+
+fn ensure_not_in_future(
+    timestamp: DateTime<Utc>,
+    now: DateTime<Utc>,
+    what: &str,
+) -> Result<(), String> {
+    if timestamp > now {
+        return Err(format!("{what} cannot be later than current time"));
+    }
+    Ok(())
+}
+
+/// Checks that each of `changed` that belongs to `task_id` sits between
+/// events of the other kind in the task's replay order. Only the neighbours
+/// are checked, so an orphan stop or duplicate start left elsewhere by a sync
+/// conflict or a hand edit doesn't block new changes.
+fn ensure_alternating_around(
+    events: &[TimeEvent],
+    task_id: &str,
+    changed: &[usize],
+) -> Result<(), String> {
+    let order = task_event_order(events, task_id);
+    let is_start = |index: usize| matches!(events[index].kind, EventKind::Start { .. });
+    for &index in changed {
+        let Some(position) = order.iter().position(|candidate| *candidate == index) else {
+            continue;
+        };
+        let neighbours = [
+            position.checked_sub(1).map(|previous| order[previous]),
+            order.get(position + 1).copied(),
+        ];
+        if neighbours
+            .into_iter()
+            .flatten()
+            .any(|neighbour| is_start(neighbour) == is_start(index))
+        {
+            return Err(format!(
+                "change would overlap another session of task {task_id}"
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn event_note(event: &TimeEvent) -> Option<String> {
+    match &event.kind {
+        EventKind::Start { note, .. } | EventKind::Stop { note, .. } => note.clone(),
+    }
+}
+
+fn set_event_note(event: &mut TimeEvent, value: Option<String>) {
+    match &mut event.kind {
+        EventKind::Start { note, .. } | EventKind::Stop { note, .. } => *note = value,
+    }
+}
+
+fn join_notes(first: Option<String>, second: Option<String>) -> Option<String> {
+    match (first, second) {
+        (Some(first), Some(second)) if first == second => Some(first),
+        (Some(first), Some(second)) => Some(format!("{first}\n{second}")),
+        (first, None) => first,
+        (None, second) => second,
+    }
+}
+
 pub fn generate_id() -> String {
     thread_rng()
         .sample_iter(&Alphanumeric)
@@ -467,9 +1522,30 @@ pub fn format_duration(duration: Duration) -> String {
 
 #[cfg(test)]
 mod tests {
-    use chrono::{TimeZone, Utc};
+    use chrono::{DateTime, Duration, NaiveTime, TimeZone, Utc};
+
+    use super::{ArchiveReport, EntitySelection, EventKind, Ledger, TimeEvent, format_duration};
 
-    use super::{Ledger, format_duration};
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 5, hour, minute, 0).unwrap()
+    }
+
+    /// Fixed clock for domain calls, later than every fixture.
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap()
+    }
+
+    fn notes(ledger: &Ledger) -> Vec<(String, Option<String>)> {
+        let mut events = ledger.events.clone();
+        events.sort_by_key(TimeEvent::replay_key);
+        events
+            .into_iter()
+            .map(|event| match event.kind {
+                EventKind::Start { task_id, note } => (format!("start {task_id}"), note),
+                EventKind::Stop { task_id, note } => (format!("stop {task_id}"), note),
+            })
+            .collect()
+    }
 
     #[test]
     fn computes_parallel_time_independently() {
@@ -481,12 +1557,14 @@ mod tests {
         let task_b = ledger
             .add_task(project, None, "Task B".to_string())
             .expect("task should be created");
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 10, 30, 0).unwrap();
 
         ledger
             .start_task(
                 &task_a,
                 Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap(),
                 None,
+                now,
             )
             .expect("start should work");
         ledger
@@ -494,6 +1572,7 @@ mod tests {
                 &task_b,
                 Utc.with_ymd_and_hms(2026, 1, 1, 9, 30, 0).unwrap(),
                 None,
+                now,
             )
             .expect("start should work");
         ledger
@@ -501,6 +1580,7 @@ mod tests {
                 &task_a,
                 Utc.with_ymd_and_hms(2026, 1, 1, 10, 0, 0).unwrap(),
                 None,
+                now,
             )
             .expect("stop should work");
         ledger
@@ -508,10 +1588,10 @@ mod tests {
                 &task_b,
                 Utc.with_ymd_and_hms(2026, 1, 1, 10, 30, 0).unwrap(),
                 None,
+                now,
             )
             .expect("stop should work");
 
-        let now = Utc.with_ymd_and_hms(2026, 1, 1, 10, 30, 0).unwrap();
         let snapshot = ledger.snapshot(now);
         let day = ledger.day_for_timestamp(now);
         let task_totals = snapshot.totals_for_day(day);
@@ -551,6 +1631,7 @@ mod tests {
                 &task_id,
                 Utc.with_ymd_and_hms(2026, 1, 2, 9, 0, 0).unwrap(),
                 None,
+                now(),
             )
             .expect("start should work");
 
@@ -587,4 +1668,619 @@ mod tests {
         assert!(err.contains("used by tasks"));
         assert!(ledger.category(&category_id).is_some());
     }
+
+    #[test]
+    fn splits_session_onto_another_task() {
+        let mut ledger = Ledger::new();
+        let project = ledger.add_project("Work".to_string(), None);
+        let task_a = ledger
+            .add_task(project.clone(), None, "Task A".to_string())
+            .expect("task should be created");
+        let task_b = ledger
+            .add_task(project, None, "Task B".to_string())
+            .expect("task should be created");
+        ledger
+            .start_task(&task_a, at(10, 0), Some("planning".to_string()), now())
+            .expect("start should work");
+        ledger
+            .stop_task(&task_a, at(12, 0), Some("done".to_string()), now())
+            .expect("stop should work");
+
+        ledger
+            .split_session(0, at(11, 0), Some(&task_b))
+            .expect("split should work");
+
+        let sessions = ledger.sessions(at(12, 0));
+        for task_id in [&task_a, &task_b] {
+            let total = sessions
+                .iter()
+                .filter(|session| &session.task_id == task_id)
+                .fold(Duration::zero(), |acc, session| {
+                    acc + (session.stop - session.start)
+                });
+            assert_eq!(format_duration(total), "01:00:00");
+        }
+        assert_eq!(
+            notes(&ledger),
+            vec![
+                (format!("start {task_a}"), Some("planning".to_string())),
+                (format!("stop {task_a}"), None),
+                (format!("start {task_b}"), None),
+                (format!("stop {task_b}"), Some("done".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_undoes_a_same_task_split() {
+        let mut ledger = Ledger::new();
+        let project = ledger.add_project("Work".to_string(), None);
+        let task_id = ledger
+            .add_task(project, None, "Draft".to_string())
+            .expect("task should be created");
+        ledger
+            .start_task(&task_id, at(10, 0), Some("outline".to_string()), now())
+            .expect("start should work");
+        ledger
+            .stop_task(&task_id, at(12, 0), None, now())
+            .expect("stop should work");
+
+        ledger
+            .split_session(0, at(11, 0), None)
+            .expect("split should work");
+        assert_eq!(ledger.events.len(), 4);
+        assert_eq!(ledger.session_stop_index(0), Some(2));
+        assert_eq!(ledger.session_stop_index(3), Some(1));
+
+        ledger.merge_sessions(3, 0).expect("merge should work");
+        assert_eq!(
+            notes(&ledger),
+            vec![
+                (format!("start {task_id}"), Some("outline".to_string())),
+                (format!("stop {task_id}"), None),
+            ]
+        );
+        assert_eq!(ledger.events[0].timestamp, at(10, 0));
+        assert_eq!(ledger.events[1].timestamp, at(12, 0));
+    }
+
+    #[test]
+    fn merge_keeps_notes_and_rejects_non_consecutive_sessions() {
+        let mut ledger = Ledger::new();
+        let project = ledger.add_project("Work".to_string(), None);
+        let task_id = ledger
+            .add_task(project, None, "Draft".to_string())
+            .expect("task should be created");
+        for (start, stop, note) in [(9, 10, "first"), (10, 11, "second"), (11, 12, "third")] {
+            ledger
+                .start_task(&task_id, at(start, 0), Some(note.to_string()), now())
+                .expect("start should work");
+            ledger
+                .stop_task(&task_id, at(stop, 0), Some(format!("{note} stop")), now())
+                .expect("stop should work");
+        }
+
+        let err = ledger.merge_sessions(0, 4).expect_err("merge should fail");
+        assert!(err.contains("consecutive"));
+
+        ledger.merge_sessions(2, 4).expect("merge should work");
+        assert_eq!(
+            notes(&ledger)[2..],
+            [
+                (
+                    format!("start {task_id}"),
+                    Some("second\nthird".to_string())
+                ),
+                (
+                    format!("stop {task_id}"),
+                    Some("second stop\nthird stop".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_refuses_sessions_with_a_gap_between_them() {
+        let mut ledger = Ledger::new();
+        let project = ledger.add_project("Work".to_string(), None);
+        let task_id = ledger
+            .add_task(project, None, "Draft".to_string())
+            .expect("task should be created");
+        ledger
+            .log_session(&task_id, at(9, 0), at(10, 0), None, now())
+            .expect("log should work");
+        ledger
+            .log_session(&task_id, at(15, 0), at(16, 0), None, now())
+            .expect("log should work");
+        ledger
+            .log_session(
+                &task_id,
+                at(16, 0) + Duration::seconds(30),
+                at(17, 0),
+                None,
+                now(),
+            )
+            .expect("log should work");
+
+        let err = ledger.merge_sessions(0, 2).expect_err("merge should fail");
+        assert_eq!(
+            err,
+            "sessions are 05:00:00 apart; only adjacent sessions can be merged"
+        );
+        assert_eq!(ledger.events.len(), 6);
+
+        ledger
+            .merge_sessions(2, 4)
+            .expect("sessions within the tolerance should merge");
+        assert_eq!(ledger.events.len(), 4);
+    }
+
+    #[test]
+    fn refuses_split_that_overlaps_the_other_task() {
+        let mut ledger = Ledger::new();
+        let project = ledger.add_project("Work".to_string(), None);
+        let task_a = ledger
+            .add_task(project.clone(), None, "Task A".to_string())
+            .expect("task should be created");
+        let task_b = ledger
+            .add_task(project, None, "Task B".to_string())
+            .expect("task should be created");
+        ledger
+            .start_task(&task_a, at(10, 0), None, now())
+            .expect("start should work");
+        ledger
+            .start_task(&task_b, at(11, 30), None, now())
+            .expect("start should work");
+        ledger
+            .stop_task(&task_a, at(12, 0), None, now())
+            .expect("stop should work");
+
+        let err = ledger
+            .split_session(0, at(11, 0), Some(&task_b))
+            .expect_err("split should fail");
+        assert!(err.contains("overlap"));
+        assert_eq!(ledger.events.len(), 3);
+    }
+
+    #[test]
+    fn logs_past_session_while_task_runs_later() {
+        let mut ledger = Ledger::new();
+        let project = ledger.add_project("Work".to_string(), None);
+        let task_id = ledger
+            .add_task(project, None, "Task".to_string())
+            .expect("task should be created");
+        ledger
+            .start_task(&task_id, at(12, 0), None, now())
+            .expect("start should work");
+
+        ledger
+            .log_session(
+                &task_id,
+                at(9, 0),
+                at(10, 0),
+                Some("gap".to_string()),
+                now(),
+            )
+            .expect("log should work");
+        let err = ledger
+            .log_session(&task_id, at(11, 30), at(12, 30), None, now())
+            .expect_err("overlapping log should fail");
+        assert!(err.contains("overlap"));
+        ledger
+            .log_session(&task_id, at(10, 0), at(12, 0), None, now())
+            .expect("touching log should work");
+        // Logged sessions are appended; replay order comes from `replay_key`.
+        assert_eq!(ledger.events[0].timestamp, at(12, 0));
+        assert_eq!(ledger.events[4].timestamp, at(12, 0));
+        assert_eq!(
+            notes(&ledger),
+            [
+                (format!("start {task_id}"), Some("gap".to_string())),
+                (format!("stop {task_id}"), None),
+                (format!("start {task_id}"), None),
+                (format!("stop {task_id}"), None),
+                (format!("start {task_id}"), None),
+            ]
+        );
+        assert!(
+            ledger
+                .snapshot(at(13, 0))
+                .active_tasks
+                .contains_key(&task_id)
+        );
+    }
+
+    #[test]
+    fn copies_entities_with_their_ids_and_dependencies() {
+        let mut old = Ledger::new();
+        let work = old.add_project("Work".to_string(), None);
+        let home = old.add_project("Home".to_string(), None);
+        let retired = old.add_project("Retired".to_string(), None);
+        let billable = old.add_category("Billable".to_string(), None);
+        let design = old
+            .add_task(work.clone(), Some(billable.clone()), "Design".to_string())
+            .expect("task should be created");
+        let done = old
+            .add_task(work.clone(), None, "Done".to_string())
+            .expect("task should be created");
+        let chores = old
+            .add_task(home.clone(), None, "Chores".to_string())
+            .expect("task should be created");
+        old.add_task(retired.clone(), None, "Old".to_string())
+            .expect("task should be created");
+        for task in &mut old.header.tasks {
+            task.archived = task.id == done || task.project_id == retired;
+        }
+        for project in &mut old.header.projects {
+            project.archived = project.id == retired;
+        }
+
+        let mut new = Ledger::new();
+        let selection = EntitySelection {
+            projects: vec![work.clone()],
+            ..EntitySelection::default()
+        };
+        let report = new
+            .copy_entities_from(&old, &selection)
+            .expect("selection should exist");
+        assert_eq!(report.projects, ["Work"]);
+        assert_eq!(report.categories, ["Billable"]);
+        assert_eq!(report.tasks, ["Work · Design"]);
+        assert_eq!(
+            new.task(&design).map(|task| task.category_id.clone()),
+            Some(Some(billable))
+        );
+
+        let report = new
+            .copy_entities_from(&old, &EntitySelection::default())
+            .expect("empty selection copies everything active");
+        assert_eq!(report.projects, ["Home"]);
+        assert_eq!(report.tasks, ["Home · Chores"]);
+        assert_eq!(report.already_present, 3);
+        assert!(new.task(&chores).is_some());
+        assert!(new.task(&done).is_none());
+        assert!(new.project(&retired).is_none());
+
+        let missing = EntitySelection {
+            tasks: vec!["nope".to_string()],
+            ..EntitySelection::default()
+        };
+        assert!(new.copy_entities_from(&old, &missing).is_err());
+    }
+
+    #[test]
+    fn splits_off_closed_sessions_before_the_cutoff() {
+        let mut ledger = Ledger::new();
+        ledger.header.day_start_offset_hours = 4;
+        let work = ledger.add_project("Work".to_string(), None);
+        let home = ledger.add_project("Home".to_string(), None);
+        let old = ledger
+            .add_task(work.clone(), None, "Old".to_string())
+            .expect("task should be created");
+        let long = ledger
+            .add_task(work, None, "Long".to_string())
+            .expect("task should be created");
+        let new = ledger
+            .add_task(home, None, "New".to_string())
+            .expect("task should be created");
+        ledger
+            .log_session(&old, at(8, 0), at(9, 0), Some("early".to_string()), now())
+            .expect("session should be logged");
+        ledger
+            .log_session(&long, at(9, 0), at(12, 0), Some("spans".to_string()), now())
+            .expect("session should be logged");
+        ledger
+            .log_session(&new, at(13, 0), at(14, 0), None, now())
+            .expect("session should be logged");
+        ledger
+            .start_task(&old, at(10, 0), None, now())
+            .expect("task should start");
+
+        let (archive, report) = ledger
+            .split_off_before(at(11, 0), at(15, 0))
+            .expect("archive should split");
+        assert_eq!(
+            report,
+            ArchiveReport {
+                sessions_moved: 1,
+                sessions_split: 1
+            }
+        );
+        assert_eq!(archive.header.day_start_offset_hours, 4);
+        assert_eq!(archive.header.projects.len(), 1);
+        assert_eq!(archive.header.tasks.len(), 2);
+        assert!(archive.task(&new).is_none());
+
+        let spans = |ledger: &Ledger| {
+            ledger
+                .sessions(at(15, 0))
+                .into_iter()
+                .map(|session| {
+                    (
+                        session.task_id.clone(),
+                        session.start,
+                        session.is_running(),
+                        session.note,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut archived = spans(&archive);
+        archived.sort_by_key(|(_, start, _, _)| *start);
+        assert_eq!(
+            archived,
+            [
+                (old.clone(), at(8, 0), false, Some("early".to_string())),
+                (long.clone(), at(9, 0), false, Some("spans".to_string())),
+            ]
+        );
+        assert_eq!(archive.sessions(at(15, 0))[1].stop, at(11, 0));
+
+        let mut kept = spans(&ledger);
+        kept.sort_by_key(|(_, start, _, _)| *start);
+        assert_eq!(
+            kept,
+            [
+                (old, at(10, 0), true, None),
+                (long, at(11, 0), false, Some("spans".to_string())),
+                (new, at(13, 0), false, None),
+            ]
+        );
+        assert_eq!(ledger.header.tasks.len(), 3);
+    }
+
+    #[test]
+    fn merges_ledgers_by_id_then_by_name() {
+        let mut first = Ledger::new();
+        let work = first.add_project("Work".to_string(), None);
+        let design = first
+            .add_task(work.clone(), None, "Design".to_string())
+            .expect("task should be created");
+        first
+            .log_session(&design, at(9, 0), at(10, 0), None, now())
+            .expect("session should be logged");
+
+        // Same IDs (a copied ledger) plus a hand-made "work" project.
+        let mut second = Ledger::new();
+        second.header.projects = first.header.projects.clone();
+        second.header.tasks = first.header.tasks.clone();
+        let renamed = second.add_project("work".to_string(), None);
+        let review = second
+            .add_task(renamed, None, "Review".to_string())
+            .expect("task should be created");
+        second
+            .log_session(&design, at(9, 0), at(10, 0), None, now())
+            .expect("session should be logged");
+        second
+            .log_session(
+                &design,
+                at(11, 0),
+                at(12, 0),
+                Some("later".to_string()),
+                now(),
+            )
+            .expect("session should be logged");
+        second
+            .start_task(&review, at(13, 0), None, now())
+            .expect("task should start");
+
+        first.merge_from(&second, at(14, 0));
+        assert_eq!(first.header.projects.len(), 1);
+        assert_eq!(first.header.tasks.len(), 2);
+        assert!(
+            first
+                .header
+                .tasks
+                .iter()
+                .all(|task| task.project_id == work)
+        );
+
+        let sessions = first.sessions(at(14, 0));
+        assert_eq!(sessions.len(), 3);
+        assert_eq!(
+            sessions
+                .iter()
+                .map(|session| session.stop - session.start)
+                .sum::<Duration>(),
+            Duration::hours(3)
+        );
+        assert!(
+            sessions
+                .iter()
+                .any(|session| session.task_id == review && session.is_running())
+        );
+    }
+
+    #[test]
+    fn working_hours_default_and_round_trip() {
+        let mut ledger = Ledger::new();
+        let time = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+        assert_eq!(ledger.working_hours(), (time(9, 0), time(17, 0)));
+
+        ledger
+            .set_working_hours(time(8, 30), time(16, 45))
+            .expect("hours should be set");
+        assert_eq!(ledger.header.work_day_start.as_deref(), Some("08:30"));
+        assert_eq!(ledger.working_hours(), (time(8, 30), time(16, 45)));
+        assert!(ledger.set_working_hours(time(9, 0), time(9, 0)).is_err());
+
+        ledger.header.work_day_end = Some("late".to_string());
+        assert_eq!(ledger.working_hours().1, time(17, 0));
+    }
+
+    #[test]
+    fn tolerates_duplicate_starts_elsewhere_in_the_history() {
+        let mut ledger = Ledger::new();
+        let project = ledger.add_project("Work".to_string(), None);
+        let task_id = ledger
+            .add_task(project, None, "Task".to_string())
+            .expect("task should be created");
+        // A sync conflict left two starts before one stop, plus an orphan stop.
+        ledger.events.extend([
+            TimeEvent::start(task_id.clone(), at(8, 0), None),
+            TimeEvent::start(task_id.clone(), at(9, 0), None),
+            TimeEvent::stop(task_id.clone(), at(10, 0), None),
+            TimeEvent::stop(task_id.clone(), at(10, 30), None),
+        ]);
+
+        ledger
+            .start_task(&task_id, at(11, 0), None, now())
+            .expect("start after the broken history should work");
+        ledger
+            .stop_task(&task_id, at(12, 0), None, now())
+            .expect("stop should work");
+        ledger
+            .log_session(&task_id, at(13, 0), at(14, 0), None, now())
+            .expect("log should work");
+        let err = ledger
+            .log_session(&task_id, at(11, 30), at(12, 30), None, now())
+            .expect_err("overlapping log should still fail");
+        assert!(err.contains("overlap"));
+        assert_eq!(ledger.events.len(), 8);
+    }
+
+    #[test]
+    fn retroactive_start_and_stop_cannot_overlap_or_reach_the_future() {
+        let mut ledger = Ledger::new();
+        let project = ledger.add_project("Work".to_string(), None);
+        let task_id = ledger
+            .add_task(project, None, "Task".to_string())
+            .expect("task should be created");
+        ledger
+            .log_session(&task_id, at(10, 0), at(11, 0), None, now())
+            .expect("log should work");
+
+        let err = ledger
+            .start_task(&task_id, at(10, 30), None, now())
+            .expect_err("start inside a session should fail");
+        assert!(err.contains("overlap"));
+        let err = ledger
+            .start_task(&task_id, now() + chrono::Duration::hours(1), None, now())
+            .expect_err("future start should fail");
+        assert!(err.contains("later than current time"));
+
+        ledger
+            .start_task(&task_id, at(11, 0), None, now())
+            .expect("start right after the session should work");
+        let err = ledger
+            .stop_task(&task_id, at(10, 45), None, now())
+            .expect_err("stop before the running start should fail");
+        assert!(err.contains("overlap"));
+        assert_eq!(ledger.events.len(), 3);
+    }
+
+    #[test]
+    fn matches_tasks_by_id_prefix_title_words_and_fuzzy() {
+        let mut ledger = Ledger::new();
+        let work = ledger.add_project("Work".to_string(), None);
+        let home = ledger.add_project("Home".to_string(), None);
+        let docs = ledger
+            .add_task(work.clone(), None, "Write docs\nfor the API".to_string())
+            .expect("task should be created");
+        let review = ledger
+            .add_task(work, None, "Review PRs".to_string())
+            .expect("task should be created");
+        let garden = ledger
+            .add_task(home, None, "Garden".to_string())
+            .expect("task should be created");
+
+        assert_eq!(ledger.match_tasks(&docs), std::slice::from_ref(&docs));
+        assert_eq!(
+            ledger.match_tasks(&review[..5]),
+            std::slice::from_ref(&review)
+        );
+        assert_eq!(
+            ledger.match_tasks("review prs"),
+            std::slice::from_ref(&review)
+        );
+        assert_eq!(ledger.match_tasks("work api"), std::slice::from_ref(&docs));
+        assert_eq!(ledger.match_tasks("wrdcs"), std::slice::from_ref(&docs));
+        assert_eq!(ledger.match_tasks("work").len(), 2);
+        assert!(ledger.match_tasks("xyz").is_empty());
+
+        ledger.header.tasks[2].archived = true;
+        assert!(ledger.match_tasks("garden").is_empty());
+        assert_eq!(ledger.match_tasks(&garden), [garden]);
+        assert_eq!(ledger.match_projects("hom").len(), 1);
+    }
+
+    #[test]
+    fn status_reports_running_sessions_and_todays_total() {
+        let mut ledger = Ledger::new();
+        let project = ledger.add_project("Work".to_string(), None);
+        let task_a = ledger
+            .add_task(project.clone(), None, "Task A".to_string())
+            .expect("task should be created");
+        let task_b = ledger
+            .add_task(project, None, "Task B".to_string())
+            .expect("task should be created");
+        ledger
+            .log_session(
+                &task_a,
+                at(1, 0) - chrono::Duration::hours(2),
+                at(1, 0),
+                None,
+                now(),
+            )
+            .expect("log should work");
+        ledger
+            .start_task(&task_a, at(9, 0), Some("focus".to_string()), now())
+            .expect("start should work");
+        ledger
+            .start_task(&task_b, at(9, 30), None, now())
+            .expect("start should work");
+
+        let status = ledger.status(at(10, 0));
+        let running = status
+            .running
+            .iter()
+            .map(|session| (session.task_id.as_str(), session.note.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            running,
+            [(task_a.as_str(), Some("focus")), (task_b.as_str(), None)]
+        );
+        let (day_start, _) = ledger.day_bounds_utc(ledger.day_for_timestamp(at(10, 0)));
+        let tracked_since = |start: DateTime<Utc>, stop: DateTime<Utc>| {
+            (stop - start.max(day_start)).max(chrono::Duration::zero())
+        };
+        assert_eq!(
+            status.today_total,
+            tracked_since(at(1, 0) - chrono::Duration::hours(2), at(1, 0))
+                + tracked_since(at(9, 0), at(10, 0))
+                + tracked_since(at(9, 30), at(10, 0))
+        );
+    }
+
+    #[test]
+    fn switches_stops_all_and_resumes_last_session() {
+        let mut ledger = Ledger::new();
+        let project = ledger.add_project("Work".to_string(), None);
+        let task_a = ledger
+            .add_task(project.clone(), None, "Task A".to_string())
+            .expect("task should be created");
+        let task_b = ledger
+            .add_task(project, None, "Task B".to_string())
+            .expect("task should be created");
+        ledger
+            .start_task(&task_a, at(9, 0), Some("deep work".to_string()), now())
+            .expect("start should work");
+
+        let stopped = ledger
+            .switch_to(&task_b, at(10, 0), None, now())
+            .expect("switch should work");
+        assert_eq!(stopped, std::slice::from_ref(&task_a));
+        assert_eq!(
+            ledger.last_stopped_session(),
+            Some((task_a.clone(), Some("deep work".to_string())))
+        );
+
+        let stopped = ledger
+            .stop_all(at(11, 0), now())
+            .expect("stop all should work");
+        assert_eq!(stopped, std::slice::from_ref(&task_b));
+        assert!(ledger.status(at(11, 0)).running.is_empty());
+        assert_eq!(ledger.last_stopped_session(), Some((task_b, None)));
+        assert!(ledger.stop_all(at(11, 5), now()).expect("no-op").is_empty());
+    }
 }
@@ -0,0 +1,279 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use crate::domain::Ledger;
+use crate::report::ReportFilter;
+
+/// One exported interval: a whole session, or the part of it inside one
+/// ledger day when splitting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedSession {
+    pub task_id: String,
+    /// Ledger day the interval starts in.
+    pub day: NaiveDate,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub note: Option<String>,
+    /// Still running; `end` is the export time.
+    pub running: bool,
+}
+
+/// Sessions from `Ledger::sessions` that touch the ledger days
+/// `from..=to` (open-ended when `None`), oldest first. Without
+/// `split_days` sessions are kept whole, even when they run past the range;
+/// with it each session becomes one interval per ledger day and only days
+/// inside the range are kept.
+pub fn export_sessions(
+    ledger: &Ledger,
+    now: DateTime<Utc>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    filter: &ReportFilter,
+    split_days: bool,
+) -> Vec<ExportedSession> {
+    let in_range =
+        |day: NaiveDate| from.is_none_or(|from| day >= from) && to.is_none_or(|to| day <= to);
+    let mut rows = Vec::new();
+
+    for session in ledger.sessions(now) {
+        if !ledger
+            .task(&session.task_id)
+            .is_some_and(|task| filter.matches(task))
+        {
+            continue;
+        }
+
+        let running = session.is_running();
+        if !split_days {
+            let first_day = ledger.day_for_timestamp(session.start);
+            let last_day = ledger.day_for_timestamp(session.stop - Duration::nanoseconds(1));
+            if from.is_some_and(|from| last_day < from) || to.is_some_and(|to| first_day > to) {
+                continue;
+            }
+            rows.push(ExportedSession {
+                task_id: session.task_id,
+                day: first_day,
+                start: session.start,
+                end: session.stop,
+                note: session.note,
+                running,
+            });
+            continue;
+        }
+
+        let mut start = session.start;
+        while start < session.stop {
+            let day = ledger.day_for_timestamp(start);
+            let end = ledger.day_bounds_utc(day).1.min(session.stop);
+            if in_range(day) {
+                rows.push(ExportedSession {
+                    task_id: session.task_id.clone(),
+                    day,
+                    start,
+                    end,
+                    note: session.note.clone(),
+                    running: running && end == session.stop,
+                });
+            }
+            start = end;
+        }
+    }
+
+    rows.sort_by(|left, right| {
+        left.start
+            .cmp(&right.start)
+            .then_with(|| left.task_id.cmp(&right.task_id))
+    });
+    rows
+}
+
+/// Renders sessions as an RFC 5545 calendar with one VEVENT per session.
+/// `now` becomes each event's DTSTAMP.
+pub fn render_ics(ledger: &Ledger, sessions: &[ExportedSession], now: DateTime<Utc>) -> String {
+    // Hashing the creation time keeps UIDs of two ledgers apart while
+    // staying the same across exports of one ledger.
+    let ledger_key = fnv1a64(ledger.header.created_at.to_rfc3339().as_bytes());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//chronos-timeledger//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for session in sessions {
+        let task = ledger.task(&session.task_id);
+        let project = task.and_then(|task| ledger.project(&task.project_id));
+        let summary = format!(
+            "{} · {}",
+            project
+                .map(|project| project.name.as_str())
+                .unwrap_or("Unknown project"),
+            task.map(|task| task.short_description())
+                .unwrap_or_else(|| "Unknown task".to_string())
+        );
+        let description = [
+            session.note.as_deref(),
+            task.map(|task| task.description.as_str()),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|text| !text.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!(
+            "UID:{}-{}-{ledger_key:016x}@chronos-timeledger",
+            session.task_id,
+            ics_timestamp(session.start)
+        ));
+        lines.push(format!("DTSTAMP:{}", ics_timestamp(now)));
+        lines.push(format!("DTSTART:{}", ics_timestamp(session.start)));
+        lines.push(format!("DTEND:{}", ics_timestamp(session.end)));
+        lines.push(format!("SUMMARY:{}", ics_text(&summary)));
+        if !description.is_empty() {
+            lines.push(format!("DESCRIPTION:{}", ics_text(&description)));
+        }
+        if let Some(color) = project.and_then(|project| project.color.as_deref()) {
+            lines.push(format!("CATEGORIES:{}", ics_text(color)));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut out = String::new();
+    for line in lines {
+        out.push_str(&fold_ics_line(&line));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn ics_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes an RFC 5545 TEXT value.
+fn ics_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\n', '\r'], "\\n")
+}
+
+/// Folds a content line to at most 75 octets per line without splitting a
+/// UTF-8 character; continuation lines start with a space.
+fn fold_ics_line(line: &str) -> String {
+    let mut out = String::with_capacity(line.len() + line.len() / 74 * 3);
+    let mut width = 0;
+    for ch in line.chars() {
+        if width + ch.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(ch);
+        width += ch.len_utf8();
+    }
+    out
+}
+
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono::{Local, NaiveDate, TimeZone, Utc};
+
+    use super::{export_sessions, render_ics};
+    use crate::domain::Ledger;
+    use crate::report::ReportFilter;
+    use crate::storage::load_ledger;
+
+    #[test]
+    fn exports_whole_or_per_day_sessions_within_range() {
+        let mut ledger = Ledger::new();
+        ledger.header.day_start_offset_hours = 0;
+        let work = ledger.add_project("Work".to_string(), None);
+        let home = ledger.add_project("Home".to_string(), None);
+        let docs = ledger
+            .add_task(work.clone(), None, "Docs".to_string())
+            .expect("task should be created");
+        let garden = ledger
+            .add_task(home, None, "Garden".to_string())
+            .expect("task should be created");
+        // Ledger days follow local time.
+        let at = |day: u32, hour: u32| {
+            Local
+                .with_ymd_and_hms(2026, 3, day, hour, 0, 0)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let now = at(4, 9);
+        ledger
+            .log_session(&docs, at(2, 22), at(3, 2), Some("late".to_string()), now)
+            .expect("log should work");
+        ledger
+            .log_session(&garden, at(3, 9), at(3, 10), None, now)
+            .expect("log should work");
+        ledger
+            .start_task(&docs, at(4, 8), None, now)
+            .expect("start should work");
+        let day = |day: u32| NaiveDate::from_ymd_opt(2026, 3, day).unwrap();
+
+        let whole = export_sessions(
+            &ledger,
+            now,
+            Some(day(3)),
+            None,
+            &ReportFilter::default(),
+            false,
+        );
+        let spans = whole
+            .iter()
+            .map(|row| (row.day, row.start, row.end, row.running))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            spans,
+            [
+                (day(2), at(2, 22), at(3, 2), false),
+                (day(3), at(3, 9), at(3, 10), false),
+                (day(4), at(4, 8), now, true),
+            ]
+        );
+
+        let filter = ReportFilter {
+            projects: vec![work],
+            ..ReportFilter::default()
+        };
+        let split = export_sessions(&ledger, now, Some(day(3)), Some(day(3)), &filter, true);
+        assert_eq!(split.len(), 1);
+        assert_eq!((split[0].start, split[0].end), (at(3, 0), at(3, 2)));
+        assert_eq!(split[0].note.as_deref(), Some("late"));
+    }
+
+    #[test]
+    fn renders_ics_matching_fixture() {
+        let fixtures =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../contracts/fixtures/export");
+        let ledger =
+            load_ledger(&fixtures.join("sessions.ledger")).expect("fixture ledger should load");
+        let now = Utc.with_ymd_and_hms(2026, 3, 9, 12, 0, 0).unwrap();
+        let sessions = export_sessions(&ledger, now, None, None, &ReportFilter::default(), false);
+
+        let ics = render_ics(&ledger, &sessions, now);
+        let expected =
+            std::fs::read_to_string(fixtures.join("sessions.ics")).expect("fixture should exist");
+        assert_eq!(ics, expected);
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+        for line in ics.split_terminator("\r\n") {
+            assert!(line.len() <= 75, "line longer than 75 octets: {line}");
+            assert!(!line.contains('\n'), "bare line feed in {line}");
+        }
+    }
+}
@@ -0,0 +1,436 @@
+mod generic_csv;
+mod timewarrior;
+mod toggl;
+mod watson;
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, FixedOffset, LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::domain::{Ledger, SessionBatch, SessionChange};
+
+pub use generic_csv::{CsvMapping, parse_mapped_csv};
+pub use timewarrior::{default_timewarrior_dir, read_timewarrior};
+pub use toggl::{TogglCategorySource, parse_toggl_csv};
+pub use watson::{default_watson_dir, read_watson};
+
+/// One session read from another tool, with entities still given by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedSession {
+    /// Where the session came from in the source, e.g. `row 12`.
+    pub source: String,
+    pub project: String,
+    pub category: Option<String>,
+    pub task: String,
+    pub start: DateTime<Utc>,
+    /// `None` keeps the session running.
+    pub end: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+}
+
+/// A row that could not be read or applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportIssue {
+    pub source: String,
+    pub message: String,
+}
+
+impl ImportIssue {
+    pub fn new(source: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// What an import created, skipped and rejected.
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    pub projects_created: Vec<String>,
+    pub categories_created: Vec<String>,
+    pub tasks_created: Vec<String>,
+    pub sessions_added: usize,
+    pub running_started: usize,
+    pub duplicates: usize,
+    pub errors: Vec<ImportIssue>,
+}
+
+/// Adds imported sessions to `ledger`, oldest first. Projects, categories
+/// and tasks are matched by name (case-insensitive) and created when
+/// missing; a session whose task, start and end already exist in the
+/// ledger counts as a duplicate, so re-running an import adds nothing. A
+/// session imported while still running is stopped once the source has an
+/// end for it. Sessions the ledger rejects, e.g. because they overlap
+/// another session of the same task, end up in `errors` without being
+/// recorded, though any project, category or task created for them stays.
+pub fn apply_import(
+    ledger: &mut Ledger,
+    mut sessions: Vec<ImportedSession>,
+    now: DateTime<Utc>,
+) -> ImportReport {
+    let mut report = ImportReport::default();
+    let mut existing = ledger
+        .sessions(now)
+        .into_iter()
+        .map(|session| {
+            let end = (!session.is_running()).then_some(session.stop);
+            (session.task_id, session.start, end)
+        })
+        .collect::<HashSet<_>>();
+
+    let mut batch = SessionBatch::new(ledger);
+    sessions.sort_by_key(|session| session.start);
+    for session in sessions {
+        let project_id = find_or_create_project(ledger, &session.project, &mut report);
+        let category_id = session
+            .category
+            .as_deref()
+            .map(|name| find_or_create_category(ledger, name, &mut report));
+        let task_id =
+            match find_or_create_task(ledger, &project_id, category_id, &session.task, &mut report)
+            {
+                Ok(task_id) => task_id,
+                Err(message) => {
+                    report
+                        .errors
+                        .push(ImportIssue::new(session.source, message));
+                    continue;
+                }
+            };
+
+        let key = (task_id.clone(), session.start, session.end);
+        if existing.contains(&key) {
+            report.duplicates += 1;
+            continue;
+        }
+
+        let was_running = existing.contains(&(task_id.clone(), session.start, None));
+        let change = match session.end {
+            Some(end) if was_running => SessionChange::Stop {
+                at: end,
+                note: None,
+            },
+            Some(end) => SessionChange::Log {
+                start: session.start,
+                stop: end,
+                note: session.note,
+            },
+            None => SessionChange::Start {
+                start: session.start,
+                note: session.note,
+            },
+        };
+        match batch.record(ledger, &task_id, change, now) {
+            Ok(()) => {
+                if session.end.is_some() {
+                    report.sessions_added += 1;
+                } else {
+                    report.running_started += 1;
+                }
+                if was_running {
+                    existing.remove(&(task_id, session.start, None));
+                }
+                existing.insert(key);
+            }
+            Err(message) => report
+                .errors
+                .push(ImportIssue::new(session.source, message)),
+        }
+    }
+
+    batch.commit(ledger);
+    report
+}
+
+fn find_or_create_project(ledger: &mut Ledger, name: &str, report: &mut ImportReport) -> String {
+    let existing = ledger
+        .header
+        .projects
+        .iter()
+        .filter(|project| project.name.eq_ignore_ascii_case(name))
+        .min_by_key(|project| project.archived)
+        .map(|project| project.id.clone());
+    existing.unwrap_or_else(|| {
+        report.projects_created.push(name.to_string());
+        ledger.add_project(name.to_string(), None)
+    })
+}
+
+fn find_or_create_category(ledger: &mut Ledger, name: &str, report: &mut ImportReport) -> String {
+    let existing = ledger
+        .header
+        .categories
+        .iter()
+        .filter(|category| category.name.eq_ignore_ascii_case(name))
+        .min_by_key(|category| category.archived)
+        .map(|category| category.id.clone());
+    existing.unwrap_or_else(|| {
+        report.categories_created.push(name.to_string());
+        ledger.add_category(name.to_string(), None)
+    })
+}
+
+/// Tasks match on project and first description line; an existing task
+/// keeps its category.
+fn find_or_create_task(
+    ledger: &mut Ledger,
+    project_id: &str,
+    category_id: Option<String>,
+    name: &str,
+    report: &mut ImportReport,
+) -> Result<String, String> {
+    let existing = ledger
+        .header
+        .tasks
+        .iter()
+        .filter(|task| {
+            task.project_id == project_id
+                && task.short_description().trim().eq_ignore_ascii_case(name)
+        })
+        .min_by_key(|task| task.archived)
+        .map(|task| task.id.clone());
+    if let Some(task_id) = existing {
+        return Ok(task_id);
+    }
+
+    let task_id = ledger.add_task(project_id.to_string(), category_id, name.to_string())?;
+    let project = ledger
+        .project(project_id)
+        .map(|project| project.name.as_str())
+        .unwrap_or_default();
+    report.tasks_created.push(format!("{project} · {name}"));
+    Ok(task_id)
+}
+
+/// Task name for sessions without anything to name them after.
+const NO_DESCRIPTION: &str = "(no description)";
+
+/// `--map TAG=PROJECT` rules for trackers that organise time by tags.
+#[derive(Debug, Clone, Default)]
+pub struct TagMapping {
+    rules: Vec<(String, String)>,
+}
+
+impl TagMapping {
+    pub fn parse(rules: &[String]) -> Result<Self, String> {
+        let rules = rules
+            .iter()
+            .map(|rule| match rule.split_once('=') {
+                Some((tag, project)) if !tag.trim().is_empty() && !project.trim().is_empty() => {
+                    Ok((tag.trim().to_string(), project.trim().to_string()))
+                }
+                _ => Err(format!("invalid mapping '{rule}': use TAG=PROJECT")),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Removes the first tag with a rule and returns that rule's project.
+    fn take_project(&self, tags: &mut Vec<String>) -> Option<String> {
+        let (index, project) = tags.iter().enumerate().find_map(|(index, tag)| {
+            self.rules
+                .iter()
+                .find(|(rule_tag, _)| rule_tag == tag)
+                .map(|(_, project)| (index, project.clone()))
+        })?;
+        tags.remove(index);
+        Some(project)
+    }
+}
+
+/// Names a task after the tags left once the project is known.
+fn task_from_tags(tags: &[String]) -> String {
+    if tags.is_empty() {
+        NO_DESCRIPTION.to_string()
+    } else {
+        tags.join(" ")
+    }
+}
+
+/// How to read timestamps that carry no offset of their own.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceTimezone {
+    Local,
+    Named(Tz),
+    Fixed(FixedOffset),
+}
+
+impl SourceTimezone {
+    /// Accepts `local`, `UTC`, an IANA name such as `Europe/Berlin`, or a
+    /// fixed offset such as `+02:00`.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        if input.eq_ignore_ascii_case("local") {
+            return Ok(SourceTimezone::Local);
+        }
+        if input.eq_ignore_ascii_case("utc") || input.eq_ignore_ascii_case("z") {
+            return Ok(SourceTimezone::Fixed(
+                FixedOffset::east_opt(0).expect("zero offset"),
+            ));
+        }
+        if let Ok(offset) = input.parse::<FixedOffset>() {
+            return Ok(SourceTimezone::Fixed(offset));
+        }
+        input.parse::<Tz>().map(SourceTimezone::Named).map_err(|_| {
+            format!(
+                "invalid timezone '{input}': use local, UTC, a name like Europe/Berlin or an offset like +02:00"
+            )
+        })
+    }
+
+    /// Resolves a wall-clock time. Times repeated by a DST change take the
+    /// earlier instant; times skipped by one are an error.
+    pub fn resolve(&self, naive: NaiveDateTime) -> Result<DateTime<Utc>, String> {
+        let resolved = match self {
+            SourceTimezone::Local => map_local(chrono::Local.from_local_datetime(&naive)),
+            SourceTimezone::Named(tz) => map_local(tz.from_local_datetime(&naive)),
+            SourceTimezone::Fixed(offset) => map_local(offset.from_local_datetime(&naive)),
+        };
+        resolved.ok_or_else(|| format!("{naive} does not exist in this timezone"))
+    }
+}
+
+fn map_local<T: TimeZone>(result: LocalResult<DateTime<T>>) -> Option<DateTime<Utc>> {
+    result
+        .earliest()
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate, TimeZone, Utc};
+
+    use super::{ImportedSession, SourceTimezone, apply_import};
+    use crate::domain::Ledger;
+
+    #[test]
+    fn creates_entities_once_and_skips_duplicate_sessions() {
+        let mut ledger = Ledger::new();
+        let client = ledger.add_project("Client".to_string(), None);
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        let now = start + Duration::days(1);
+        let session = |task: &str, offset: i64| ImportedSession {
+            source: format!("row {offset}"),
+            project: "client".to_string(),
+            category: Some("Billable".to_string()),
+            task: task.to_string(),
+            start: start + Duration::hours(offset),
+            end: Some(start + Duration::hours(offset) + Duration::minutes(30)),
+            note: None,
+        };
+        let batch = vec![
+            session("Design", 0),
+            session("Design", 1),
+            session("Review", 1),
+        ];
+
+        let report = apply_import(&mut ledger, batch.clone(), now);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert!(report.projects_created.is_empty());
+        assert_eq!(report.categories_created, ["Billable"]);
+        assert_eq!(report.tasks_created, ["Client · Design", "Client · Review"]);
+        assert_eq!(report.sessions_added, 3);
+        assert!(
+            ledger
+                .header
+                .tasks
+                .iter()
+                .all(|task| task.project_id == client)
+        );
+
+        let again = apply_import(&mut ledger, batch, now);
+        assert_eq!((again.sessions_added, again.duplicates), (0, 3));
+        assert!(again.tasks_created.is_empty());
+        assert_eq!(ledger.events.len(), 6);
+
+        let mut overlapping = session("Design", 0);
+        overlapping.start += Duration::minutes(10);
+        let rejected = apply_import(&mut ledger, vec![overlapping], now);
+        assert_eq!(rejected.errors.len(), 1);
+        assert_eq!(ledger.events.len(), 6);
+    }
+
+    #[test]
+    fn checks_rows_against_earlier_rows_of_the_same_import() {
+        let mut ledger = Ledger::new();
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        let now = start + Duration::days(30);
+        let row = |index: i64, offset: Duration| ImportedSession {
+            source: format!("row {index}"),
+            project: "Client".to_string(),
+            category: None,
+            task: "Design".to_string(),
+            start: start + offset,
+            end: Some(start + offset + Duration::minutes(30)),
+            note: None,
+        };
+        let mut rows = (0..200)
+            .map(|index| row(index, Duration::hours(index)))
+            .collect::<Vec<_>>();
+        rows.push(row(200, Duration::hours(42) + Duration::minutes(15)));
+
+        let report = apply_import(&mut ledger, rows, now);
+        assert_eq!(report.sessions_added, 200);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].source, "row 200");
+        assert_eq!(ledger.events.len(), 400);
+        assert_eq!(ledger.sessions(now).len(), 200);
+    }
+
+    #[test]
+    fn resolves_timezones_for_naive_times() {
+        let naive = NaiveDate::from_ymd_opt(2026, 3, 29)
+            .unwrap()
+            .and_hms_opt(1, 30, 0)
+            .unwrap();
+        let berlin = SourceTimezone::parse("Europe/Berlin").expect("zone should parse");
+        assert_eq!(
+            berlin.resolve(naive),
+            Ok(Utc.with_ymd_and_hms(2026, 3, 29, 0, 30, 0).unwrap())
+        );
+        let skipped = naive + Duration::hours(1);
+        assert!(berlin.resolve(skipped).is_err());
+        assert_eq!(
+            SourceTimezone::parse("+05:30")
+                .expect("offset should parse")
+                .resolve(naive),
+            Ok(Utc.with_ymd_and_hms(2026, 3, 28, 20, 0, 0).unwrap())
+        );
+        assert!(SourceTimezone::parse("Mars/Olympus").is_err());
+    }
+
+    #[test]
+    fn reimporting_a_finished_entry_stops_the_running_session() {
+        let mut ledger = Ledger::new();
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        let end = start + Duration::minutes(45);
+        let now = end + Duration::hours(1);
+        let mut entry = ImportedSession {
+            source: "entry 1".to_string(),
+            project: "Client".to_string(),
+            category: None,
+            task: "Design".to_string(),
+            start,
+            end: None,
+            note: Some("draft".to_string()),
+        };
+
+        let first = apply_import(&mut ledger, vec![entry.clone()], now);
+        assert_eq!((first.running_started, first.sessions_added), (1, 0));
+
+        entry.end = Some(end);
+        let second = apply_import(&mut ledger, vec![entry.clone()], now);
+        assert!(second.errors.is_empty(), "{:?}", second.errors);
+        assert_eq!((second.running_started, second.sessions_added), (0, 1));
+
+        let sessions = ledger.sessions(now);
+        assert_eq!(sessions.len(), 1);
+        assert!(!sessions[0].is_running());
+        assert_eq!((sessions[0].start, sessions[0].stop), (start, end));
+
+        let third = apply_import(&mut ledger, vec![entry], now);
+        assert_eq!((third.sessions_added, third.duplicates), (0, 1));
+    }
+}
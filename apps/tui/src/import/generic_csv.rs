@@ -0,0 +1,452 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use super::{ImportIssue, ImportedSession, SourceTimezone};
+use crate::timeinput::parse_duration_input;
+
+/// Unit of bare numbers in a duration column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DurationUnit {
+    #[default]
+    Minutes,
+    Hours,
+}
+
+/// Which columns hold what, from `--*-column` flags or a TOML mapping file
+/// with the same names (`project = "Client"`, `date_format = "%d.%m.%Y"`).
+/// Column names match headers case-insensitively.
+#[derive(Debug, Clone, Default, clap::Args, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CsvMapping {
+    /// Column with the project name
+    #[arg(long = "project-column", value_name = "COLUMN")]
+    pub project: Option<String>,
+    /// Project for rows without a project column or value
+    #[arg(long, value_name = "NAME")]
+    pub default_project: Option<String>,
+    /// Column with the task (its first line is matched against existing tasks)
+    #[arg(long = "task-column", value_name = "COLUMN")]
+    pub task: Option<String>,
+    /// Column with the category name
+    #[arg(long = "category-column", value_name = "COLUMN")]
+    pub category: Option<String>,
+    /// Column with the session note
+    #[arg(long = "note-column", value_name = "COLUMN")]
+    pub note: Option<String>,
+    /// Column with the day; start and end then only hold times
+    #[arg(long = "date-column", value_name = "COLUMN")]
+    pub date: Option<String>,
+    /// Column with the start (date and time, or a time with --date-column)
+    #[arg(long = "start-column", value_name = "COLUMN")]
+    pub start: Option<String>,
+    /// Column with the end; an end time before the start means the next day
+    #[arg(long = "end-column", value_name = "COLUMN")]
+    pub end: Option<String>,
+    /// Column with the duration, used when there is no end
+    #[arg(long = "duration-column", value_name = "COLUMN")]
+    pub duration: Option<String>,
+    /// chrono format of the date column [default: %Y-%m-%d]
+    #[arg(long, value_name = "FORMAT")]
+    pub date_format: Option<String>,
+    /// chrono format of times [default: %H:%M:%S or %H:%M]
+    #[arg(long, value_name = "FORMAT")]
+    pub time_format: Option<String>,
+    /// chrono format of start/end without a date column [default: RFC 3339
+    /// or ISO date and time]
+    #[arg(long, value_name = "FORMAT")]
+    pub datetime_format: Option<String>,
+    /// Unit of bare numbers in the duration column
+    #[arg(long, value_enum)]
+    pub duration_unit: Option<DurationUnit>,
+    /// Field separator [default: ,]
+    #[arg(long, value_name = "CHAR")]
+    pub delimiter: Option<char>,
+    /// Timezone of times without an offset [default: local]
+    #[arg(long)]
+    pub timezone: Option<String>,
+}
+
+impl CsvMapping {
+    /// Fills every setting not given on the command line from `file`.
+    pub fn or(self, file: CsvMapping) -> CsvMapping {
+        CsvMapping {
+            project: self.project.or(file.project),
+            default_project: self.default_project.or(file.default_project),
+            task: self.task.or(file.task),
+            category: self.category.or(file.category),
+            note: self.note.or(file.note),
+            date: self.date.or(file.date),
+            start: self.start.or(file.start),
+            end: self.end.or(file.end),
+            duration: self.duration.or(file.duration),
+            date_format: self.date_format.or(file.date_format),
+            time_format: self.time_format.or(file.time_format),
+            datetime_format: self.datetime_format.or(file.datetime_format),
+            duration_unit: self.duration_unit.or(file.duration_unit),
+            delimiter: self.delimiter.or(file.delimiter),
+            timezone: self.timezone.or(file.timezone),
+        }
+    }
+}
+
+/// Column positions resolved against the header line.
+struct Columns {
+    project: Option<usize>,
+    task: usize,
+    category: Option<usize>,
+    note: Option<usize>,
+    date: Option<usize>,
+    start: usize,
+    end: Option<usize>,
+    duration: Option<usize>,
+}
+
+/// Reads a CSV file with a header line using `mapping`. Mapping problems
+/// (unknown columns, a bad timezone) fail the whole import; row problems
+/// are reported per row.
+pub fn parse_mapped_csv(
+    input: &str,
+    mapping: &CsvMapping,
+) -> Result<(Vec<ImportedSession>, Vec<ImportIssue>), String> {
+    let delimiter = mapping.delimiter.unwrap_or(',');
+    if !delimiter.is_ascii() {
+        return Err(format!(
+            "delimiter '{delimiter}' must be a single ASCII character"
+        ));
+    }
+    let timezone = SourceTimezone::parse(mapping.timezone.as_deref().unwrap_or("local"))?;
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter as u8)
+        .flexible(true)
+        .from_reader(input.trim_start_matches('\u{feff}').as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|err| format!("failed to read the header line: {err}"))?
+        .iter()
+        .map(|header| header.trim().to_lowercase())
+        .collect::<Vec<_>>();
+
+    let find = |name: &Option<String>, flag: &str| -> Result<Option<usize>, String> {
+        let Some(name) = name else {
+            return Ok(None);
+        };
+        headers
+            .iter()
+            .position(|header| *header == name.trim().to_lowercase())
+            .map(Some)
+            .ok_or_else(|| {
+                format!(
+                    "{flag}: no column named '{name}' (columns: {})",
+                    headers.join(", ")
+                )
+            })
+    };
+    let required = |name: &Option<String>, flag: &str| {
+        find(name, flag)?.ok_or_else(|| format!("{flag} is required"))
+    };
+    let columns = Columns {
+        project: find(&mapping.project, "--project-column")?,
+        task: required(&mapping.task, "--task-column")?,
+        category: find(&mapping.category, "--category-column")?,
+        note: find(&mapping.note, "--note-column")?,
+        date: find(&mapping.date, "--date-column")?,
+        start: required(&mapping.start, "--start-column")?,
+        end: find(&mapping.end, "--end-column")?,
+        duration: find(&mapping.duration, "--duration-column")?,
+    };
+    if columns.end.is_none() && columns.duration.is_none() {
+        return Err("pass --end-column or --duration-column".to_string());
+    }
+    if columns.project.is_none() && mapping.default_project.is_none() {
+        return Err("pass --project-column or --default-project".to_string());
+    }
+
+    let mut sessions = Vec::new();
+    let mut issues = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let source = format!("row {}", index + 2);
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                issues.push(ImportIssue::new(source, err.to_string()));
+                continue;
+            }
+        };
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| record.get(column))
+                .map(str::trim)
+                .unwrap_or_default()
+        };
+        match parse_row(&columns, &field, mapping, &timezone) {
+            Ok(session) => sessions.push(ImportedSession { source, ..session }),
+            Err(message) => issues.push(ImportIssue::new(source, message)),
+        }
+    }
+    Ok((sessions, issues))
+}
+
+fn parse_row<'a>(
+    columns: &Columns,
+    field: &impl Fn(Option<usize>) -> &'a str,
+    mapping: &CsvMapping,
+    timezone: &SourceTimezone,
+) -> Result<ImportedSession, String> {
+    let project = Some(field(columns.project))
+        .filter(|project| !project.is_empty())
+        .map(str::to_string)
+        .or_else(|| mapping.default_project.clone())
+        .ok_or_else(|| "missing project".to_string())?;
+    let task = field(Some(columns.task));
+    if task.is_empty() {
+        return Err("missing task".to_string());
+    }
+
+    let date = match columns.date {
+        Some(column) => {
+            let format = mapping.date_format.as_deref().unwrap_or("%Y-%m-%d");
+            let value = field(Some(column));
+            Some(
+                NaiveDate::parse_from_str(value, format)
+                    .map_err(|_| format!("invalid date '{value}', expected {format}"))?,
+            )
+        }
+        None => None,
+    };
+    let start = parse_moment(field(Some(columns.start)), date, mapping, timezone, "start")?;
+    let end_value = field(columns.end);
+    let end = if !end_value.is_empty() {
+        let mut end = parse_moment(end_value, date, mapping, timezone, "end")?;
+        // `22:00` to `01:00` on one date crosses midnight.
+        if date.is_some() && end <= start {
+            end = end
+                .checked_add_signed(Duration::days(1))
+                .ok_or("end is out of range")?;
+        }
+        end
+    } else {
+        let value = field(columns.duration);
+        if value.is_empty() {
+            return Err("missing end and duration".to_string());
+        }
+        start
+            .checked_add_signed(parse_duration(
+                value,
+                mapping.duration_unit.unwrap_or_default(),
+            )?)
+            .ok_or_else(|| format!("duration '{value}' is out of range"))?
+    };
+    if end <= start {
+        return Err("end must be after start".to_string());
+    }
+
+    let optional = |column: Option<usize>| {
+        Some(field(column))
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    Ok(ImportedSession {
+        source: String::new(),
+        project,
+        category: optional(columns.category),
+        task: task.to_string(),
+        start,
+        end: Some(end),
+        note: optional(columns.note),
+    })
+}
+
+/// A full timestamp, or a time on `date` when there is a date column.
+fn parse_moment(
+    value: &str,
+    date: Option<NaiveDate>,
+    mapping: &CsvMapping,
+    timezone: &SourceTimezone,
+    what: &str,
+) -> Result<DateTime<Utc>, String> {
+    if value.is_empty() {
+        return Err(format!("missing {what}"));
+    }
+    if let Some(date) = date {
+        let time = match &mapping.time_format {
+            Some(format) => NaiveTime::parse_from_str(value, format)
+                .map_err(|_| format!("invalid {what} time '{value}', expected {format}")),
+            None => NaiveTime::parse_from_str(value, "%H:%M:%S")
+                .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M"))
+                .map_err(|_| format!("invalid {what} time '{value}', expected HH:MM")),
+        }?;
+        return timezone.resolve(NaiveDateTime::new(date, time));
+    }
+
+    if let Some(format) = &mapping.datetime_format {
+        // Formats with an offset (`%z`) carry their own timezone.
+        if let Ok(timestamp) = DateTime::parse_from_str(value, format) {
+            return Ok(timestamp.with_timezone(&Utc));
+        }
+        let naive = NaiveDateTime::parse_from_str(value, format)
+            .map_err(|_| format!("invalid {what} '{value}', expected {format}"))?;
+        return timezone.resolve(naive);
+    }
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    let naive = [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .ok_or_else(|| {
+        format!("invalid {what} '{value}', expected YYYY-MM-DD HH:MM or --datetime-format")
+    })?;
+    timezone.resolve(naive)
+}
+
+/// Accepts `H:MM[:SS]`, the `45m`/`1h30` forms of the time prompts, and
+/// bare numbers (decimals allowed) in `unit`.
+fn parse_duration(value: &str, unit: DurationUnit) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration '{value}'");
+    let out_of_range = || format!("duration '{value}' is out of range");
+    if value.contains(':') {
+        let parts = value
+            .split(':')
+            .map(|part| part.parse::<i64>().ok().filter(|part| *part >= 0))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+        let (hours, minutes, seconds) = match parts.as_slice() {
+            [hours, minutes] if *minutes < 60 => (*hours, *minutes, 0),
+            [hours, minutes, seconds] if *minutes < 60 && *seconds < 60 => {
+                (*hours, *minutes, *seconds)
+            }
+            _ => return Err(invalid()),
+        };
+        let duration = Duration::try_hours(hours)
+            .and_then(|total| total.checked_add(&Duration::minutes(minutes)))
+            .and_then(|total| total.checked_add(&Duration::seconds(seconds)))
+            .ok_or_else(out_of_range)?;
+        return Some(duration)
+            .filter(|duration| *duration > Duration::zero())
+            .ok_or_else(invalid);
+    }
+    if let Ok(amount) = value.replace(',', ".").parse::<f64>() {
+        let scale = match unit {
+            DurationUnit::Minutes => 60.0,
+            DurationUnit::Hours => 3600.0,
+        };
+        let seconds = (amount * scale).round();
+        if seconds.is_nan() || seconds <= 0.0 {
+            return Err(invalid());
+        }
+        return (seconds < i64::MAX as f64)
+            .then(|| Duration::try_seconds(seconds as i64))
+            .flatten()
+            .ok_or_else(out_of_range);
+    }
+    parse_duration_input(value, "duration")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::{CsvMapping, DurationUnit, parse_mapped_csv};
+
+    #[test]
+    fn maps_columns_and_reports_every_bad_row() {
+        let csv = "Day;Client;Activity;From;To;Hours;Comment\n\
+            02.03.2026;Acme;Design;09:00;10:30;;first pass\n\
+            02.03.2026;;Support;22:00;01:00;;\n\
+            03.03.2026;Acme;Review;13:00;;1,5;\n\
+            31.02.2026;Acme;Design;09:00;10:00;;\n\
+            03.03.2026;Acme;;09:00;10:00;;\n";
+        let file = toml::from_str::<CsvMapping>(
+            r#"
+            date = "Day"
+            project = "Client"
+            task = "Activity"
+            start = "From"
+            end = "To"
+            duration = "Hours"
+            note = "Comment"
+            date_format = "%d.%m.%Y"
+            duration_unit = "hours"
+            delimiter = ";"
+            timezone = "UTC"
+            "#,
+        )
+        .expect("mapping file should parse");
+        let mapping = CsvMapping {
+            default_project: Some("Internal".to_string()),
+            ..CsvMapping::default()
+        }
+        .or(file);
+        assert_eq!(mapping.duration_unit, Some(DurationUnit::Hours));
+
+        let (sessions, issues) = parse_mapped_csv(csv, &mapping).expect("mapping should resolve");
+        let issues = issues
+            .iter()
+            .map(|issue| issue.source.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(issues, ["row 5", "row 6"]);
+
+        let at = |day: u32, hour: u32, minute: u32| {
+            Utc.with_ymd_and_hms(2026, 3, day, hour, minute, 0).unwrap()
+        };
+        assert_eq!(sessions.len(), 3);
+        assert_eq!(sessions[0].note.as_deref(), Some("first pass"));
+        assert_eq!(
+            (sessions[0].start, sessions[0].end),
+            (at(2, 9, 0), Some(at(2, 10, 30)))
+        );
+        assert_eq!(sessions[1].project, "Internal");
+        assert_eq!(sessions[1].end, Some(at(3, 1, 0)));
+        assert_eq!(sessions[2].end, Some(at(3, 13, 0) + Duration::minutes(90)));
+
+        let missing = CsvMapping {
+            task: Some("Nope".to_string()),
+            ..mapping
+        };
+        assert!(parse_mapped_csv(csv, &missing).is_err());
+    }
+
+    #[test]
+    fn out_of_range_durations_are_row_errors() {
+        let csv = "Task,Start,Minutes\n\
+            Design,2026-03-02 09:00,9999999999999999\n\
+            Design,2026-03-02 09:00,99999999999:00\n\
+            Design,2026-03-02 09:00,9999999999999:00:00\n\
+            Design,2026-03-02 09:00,45\n";
+        let mapping = CsvMapping {
+            default_project: Some("Internal".to_string()),
+            task: Some("Task".to_string()),
+            start: Some("Start".to_string()),
+            duration: Some("Minutes".to_string()),
+            timezone: Some("UTC".to_string()),
+            ..CsvMapping::default()
+        };
+
+        let (sessions, issues) = parse_mapped_csv(csv, &mapping).expect("mapping should resolve");
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(
+            sessions[0].end,
+            Some(Utc.with_ymd_and_hms(2026, 3, 2, 9, 45, 0).unwrap())
+        );
+        let sources = issues
+            .iter()
+            .map(|issue| issue.source.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(sources, ["row 2", "row 3", "row 4"]);
+        assert!(
+            issues
+                .iter()
+                .all(|issue| issue.message.contains("out of range"))
+        );
+    }
+}
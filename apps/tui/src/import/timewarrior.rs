@@ -0,0 +1,236 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use super::{ImportIssue, ImportedSession, TagMapping, task_from_tags};
+use crate::ledgers::home_dir;
+
+const UNTAGGED_PROJECT: &str = "Untagged";
+
+/// `$TIMEWARRIORDB/data`, else `~/.timewarrior/data` when it exists, else
+/// the XDG location `~/.local/share/timewarrior/data`.
+pub fn default_timewarrior_dir() -> Result<PathBuf, String> {
+    if let Some(path) = env::var_os("TIMEWARRIORDB") {
+        return Ok(PathBuf::from(path).join("data"));
+    }
+    let legacy = home_dir()?.join(".timewarrior").join("data");
+    if legacy.is_dir() {
+        return Ok(legacy);
+    }
+    let data_home = match env::var_os("XDG_DATA_HOME") {
+        Some(path) => PathBuf::from(path),
+        None => home_dir()?.join(".local").join("share"),
+    };
+    Ok(data_home.join("timewarrior").join("data"))
+}
+
+/// Reads a Timewarrior `YYYY-MM.data` file, or every such file in a data
+/// directory.
+pub fn read_timewarrior(
+    path: &Path,
+    mapping: &TagMapping,
+) -> Result<(Vec<ImportedSession>, Vec<ImportIssue>), String> {
+    let files = if path.is_dir() {
+        let mut files = fs::read_dir(path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|file| is_month_file(file))
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut sessions = Vec::new();
+    let mut issues = Vec::new();
+    for file in files {
+        let input = fs::read_to_string(&file)
+            .map_err(|err| format!("failed to read {}: {err}", file.display()))?;
+        let name = file
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let (file_sessions, file_issues) = parse_timewarrior_data(&input, &name, mapping);
+        sessions.extend(file_sessions);
+        issues.extend(file_issues);
+    }
+    Ok((sessions, issues))
+}
+
+/// Timewarrior keeps intervals in month files next to `tags.data` and
+/// `undo.data`, which are not intervals.
+fn is_month_file(path: &Path) -> bool {
+    let Some(stem) = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_suffix(".data"))
+    else {
+        return false;
+    };
+    let bytes = stem.as_bytes();
+    bytes.len() == 7
+        && bytes[4] == b'-'
+        && bytes
+            .iter()
+            .enumerate()
+            .all(|(index, byte)| index == 4 || byte.is_ascii_digit())
+}
+
+/// Parses interval lines such as
+/// `inc 20260302T090000Z - 20260302T103000Z # client "code review" # "notes"`.
+/// An interval without an end is still running.
+///
+/// The project comes from the first tag with a `--map` rule, else from the
+/// first tag; the remaining tags name the task and the annotation becomes
+/// the session note.
+pub fn parse_timewarrior_data(
+    input: &str,
+    file_name: &str,
+    mapping: &TagMapping,
+) -> (Vec<ImportedSession>, Vec<ImportIssue>) {
+    let mut sessions = Vec::new();
+    let mut issues = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let source = format!("{file_name}:{}", index + 1);
+        match parse_interval(line, mapping) {
+            Ok(session) => sessions.push(ImportedSession { source, ..session }),
+            Err(message) => issues.push(ImportIssue::new(source, message)),
+        }
+    }
+    (sessions, issues)
+}
+
+fn parse_interval(line: &str, mapping: &TagMapping) -> Result<ImportedSession, String> {
+    let rest = line
+        .strip_prefix("inc ")
+        .ok_or_else(|| "expected a line starting with 'inc'".to_string())?;
+    let (range, rest) = match rest.split_once(" # ") {
+        Some((range, rest)) => (range, Some(rest)),
+        None => (rest.trim_end_matches(" #"), None),
+    };
+    let (start, end) = match range.split_once(" - ") {
+        Some((start, end)) => (parse_timestamp(start)?, Some(parse_timestamp(end)?)),
+        None => (parse_timestamp(range)?, None),
+    };
+    if end.is_some_and(|end| end <= start) {
+        return Err("end must be after start".to_string());
+    }
+
+    let mut words = split_words(rest.unwrap_or_default())?;
+    // A second `#` separates the tags from the annotation.
+    let annotation = match words.iter().position(|word| word == "#") {
+        Some(position) => {
+            let annotation = words.split_off(position + 1).join(" ");
+            words.pop();
+            Some(annotation).filter(|annotation| !annotation.is_empty())
+        }
+        None => None,
+    };
+    let mut tags = words;
+    let project = mapping
+        .take_project(&mut tags)
+        .or_else(|| (!tags.is_empty()).then(|| tags.remove(0)))
+        .unwrap_or_else(|| UNTAGGED_PROJECT.to_string());
+
+    Ok(ImportedSession {
+        source: String::new(),
+        project,
+        category: None,
+        task: task_from_tags(&tags),
+        start,
+        end,
+        note: annotation,
+    })
+}
+
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, String> {
+    NaiveDateTime::parse_from_str(value.trim(), "%Y%m%dT%H%M%SZ")
+        .map(|timestamp| timestamp.and_utc())
+        .map_err(|_| format!("invalid timestamp '{}'", value.trim()))
+}
+
+/// Splits on spaces, keeping double-quoted words (with `\"` escapes)
+/// together. A bare `#` stays a word of its own.
+fn split_words(input: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&ch) = chars.peek() {
+        if ch == ' ' {
+            chars.next();
+            continue;
+        }
+        let mut word = String::new();
+        if ch == '"' {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('\\') => word.extend(chars.next()),
+                    Some('"') => break,
+                    Some(ch) => word.push(ch),
+                    None => return Err("unterminated quote".to_string()),
+                }
+            }
+        } else {
+            while let Some(&ch) = chars.peek() {
+                if ch == ' ' {
+                    break;
+                }
+                word.push(ch);
+                chars.next();
+            }
+        }
+        words.push(word);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::parse_timewarrior_data;
+    use crate::import::TagMapping;
+
+    #[test]
+    fn parses_closed_and_open_intervals_with_tags_and_annotations() {
+        let data = "inc 20260302T090000Z - 20260302T103000Z # acme \"code review\" # \"PR \\\"42\\\"\"\n\
+            inc 20260302T110000Z - 20260302T113000Z # meeting infra\n\
+            inc 20260302T120000Z - 20260302T113000Z # broken\n\
+            inc 20260302T140000Z # # \"just started\"\n";
+        let mapping = TagMapping::parse(&["infra=Operations".to_string()]).expect("rule parses");
+
+        let (sessions, issues) = parse_timewarrior_data(data, "2026-03.data", &mapping);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].source, "2026-03.data:3");
+        assert_eq!(sessions.len(), 3);
+
+        let review = &sessions[0];
+        assert_eq!(
+            (review.project.as_str(), review.task.as_str()),
+            ("acme", "code review")
+        );
+        assert_eq!(review.note.as_deref(), Some("PR \"42\""));
+        assert_eq!(
+            review.end,
+            Some(Utc.with_ymd_and_hms(2026, 3, 2, 10, 30, 0).unwrap())
+        );
+
+        let mapped = &sessions[1];
+        assert_eq!(
+            (mapped.project.as_str(), mapped.task.as_str()),
+            ("Operations", "meeting")
+        );
+
+        let open = &sessions[2];
+        assert_eq!(open.project, "Untagged");
+        assert_eq!(open.end, None);
+        assert_eq!(open.note.as_deref(), Some("just started"));
+    }
+}
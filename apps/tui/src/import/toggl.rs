@@ -0,0 +1,287 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use clap::ValueEnum;
+
+use super::{ImportIssue, ImportedSession, NO_DESCRIPTION, SourceTimezone};
+
+/// Which Toggl column becomes the ledger category.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TogglCategorySource {
+    #[default]
+    Client,
+    /// The first tag; the remaining tags stay in the note
+    Tag,
+    None,
+}
+
+const NO_PROJECT: &str = "No project";
+
+/// Reads a Toggl Track "detailed report" CSV export.
+///
+/// Project maps to project and client (or the first tag) to category. The
+/// Toggl task becomes the task with the description as session note; rows
+/// without a task use the description as the task. Tags not used as the
+/// category are appended to the note as `#tag`. Dates and times without an
+/// offset are read in `timezone`.
+pub fn parse_toggl_csv(
+    input: &str,
+    timezone: &SourceTimezone,
+    category_source: TogglCategorySource,
+) -> (Vec<ImportedSession>, Vec<ImportIssue>) {
+    let mut sessions = Vec::new();
+    let mut issues = Vec::new();
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(input.trim_start_matches('\u{feff}').as_bytes());
+
+    let headers = match reader.headers() {
+        Ok(headers) => headers
+            .iter()
+            .map(|header| header.trim().to_ascii_lowercase())
+            .collect::<Vec<_>>(),
+        Err(err) => {
+            issues.push(ImportIssue::new("header", err.to_string()));
+            return (sessions, issues);
+        }
+    };
+    let column = |name: &str| headers.iter().position(|header| header == name);
+    let Some(start_date) = column("start date") else {
+        issues.push(ImportIssue::new(
+            "header",
+            "missing 'Start date' column; is this a Toggl detailed report?",
+        ));
+        return (sessions, issues);
+    };
+    let columns = TogglColumns {
+        project: column("project"),
+        client: column("client"),
+        task: column("task"),
+        description: column("description"),
+        tags: column("tags"),
+        start_date,
+        start_time: column("start time"),
+        end_date: column("end date"),
+        end_time: column("end time"),
+        duration: column("duration"),
+    };
+
+    for (index, record) in reader.records().enumerate() {
+        // Row 1 is the header line.
+        let source = format!("row {}", index + 2);
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                issues.push(ImportIssue::new(source, err.to_string()));
+                continue;
+            }
+        };
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| record.get(column))
+                .map(str::trim)
+                .unwrap_or_default()
+        };
+        match parse_row(&columns, &field, timezone, category_source) {
+            Ok(session) => sessions.push(ImportedSession { source, ..session }),
+            Err(message) => issues.push(ImportIssue::new(source, message)),
+        }
+    }
+
+    (sessions, issues)
+}
+
+struct TogglColumns {
+    project: Option<usize>,
+    client: Option<usize>,
+    task: Option<usize>,
+    description: Option<usize>,
+    tags: Option<usize>,
+    start_date: usize,
+    start_time: Option<usize>,
+    end_date: Option<usize>,
+    end_time: Option<usize>,
+    duration: Option<usize>,
+}
+
+fn parse_row<'a>(
+    columns: &TogglColumns,
+    field: &impl Fn(Option<usize>) -> &'a str,
+    timezone: &SourceTimezone,
+    category_source: TogglCategorySource,
+) -> Result<ImportedSession, String> {
+    let start = parse_timestamp(
+        field(Some(columns.start_date)),
+        field(columns.start_time),
+        timezone,
+        "start",
+    )?;
+    let end = if !field(columns.end_date).is_empty() {
+        parse_timestamp(
+            field(columns.end_date),
+            field(columns.end_time),
+            timezone,
+            "end",
+        )?
+    } else if !field(columns.duration).is_empty() {
+        start
+            .checked_add_signed(parse_toggl_duration(field(columns.duration))?)
+            .ok_or("duration is out of range")?
+    } else {
+        return Err("missing end date and duration".to_string());
+    };
+    if end <= start {
+        return Err("end must be after start".to_string());
+    }
+
+    let mut tags = field(columns.tags)
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .collect::<Vec<_>>();
+    let category = match category_source {
+        TogglCategorySource::Client => non_empty(field(columns.client)),
+        TogglCategorySource::Tag if !tags.is_empty() => Some(tags.remove(0).to_string()),
+        TogglCategorySource::Tag | TogglCategorySource::None => None,
+    };
+
+    let description = field(columns.description);
+    let (task, note) = match non_empty(field(columns.task)) {
+        Some(task) => (task, non_empty(description)),
+        None => (
+            non_empty(description).unwrap_or_else(|| NO_DESCRIPTION.to_string()),
+            None,
+        ),
+    };
+    let tags = tags
+        .iter()
+        .map(|tag| format!("#{tag}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let note = match (note, non_empty(&tags)) {
+        (Some(note), Some(tags)) => Some(format!("{note} {tags}")),
+        (note, tags) => note.or(tags),
+    };
+
+    Ok(ImportedSession {
+        source: String::new(),
+        project: non_empty(field(columns.project)).unwrap_or_else(|| NO_PROJECT.to_string()),
+        category,
+        task,
+        start,
+        end: Some(end),
+        note,
+    })
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Toggl writes local wall-clock dates and times; API-based exports may
+/// instead put a full RFC 3339 timestamp in the date column.
+fn parse_timestamp(
+    date: &str,
+    time: &str,
+    timezone: &SourceTimezone,
+    what: &str,
+) -> Result<DateTime<Utc>, String> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(date) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(date, "%m/%d/%Y"))
+        .map_err(|_| format!("invalid {what} date '{date}'"))?;
+    let time = NaiveTime::parse_from_str(time, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+        .map_err(|_| format!("invalid {what} time '{time}'"))?;
+    timezone.resolve(NaiveDateTime::new(date, time))
+}
+
+/// Toggl durations are `H:MM:SS`, with hours that may exceed 24.
+fn parse_toggl_duration(value: &str) -> Result<Duration, String> {
+    let parts = value
+        .split(':')
+        .map(|part| part.parse::<i64>().ok().filter(|part| *part >= 0))
+        .collect::<Option<Vec<_>>>();
+    match parts.as_deref() {
+        Some([hours, minutes, seconds]) if *minutes < 60 && *seconds < 60 => {
+            Duration::try_hours(*hours)
+                .and_then(|hours| hours.checked_add(&Duration::minutes(*minutes)))
+                .and_then(|total| total.checked_add(&Duration::seconds(*seconds)))
+                .ok_or_else(|| format!("duration '{value}' is out of range"))
+        }
+        _ => Err(format!("invalid duration '{value}'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{TogglCategorySource, parse_toggl_csv};
+    use crate::import::SourceTimezone;
+
+    #[test]
+    fn maps_toggl_columns_onto_sessions() {
+        let csv = "\u{feff}User,Email,Client,Project,Task,Description,Billable,Start date,Start time,End date,End time,Duration,Tags\n\
+            Ana,ana@example.com,Acme,Website,Design,\"Header, footer\",Yes,2026-03-02,09:00:00,2026-03-02,10:30:00,01:30:00,\"urgent, review\"\n\
+            Ana,ana@example.com,,,,,No,2026-03-02,23:30:00,,,02:00:00,\n\
+            Ana,ana@example.com,,Website,,Broken,No,2026-03-02,nine,2026-03-02,10:00:00,,\n";
+        let timezone = SourceTimezone::parse("+01:00").expect("offset should parse");
+
+        let (sessions, issues) = parse_toggl_csv(csv, &timezone, TogglCategorySource::Client);
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].source, "row 4");
+
+        let first = &sessions[0];
+        assert_eq!(
+            (first.project.as_str(), first.category.as_deref()),
+            ("Website", Some("Acme"))
+        );
+        assert_eq!(first.task, "Design");
+        assert_eq!(
+            first.note.as_deref(),
+            Some("Header, footer #urgent #review")
+        );
+        assert_eq!(
+            first.start,
+            Utc.with_ymd_and_hms(2026, 3, 2, 8, 0, 0).unwrap()
+        );
+
+        let second = &sessions[1];
+        assert_eq!(second.project, "No project");
+        assert_eq!(second.task, "(no description)");
+        assert_eq!(
+            second.end,
+            Some(Utc.with_ymd_and_hms(2026, 3, 3, 0, 30, 0).unwrap())
+        );
+
+        let (tagged, _) = parse_toggl_csv(csv, &timezone, TogglCategorySource::Tag);
+        assert_eq!(tagged[0].category.as_deref(), Some("urgent"));
+        assert_eq!(tagged[0].note.as_deref(), Some("Header, footer #review"));
+    }
+
+    #[test]
+    fn out_of_range_durations_are_row_errors() {
+        let csv = "Project,Description,Start date,Start time,Duration\n\
+            Website,Huge,2026-03-02,09:00:00,99999999999:00:00\n\
+            Website,Late,2026-03-02,09:00:00,9999999999999:00:00\n\
+            Website,Fine,2026-03-02,09:00:00,01:00:00\n";
+        let timezone = SourceTimezone::parse("UTC").expect("UTC should parse");
+
+        let (sessions, issues) = parse_toggl_csv(csv, &timezone, TogglCategorySource::Client);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].task, "Fine");
+        let sources = issues
+            .iter()
+            .map(|issue| issue.source.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(sources, ["row 2", "row 3"]);
+        assert!(
+            issues
+                .iter()
+                .all(|issue| issue.message.contains("out of range"))
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use super::{ImportIssue, ImportedSession, TagMapping, task_from_tags};
+use crate::ledgers::home_dir;
+
+/// `$WATSON_DIR`, else Watson's config directory (`~/.config/watson`).
+pub fn default_watson_dir() -> Result<PathBuf, String> {
+    if let Some(path) = env::var_os("WATSON_DIR") {
+        return Ok(PathBuf::from(path));
+    }
+    let config_home = match env::var_os("XDG_CONFIG_HOME") {
+        Some(path) => PathBuf::from(path),
+        None => home_dir()?.join(".config"),
+    };
+    Ok(config_home.join("watson"))
+}
+
+/// Reads Watson's `frames` file, or the `frames` and `state` files of a
+/// Watson directory; a frame in `state` is imported as a running session.
+pub fn read_watson(
+    path: &Path,
+    mapping: &TagMapping,
+) -> Result<(Vec<ImportedSession>, Vec<ImportIssue>), String> {
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|err| format!("failed to read {}: {err}", path.display()))
+    };
+    let (frames, state) = if path.is_dir() {
+        let state_path = path.join("state");
+        let state = if state_path.exists() {
+            Some(read(&state_path)?)
+        } else {
+            None
+        };
+        (read(&path.join("frames"))?, state)
+    } else {
+        (read(path)?, None)
+    };
+    parse_watson(&frames, state.as_deref(), mapping)
+}
+
+/// Frames are `[start, stop, project, id, tags, updated_at]` with Unix
+/// timestamps. The Watson project is the project unless a tag has a
+/// `--map` rule; the remaining tags name the task.
+pub fn parse_watson(
+    frames: &str,
+    state: Option<&str>,
+    mapping: &TagMapping,
+) -> Result<(Vec<ImportedSession>, Vec<ImportIssue>), String> {
+    let frames = serde_json::from_str::<Vec<Value>>(frames)
+        .map_err(|err| format!("invalid Watson frames file: {err}"))?;
+    let mut sessions = Vec::new();
+    let mut issues = Vec::new();
+
+    for (index, frame) in frames.iter().enumerate() {
+        let fields = frame.as_array().map(Vec::as_slice).unwrap_or_default();
+        let source = match fields.get(3).and_then(Value::as_str) {
+            Some(id) => format!("frame {}", id.chars().take(7).collect::<String>()),
+            None => format!("frame #{}", index + 1),
+        };
+        let parsed = (|| {
+            let start = timestamp(fields.first(), "start")?;
+            let end = timestamp(fields.get(1), "stop")?;
+            if end <= start {
+                return Err("stop must be after start".to_string());
+            }
+            frame_session(fields.get(2), fields.get(4), start, Some(end), mapping)
+        })();
+        match parsed {
+            Ok(session) => sessions.push(ImportedSession { source, ..session }),
+            Err(message) => issues.push(ImportIssue::new(source, message)),
+        }
+    }
+
+    if let Some(state) = state {
+        let state = serde_json::from_str::<Value>(state)
+            .map_err(|err| format!("invalid Watson state file: {err}"))?;
+        // An idle Watson writes `{}`.
+        if state.get("project").is_some() {
+            let parsed = timestamp(state.get("start"), "start").and_then(|start| {
+                frame_session(
+                    state.get("project"),
+                    state.get("tags"),
+                    start,
+                    None,
+                    mapping,
+                )
+            });
+            match parsed {
+                Ok(session) => sessions.push(ImportedSession {
+                    source: "current frame".to_string(),
+                    ..session
+                }),
+                Err(message) => issues.push(ImportIssue::new("current frame", message)),
+            }
+        }
+    }
+
+    Ok((sessions, issues))
+}
+
+fn frame_session(
+    project: Option<&Value>,
+    tags: Option<&Value>,
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+    mapping: &TagMapping,
+) -> Result<ImportedSession, String> {
+    let project = project
+        .and_then(Value::as_str)
+        .filter(|project| !project.trim().is_empty())
+        .ok_or_else(|| "missing project".to_string())?;
+    let mut tags = tags
+        .and_then(Value::as_array)
+        .map(|tags| {
+            tags.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let project = mapping
+        .take_project(&mut tags)
+        .unwrap_or_else(|| project.to_string());
+
+    Ok(ImportedSession {
+        source: String::new(),
+        project,
+        category: None,
+        task: task_from_tags(&tags),
+        start,
+        end,
+        note: None,
+    })
+}
+
+fn timestamp(value: Option<&Value>, what: &str) -> Result<DateTime<Utc>, String> {
+    value
+        .and_then(Value::as_i64)
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .ok_or_else(|| format!("invalid {what} timestamp"))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::parse_watson;
+    use crate::import::TagMapping;
+
+    #[test]
+    fn parses_frames_and_the_running_state() {
+        let frames = r#"[
+            [1772442000, 1772447400, "acme", "a1b2c3d4e5f6", ["design", "web"], 1772447400],
+            [1772449200, 1772451000, "misc", "b2c3d4e5f6a1", ["ops"], 1772451000],
+            [1772452800, 1772452000, "acme", "c3d4e5f6a1b2", [], 1772452000]
+        ]"#;
+        let state = r#"{"project": "acme", "start": 1772456400, "tags": []}"#;
+        let mapping = TagMapping::parse(&["ops=Operations".to_string()]).expect("rule parses");
+
+        let (sessions, issues) =
+            parse_watson(frames, Some(state), &mapping).expect("files should parse");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].source, "frame c3d4e5f");
+
+        let tasks = sessions
+            .iter()
+            .map(|session| (session.project.as_str(), session.task.as_str(), session.end))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tasks,
+            [
+                (
+                    "acme",
+                    "design web",
+                    Some(Utc.with_ymd_and_hms(2026, 3, 2, 10, 30, 0).unwrap())
+                ),
+                (
+                    "Operations",
+                    "(no description)",
+                    Some(Utc.with_ymd_and_hms(2026, 3, 2, 11, 30, 0).unwrap())
+                ),
+                ("acme", "(no description)", None),
+            ]
+        );
+        assert_eq!(sessions[2].source, "current frame");
+    }
+}
@@ -18,10 +18,10 @@ pub fn resolve_ledger_path(cli_path: Option<PathBuf>) -> Result<PathBuf, Error>
         }
     }
 
-    if let Ok(mut recent) = recent_ledgers(MAX_RECENT_LEDGERS) {
-        if let Some(path) = recent.drain(..).next() {
-            return Ok(path);
-        }
+    if let Ok(mut recent) = recent_ledgers(MAX_RECENT_LEDGERS)
+        && let Some(path) = recent.drain(..).next()
+    {
+        return Ok(path);
     }
 
     Err(Error::new(
@@ -62,6 +62,50 @@ pub fn ledger_path_from_input(input: &str) -> Result<PathBuf, String> {
     Ok(absolutize(path))
 }
 
+/// Resolves `--ledger` values, expanding glob patterns such as
+/// `~/time/2025-*.ledger` (sorted) and dropping repeated files. A value
+/// naming an existing file is taken literally, as is a value without `*` or
+/// `?` that matches nothing, so `acme[2025].ledger` works like any other
+/// path whether or not it exists yet.
+pub fn ledger_paths_from_inputs(inputs: &[String]) -> Result<Vec<PathBuf>, String> {
+    let mut paths = Vec::new();
+    for input in inputs {
+        let path = ledger_path_from_input(input)?;
+        if !input.contains(['*', '?', '[']) || path.exists() {
+            paths.push(path);
+            continue;
+        }
+        let literal_fallback = !input.contains(['*', '?']);
+        let pattern = path.to_string_lossy();
+        let mut matches = match glob::glob(&pattern) {
+            Ok(entries) => entries
+                .filter_map(Result::ok)
+                .filter(|path| path.is_file())
+                .map(absolutize)
+                .collect::<Vec<_>>(),
+            Err(_) if literal_fallback => Vec::new(),
+            Err(err) => return Err(format!("invalid ledger pattern '{input}': {err}")),
+        };
+        if matches.is_empty() {
+            if literal_fallback {
+                paths.push(path);
+                continue;
+            }
+            return Err(format!("no ledgers match '{input}'"));
+        }
+        matches.sort();
+        paths.extend(matches);
+    }
+
+    let mut unique = Vec::with_capacity(paths.len());
+    for path in paths {
+        if !unique.contains(&path) {
+            unique.push(path);
+        }
+    }
+    Ok(unique)
+}
+
 pub fn recent_ledgers(limit: usize) -> Result<Vec<PathBuf>, std::io::Error> {
     let path = recent_ledgers_path();
     let raw = match fs::read_to_string(path) {
@@ -142,7 +186,7 @@ fn expand_home_path(input: &str) -> Result<PathBuf, String> {
     Ok(PathBuf::from(input))
 }
 
-fn home_dir() -> Result<PathBuf, String> {
+pub fn home_dir() -> Result<PathBuf, String> {
     if let Some(path) = env::var_os("HOME") {
         return Ok(PathBuf::from(path));
     }
@@ -172,3 +216,47 @@ fn absolutize(path: PathBuf) -> PathBuf {
         path
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::ledger_paths_from_inputs;
+
+    #[test]
+    fn bracketed_ledger_names_are_taken_literally() {
+        let dir = std::env::temp_dir().join(format!("chronos-ledgers-{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("temp dir should be created");
+        let dir = fs::canonicalize(&dir).expect("temp dir should resolve");
+        let input = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        for name in ["acme[2025].ledger", "2025-a.ledger", "2025-b.ledger"] {
+            fs::write(dir.join(name), "").expect("ledger should be written");
+        }
+
+        let existing = ledger_paths_from_inputs(&[input("acme[2025].ledger")]);
+        assert_eq!(existing, Ok(vec![dir.join("acme[2025].ledger")]));
+
+        let new = ledger_paths_from_inputs(&[input("new[1].ledger")]);
+        assert_eq!(new, Ok(vec![dir.join("new[1].ledger")]));
+
+        let globbed = ledger_paths_from_inputs(&[input("2025-[ab].ledger")]);
+        assert_eq!(
+            globbed,
+            Ok(vec![dir.join("2025-a.ledger"), dir.join("2025-b.ledger")])
+        );
+
+        let several = [input("missing[1].ledger"), input("acme[2025].ledger")];
+        assert_eq!(
+            ledger_paths_from_inputs(&several),
+            Ok(vec![
+                dir.join("missing[1].ledger"),
+                dir.join("acme[2025].ledger")
+            ])
+        );
+        let unmatched = [input("2025-a.ledger"), input("none-*.ledger")];
+        assert!(ledger_paths_from_inputs(&unmatched).is_err());
+        assert!(ledger_paths_from_inputs(&[input("none-*.ledger")]).is_err());
+
+        fs::remove_dir_all(&dir).expect("temp dir should be removed");
+    }
+}
@@ -1,25 +1,57 @@
+mod diff;
 mod domain;
+mod export;
+mod import;
 mod ledgers;
+mod output;
+mod report;
 mod storage;
+mod theme;
+mod timeinput;
 mod ui;
 
 use std::collections::HashMap;
 use std::error::Error;
-use std::path::PathBuf;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
-use chrono::{Duration, NaiveDate, Utc};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, SubsecRound, Utc};
 use clap::{Parser, Subcommand};
+use serde_json::{Map, Value, json};
 
-use crate::domain::{Ledger, format_duration};
-use crate::ledgers::{recent_ledgers, remember_ledger, resolve_ledger_path};
+use crate::diff::{EventChange, EventDiff, EventSide, FieldChange, LedgerDiff, diff_ledgers};
+use crate::domain::{EntitySelection, EventKind, Ledger, format_duration};
+use crate::export::{ExportedSession, export_sessions, render_ics};
+use crate::import::{
+    CsvMapping, ImportIssue, ImportedSession, SourceTimezone, TagMapping, TogglCategorySource,
+    apply_import, default_timewarrior_dir, default_watson_dir, parse_mapped_csv, parse_toggl_csv,
+    read_timewarrior, read_watson,
+};
+use crate::ledgers::{
+    ledger_paths_from_inputs, recent_ledgers, remember_ledger, resolve_ledger_path,
+};
+use crate::output::{OutputFormat, Table, duration_json, print_json};
+use crate::report::{Report, ReportFilter, ReportGroup, build_report, end_of_month, start_of_week};
 use crate::storage::{load_ledger, save_ledger};
-use crate::ui::{print_event_log, run_dashboard};
+use crate::theme::{Theme, ThemeName, parse_color};
+use crate::timeinput::{
+    parse_date_input, parse_duration_input, parse_end_time_input, parse_time_input,
+};
+use crate::ui::run_dashboard;
 
 #[derive(Debug, Parser)]
 #[command(name = "chronos-timeledger", about = "Terminal-first time tracker")]
 struct Cli {
+    /// Ledger file; `report` and `summary` combine several (repeat the flag
+    /// or quote a glob such as `'~/time/*.ledger'`)
     #[arg(long)]
-    ledger: Option<PathBuf>,
+    ledger: Vec<String>,
+    #[arg(long, value_enum)]
+    theme: Option<ThemeName>,
+    /// Output format for listings, summaries, reports and status
+    #[arg(long, value_enum, global = true, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
     #[command(subcommand)]
     command: Option<Command>,
 }
@@ -53,18 +85,134 @@ enum Command {
         task: String,
         #[arg(long)]
         note: Option<String>,
+        /// When the session started, e.g. `09:30`, `-15m` or `yesterday 17:00`
+        #[arg(long, allow_hyphen_values = true)]
+        at: Option<String>,
     },
     Stop {
+        #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+        task: Option<String>,
+        /// Stop every running task
+        #[arg(long, conflicts_with = "note")]
+        all: bool,
+        #[arg(long)]
+        note: Option<String>,
+        /// When the session ended, e.g. `17:30` or `-5m`
+        #[arg(long, allow_hyphen_values = true)]
+        at: Option<String>,
+    },
+    /// Stop everything that is running and start one task at the same time
+    Switch {
+        #[arg(long)]
+        task: String,
+        #[arg(long)]
+        note: Option<String>,
+        #[arg(long, allow_hyphen_values = true)]
+        at: Option<String>,
+    },
+    /// Restart the most recently stopped task with its last session note
+    Resume {
+        #[arg(long, allow_hyphen_values = true)]
+        at: Option<String>,
+    },
+    /// Record a finished session after the fact
+    Log {
         #[arg(long)]
         task: String,
+        /// Session start, e.g. `09:30`, `-2h` or `2026-03-08 09:15`
+        #[arg(long, allow_hyphen_values = true)]
+        from: String,
+        /// Session end, either a time or a duration from the start (`+45m`)
+        #[arg(
+            long,
+            allow_hyphen_values = true,
+            required_unless_present = "duration",
+            conflicts_with = "duration"
+        )]
+        to: Option<String>,
+        /// Session length, e.g. `45m` or `1h30`
+        #[arg(long)]
+        duration: Option<String>,
         #[arg(long)]
         note: Option<String>,
     },
+    /// Print running tasks and today's total, e.g. for status bars
+    Status {
+        /// Template with {task}, {project}, {elapsed}, {started}, {note},
+        /// {tasks}, {count} and {today}; the task fields describe the most
+        /// recently started task
+        #[arg(long)]
+        template: Option<String>,
+        /// Text printed instead of the template when nothing is running
+        #[arg(long)]
+        idle: Option<String>,
+        /// Same as `--format json`
+        #[arg(long, conflicts_with = "waybar")]
+        json: bool,
+        /// Emit waybar custom-module JSON (text, tooltip, class)
+        #[arg(long)]
+        waybar: bool,
+    },
     ListTasks,
     Summary {
         #[arg(long)]
         day: Option<String>,
+        /// Combine every existing recent ledger
+        #[arg(long)]
+        all_recent: bool,
+    },
+    /// Totals over a date range, grouped by period and/or entity
+    Report {
+        #[command(flatten)]
+        range: RangeArgs,
+        /// Comma-separated grouping, e.g. `day,project` or `month,category,task`
+        #[arg(long, value_enum, value_delimiter = ',', default_value = "project")]
+        by: Vec<ReportGroup>,
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Combine every existing recent ledger
+        #[arg(long)]
+        all_recent: bool,
+    },
+    /// Copy projects, categories and tasks from another ledger, keeping
+    /// their IDs; without a selection, copies everything not archived
+    CopyEntities {
+        /// Ledger to copy from
+        #[arg(long)]
+        from: PathBuf,
+        /// Copy this project and its active tasks (repeatable)
+        #[arg(long)]
+        project: Vec<String>,
+        /// Copy this category (repeatable)
+        #[arg(long)]
+        category: Vec<String>,
+        /// Copy this task with its project and category (repeatable)
+        #[arg(long)]
+        task: Vec<String>,
+        /// Report what would be copied without writing the ledger
+        #[arg(long)]
+        dry_run: bool,
     },
+    /// Move closed sessions before a day into a separate archive ledger
+    Archive {
+        /// First ledger day to keep (YYYY-MM-DD); sessions crossing its start
+        /// are split there
+        #[arg(long)]
+        before: String,
+        /// Archive ledger to create [default: NAME-before-DAY.ledger next to
+        /// the current ledger]
+        #[arg(long)]
+        to: Option<PathBuf>,
+        /// Report what would be archived without writing either ledger
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Export tracked sessions
+    #[command(subcommand)]
+    Export(ExportCommand),
+    /// Import sessions from other time trackers
+    #[command(subcommand)]
+    Import(ImportCommand),
     Events {
         #[arg(long, default_value_t = 20)]
         limit: usize,
@@ -73,6 +221,187 @@ enum Command {
         #[arg(long, default_value_t = 20)]
         limit: usize,
     },
+    /// Show what changed between two ledger files: settings, projects,
+    /// categories and tasks field by field, and added, removed or retimed
+    /// events
+    Diff {
+        before: PathBuf,
+        after: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ExportCommand {
+    /// One row per session with start, end, duration and note; use
+    /// `--format json|csv` for machine-readable output
+    Sessions {
+        #[command(flatten)]
+        range: RangeArgs,
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Split sessions that cross a ledger day boundary into one row per day
+        #[arg(long)]
+        split_days: bool,
+    },
+    /// iCalendar (.ics) with one event per session, for calendar overlays
+    Ics {
+        #[command(flatten)]
+        range: RangeArgs,
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ImportCommand {
+    /// Toggl Track CSV (Reports > Detailed > Export CSV)
+    Toggl {
+        file: PathBuf,
+        /// Timezone of the CSV's dates and times: local, UTC, an IANA name
+        /// like Europe/Berlin or an offset like +02:00
+        #[arg(long, default_value = "local")]
+        timezone: String,
+        /// Toggl column that becomes the ledger category
+        #[arg(long, value_enum, default_value_t)]
+        category_from: TogglCategorySource,
+        /// Report what would be imported without writing the ledger
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Any CSV with a header line, columns named by flags or a mapping file
+    Csv {
+        file: PathBuf,
+        /// TOML file with the column settings; flags override it
+        #[arg(long, value_name = "FILE")]
+        mapping: Option<PathBuf>,
+        #[command(flatten)]
+        columns: Box<CsvMapping>,
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Timewarrior data (a `YYYY-MM.data` file or the data directory);
+    /// the first tag becomes the project unless `--map` says otherwise
+    Timewarrior {
+        /// Defaults to `$TIMEWARRIORDB/data` or `~/.timewarrior/data`
+        path: Option<PathBuf>,
+        /// Send intervals with TAG to PROJECT (repeatable)
+        #[arg(long = "map", value_name = "TAG=PROJECT")]
+        map: Vec<String>,
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Watson frames (the `frames` file or the Watson directory, which also
+    /// imports the running frame)
+    Watson {
+        /// Defaults to `$WATSON_DIR` or `~/.config/watson`
+        path: Option<PathBuf>,
+        /// Send frames with TAG to PROJECT (repeatable)
+        #[arg(long = "map", value_name = "TAG=PROJECT")]
+        map: Vec<String>,
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Ledger-day range shared by `report` and `export`.
+#[derive(Debug, clap::Args)]
+#[command(group(
+    clap::ArgGroup::new("period").args(["week", "month", "year", "last_week"])
+))]
+struct RangeArgs {
+    /// First ledger day (YYYY-MM-DD, today or yesterday)
+    #[arg(long, conflicts_with = "period")]
+    from: Option<String>,
+    /// Last ledger day, inclusive
+    #[arg(long, conflicts_with = "period")]
+    to: Option<String>,
+    /// The current week (Monday to Sunday)
+    #[arg(long)]
+    week: bool,
+    /// The current month
+    #[arg(long)]
+    month: bool,
+    /// The current year
+    #[arg(long)]
+    year: bool,
+    /// The previous week
+    #[arg(long)]
+    last_week: bool,
+}
+
+impl RangeArgs {
+    /// The requested first and last ledger day; either end is `None` when
+    /// neither a period nor that bound was given.
+    fn resolve(&self, today: NaiveDate) -> Result<(Option<NaiveDate>, Option<NaiveDate>), String> {
+        let (from, to) = if self.week {
+            (
+                Some(start_of_week(today)),
+                Some(start_of_week(today) + Duration::days(6)),
+            )
+        } else if self.last_week {
+            let start = start_of_week(today) - Duration::days(7);
+            (Some(start), Some(start + Duration::days(6)))
+        } else if self.month {
+            (today.with_day(1), Some(end_of_month(today)))
+        } else if self.year {
+            (
+                NaiveDate::from_ymd_opt(today.year(), 1, 1),
+                NaiveDate::from_ymd_opt(today.year(), 12, 31),
+            )
+        } else {
+            (
+                self.from
+                    .as_deref()
+                    .map(|from| parse_date_input(from, "--from", today))
+                    .transpose()?,
+                self.to
+                    .as_deref()
+                    .map(|to| parse_date_input(to, "--to", today))
+                    .transpose()?,
+            )
+        };
+        if let (Some(from), Some(to)) = (from, to)
+            && from > to
+        {
+            return Err("--from must not be after --to".to_string());
+        }
+        Ok((from, to))
+    }
+}
+
+#[derive(Debug, clap::Args)]
+struct FilterArgs {
+    /// Only include these projects (repeatable)
+    #[arg(long)]
+    project: Vec<String>,
+    /// Only include these categories (repeatable)
+    #[arg(long)]
+    category: Vec<String>,
+    /// Only include these tasks (repeatable)
+    #[arg(long)]
+    task: Vec<String>,
+}
+
+impl FilterArgs {
+    fn resolve(&self, ledger: &Ledger) -> Result<ReportFilter, String> {
+        Ok(ReportFilter {
+            projects: self
+                .project
+                .iter()
+                .map(|query| resolve_project(ledger, query))
+                .collect::<Result<_, _>>()?,
+            categories: self
+                .category
+                .iter()
+                .map(|query| resolve_category(ledger, query))
+                .collect::<Result<_, _>>()?,
+            tasks: self
+                .task
+                .iter()
+                .map(|query| resolve_task(ledger, query, |_| false))
+                .collect::<Result<_, _>>()?,
+        })
+    }
 }
 
 fn main() {
@@ -86,12 +415,64 @@ fn run() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
 
     if let Some(Command::Ledgers { limit }) = &cli.command {
-        print_recent_ledgers(*limit)?;
+        print_recent_ledgers(*limit, cli.format)?;
+        return Ok(());
+    }
+    if let Some(Command::Diff { before, after }) = &cli.command {
+        let before = load_ledger(before)?;
+        let after = load_ledger(after)?;
+        print_diff(&before, &after, &diff_ledgers(&before, &after), cli.format);
         return Ok(());
     }
 
-    let mut ledger_path = resolve_ledger_path(cli.ledger)?;
+    let ledger_paths = ledger_paths_from_inputs(&cli.ledger)?;
+    let all_recent = matches!(
+        &cli.command,
+        Some(
+            Command::Report {
+                all_recent: true,
+                ..
+            } | Command::Summary {
+                all_recent: true,
+                ..
+            }
+        )
+    );
+    if ledger_paths.len() > 1 || all_recent {
+        let ledger = load_combined_ledger(ledger_paths, all_recent)?;
+        return match cli.command {
+            Some(Command::Report {
+                range, by, filter, ..
+            }) => print_range_report(&ledger, &range, &by, &filter, cli.format),
+            Some(Command::Summary { day, .. }) => {
+                print_summary(&ledger, day.as_deref(), cli.format)
+            }
+            _ => Err("only report and summary accept several ledgers".into()),
+        };
+    }
+
+    let mut ledger_path = resolve_ledger_path(ledger_paths.into_iter().next())?;
     let mut ledger = load_ledger(&ledger_path)?;
+
+    // Status bars poll `status` every second; keep that path read-only.
+    if let Some(Command::Status {
+        template,
+        idle,
+        json,
+        waybar,
+    }) = &cli.command
+    {
+        let output = if *waybar {
+            StatusOutput::Waybar
+        } else if *json {
+            StatusOutput::Format(OutputFormat::Json)
+        } else {
+            StatusOutput::Format(cli.format)
+        };
+        print_status(&ledger, template.as_deref(), idle.as_deref(), output);
+        return Ok(());
+    }
+
     if let Err(err) = remember_ledger(&ledger_path) {
         eprintln!("warning: failed to store recent ledger: {err}");
     }
@@ -102,9 +483,17 @@ fn run() -> Result<(), Box<dyn Error>> {
             println!("initialized ledger at {}", ledger_path.display());
         }
         Command::Dashboard => {
-            run_dashboard(&mut ledger, &mut ledger_path)?;
+            run_dashboard(&mut ledger, &mut ledger_path, Theme::resolve(cli.theme))?;
         }
         Command::AddProject { name, color } => {
+            if let Some(color) = &color
+                && parse_color(color).is_none()
+            {
+                return Err(format!(
+                    "invalid color '{color}': use a terminal color name, #rrggbb, or 0-255"
+                )
+                .into());
+            }
             let project_id = ledger.add_project(name, color);
             save_ledger(&ledger_path, &ledger)?;
             println!("created project {project_id}");
@@ -119,77 +508,979 @@ fn run() -> Result<(), Box<dyn Error>> {
             description,
             category,
         } => {
+            let project = resolve_project(&ledger, &project)?;
+            let category = category
+                .map(|category| resolve_category(&ledger, &category))
+                .transpose()?;
             let task_id = ledger.add_task(project, category, description)?;
             save_ledger(&ledger_path, &ledger)?;
             println!("created task {task_id}");
         }
-        Command::Start { task, note } => {
-            ledger.start_task(&task, Utc::now(), note)?;
+        Command::Start { task, note, at } => {
+            let now = Utc::now();
+            let timestamp = cli_timestamp(at.as_deref(), "--at", now)?;
+            let task = resolve_task(&ledger, &task, |_| false)?;
+            ledger.start_task(&task, timestamp, note, now)?;
+            save_ledger(&ledger_path, &ledger)?;
+            println!(
+                "started {}{}",
+                task_label(&ledger, &task),
+                cli_time_suffix(at.as_deref(), timestamp)
+            );
+        }
+        Command::Stop { all: true, at, .. } => {
+            let now = Utc::now();
+            let timestamp = cli_timestamp(at.as_deref(), "--at", now)?;
+            let stopped = ledger.stop_all(timestamp, now)?;
+            save_ledger(&ledger_path, &ledger)?;
+            if stopped.is_empty() {
+                println!("nothing running");
+            }
+            for task in &stopped {
+                println!(
+                    "stopped {}{}",
+                    task_label(&ledger, task),
+                    cli_time_suffix(at.as_deref(), timestamp)
+                );
+            }
+        }
+        Command::Stop { task, note, at, .. } => {
+            let task = task.ok_or("pass --task or --all")?;
+            let now = Utc::now();
+            let timestamp = cli_timestamp(at.as_deref(), "--at", now)?;
+            let active_tasks = ledger.snapshot(now).active_tasks;
+            let task = resolve_task(&ledger, &task, |id| active_tasks.contains_key(id))?;
+            ledger.stop_task(&task, timestamp, note, now)?;
             save_ledger(&ledger_path, &ledger)?;
-            println!("started {task}");
+            println!(
+                "stopped {}{}",
+                task_label(&ledger, &task),
+                cli_time_suffix(at.as_deref(), timestamp)
+            );
         }
-        Command::Stop { task, note } => {
-            ledger.stop_task(&task, Utc::now(), note)?;
+        Command::Switch { task, note, at } => {
+            let now = Utc::now();
+            let timestamp = cli_timestamp(at.as_deref(), "--at", now)?;
+            let task = resolve_task(&ledger, &task, |_| false)?;
+            let stopped = ledger.switch_to(&task, timestamp, note, now)?;
             save_ledger(&ledger_path, &ledger)?;
-            println!("stopped {task}");
+            for stopped_task in &stopped {
+                println!("stopped {}", task_label(&ledger, stopped_task));
+            }
+            println!(
+                "switched to {}{}",
+                task_label(&ledger, &task),
+                cli_time_suffix(at.as_deref(), timestamp)
+            );
+        }
+        Command::Resume { at } => {
+            let now = Utc::now();
+            let timestamp = cli_timestamp(at.as_deref(), "--at", now)?;
+            let (task, note) = ledger
+                .last_stopped_session()
+                .ok_or("no stopped task to resume")?;
+            ledger.start_task(&task, timestamp, note, now)?;
+            save_ledger(&ledger_path, &ledger)?;
+            println!(
+                "resumed {}{}",
+                task_label(&ledger, &task),
+                cli_time_suffix(at.as_deref(), timestamp)
+            );
+        }
+        Command::Log {
+            task,
+            from,
+            to,
+            duration,
+            note,
+        } => {
+            let now = Utc::now();
+            let today = now.with_timezone(&Local).date_naive();
+            let start = parse_time_input(&from, "--from", today, now)?;
+            let stop = match (to, duration) {
+                (Some(to), _) => parse_end_time_input(&to, "--to", start, today, now)?,
+                (None, Some(duration)) => start
+                    .checked_add_signed(parse_duration_input(&duration, "--duration")?)
+                    .ok_or("--duration is out of range")?,
+                (None, None) => return Err("pass --to or --duration".into()),
+            };
+            let task = resolve_task(&ledger, &task, |_| false)?;
+            ledger.log_session(&task, start, stop, note, now)?;
+            save_ledger(&ledger_path, &ledger)?;
+            println!(
+                "logged {} {} -> {} ({})",
+                task_label(&ledger, &task),
+                start.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+                stop.with_timezone(&Local).format("%H:%M"),
+                format_duration(stop - start)
+            );
         }
         Command::ListTasks => {
-            print_tasks(&ledger);
+            print_tasks(&ledger, cli.format);
+        }
+        Command::Summary { day, .. } => {
+            print_summary(&ledger, day.as_deref(), cli.format)?;
+        }
+        Command::Report {
+            range, by, filter, ..
+        } => {
+            print_range_report(&ledger, &range, &by, &filter, cli.format)?;
+        }
+        Command::Export(ExportCommand::Sessions {
+            range,
+            filter,
+            split_days,
+        }) => {
+            // Running sessions end "now"; keep exported timestamps whole.
+            let now = Utc::now().trunc_subsecs(0);
+            let (from, to) = range.resolve(ledger.day_for_timestamp(now))?;
+            let filter = filter.resolve(&ledger)?;
+            let sessions = export_sessions(&ledger, now, from, to, &filter, split_days);
+            print_sessions(&ledger, &sessions, cli.format);
+        }
+        Command::Export(ExportCommand::Ics { range, filter }) => {
+            let now = Utc::now().trunc_subsecs(0);
+            let (from, to) = range.resolve(ledger.day_for_timestamp(now))?;
+            let filter = filter.resolve(&ledger)?;
+            let sessions = export_sessions(&ledger, now, from, to, &filter, false);
+            print!("{}", render_ics(&ledger, &sessions, now));
+        }
+        Command::CopyEntities {
+            from,
+            project,
+            category,
+            task,
+            dry_run,
+        } => {
+            let source = load_ledger(&from)?;
+            let selection = EntitySelection {
+                projects: project
+                    .iter()
+                    .map(|query| resolve_project(&source, query))
+                    .collect::<Result<_, _>>()?,
+                categories: category
+                    .iter()
+                    .map(|query| resolve_category(&source, query))
+                    .collect::<Result<_, _>>()?,
+                tasks: task
+                    .iter()
+                    .map(|query| resolve_task(&source, query, |_| false))
+                    .collect::<Result<_, _>>()?,
+            };
+            let report = ledger.copy_entities_from(&source, &selection)?;
+            let verb = if dry_run { "would copy" } else { "copied" };
+            for (kind, names) in [
+                ("projects", &report.projects),
+                ("categories", &report.categories),
+                ("tasks", &report.tasks),
+            ] {
+                if !names.is_empty() {
+                    println!("{verb} {kind}: {}", names.join(", "));
+                }
+            }
+            if report.already_present > 0 {
+                println!("already present: {}", report.already_present);
+            }
+            if report.copied() == 0 {
+                println!("nothing to copy");
+            } else if dry_run {
+                println!("dry run: ledger not changed");
+            } else {
+                save_ledger(&ledger_path, &ledger)?;
+            }
         }
-        Command::Summary { day } => {
-            print_summary(&ledger, day.as_deref())?;
+        Command::Archive {
+            before,
+            to,
+            dry_run,
+        } => {
+            let now = Utc::now();
+            let before = parse_date_input(&before, "--before", ledger.day_for_timestamp(now))?;
+            let archive_path = to.unwrap_or_else(|| default_archive_path(&ledger_path, before));
+            if archive_path.exists() {
+                return Err(format!(
+                    "{} already exists; pass --to with a new file",
+                    archive_path.display()
+                )
+                .into());
+            }
+            let cutoff = ledger.day_bounds_utc(before).0;
+            let (archive, report) = ledger.split_off_before(cutoff, now)?;
+            println!(
+                "sessions before {before}: {} moved, {} split at the cutoff",
+                report.sessions_moved, report.sessions_split
+            );
+            if report.sessions_moved + report.sessions_split == 0 {
+                println!("nothing to archive");
+            } else if dry_run {
+                println!(
+                    "dry run: would write {} with {} tasks",
+                    archive_path.display(),
+                    archive.header.tasks.len()
+                );
+            } else {
+                save_ledger(&archive_path, &archive)?;
+                save_ledger(&ledger_path, &ledger)?;
+                // Register the archive first so the active ledger stays the
+                // most recent one.
+                for path in [&archive_path, &ledger_path] {
+                    if let Err(err) = remember_ledger(path) {
+                        eprintln!("warning: failed to store recent ledger: {err}");
+                    }
+                }
+                println!("archived to {}", archive_path.display());
+            }
+        }
+        Command::Import(ImportCommand::Toggl {
+            file,
+            timezone,
+            category_from,
+            dry_run,
+        }) => {
+            let timezone = SourceTimezone::parse(&timezone)?;
+            let input = fs::read_to_string(&file)
+                .map_err(|err| format!("failed to read {}: {err}", file.display()))?;
+            let (sessions, issues) = parse_toggl_csv(&input, &timezone, category_from);
+            import_sessions(&mut ledger, &ledger_path, sessions, issues, dry_run)?;
+        }
+        Command::Import(ImportCommand::Csv {
+            file,
+            mapping,
+            columns,
+            dry_run,
+        }) => {
+            let columns = match mapping {
+                Some(path) => {
+                    let contents = fs::read_to_string(&path)
+                        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+                    let file = toml::from_str::<CsvMapping>(&contents)
+                        .map_err(|err| format!("invalid mapping {}: {err}", path.display()))?;
+                    (*columns).or(file)
+                }
+                None => *columns,
+            };
+            let input = fs::read_to_string(&file)
+                .map_err(|err| format!("failed to read {}: {err}", file.display()))?;
+            let (sessions, issues) = parse_mapped_csv(&input, &columns)?;
+            import_sessions(&mut ledger, &ledger_path, sessions, issues, dry_run)?;
+        }
+        Command::Import(ImportCommand::Timewarrior { path, map, dry_run }) => {
+            let mapping = TagMapping::parse(&map)?;
+            let path = path.map_or_else(default_timewarrior_dir, Ok)?;
+            let (sessions, issues) = read_timewarrior(&path, &mapping)?;
+            import_sessions(&mut ledger, &ledger_path, sessions, issues, dry_run)?;
+        }
+        Command::Import(ImportCommand::Watson { path, map, dry_run }) => {
+            let mapping = TagMapping::parse(&map)?;
+            let path = path.map_or_else(default_watson_dir, Ok)?;
+            let (sessions, issues) = read_watson(&path, &mapping)?;
+            import_sessions(&mut ledger, &ledger_path, sessions, issues, dry_run)?;
         }
         Command::Events { limit } => {
-            print_event_log(&ledger, limit);
+            print_event_log(&ledger, limit, cli.format);
         }
-        Command::Ledgers { .. } => {}
+        Command::Ledgers { .. } | Command::Status { .. } | Command::Diff { .. } => {}
     }
 
     Ok(())
 }
 
-fn print_recent_ledgers(limit: usize) -> Result<(), Box<dyn Error>> {
-    let rows = recent_ledgers(limit)?;
-    if rows.is_empty() {
-        println!("no recent ledgers");
-        return Ok(());
+/// Resolves a `--task` argument by ID, ID prefix, title or fuzzy match.
+/// When several tasks match, those accepted by `prefer` win (e.g. running
+/// tasks for `stop`).
+fn resolve_task(
+    ledger: &Ledger,
+    query: &str,
+    prefer: impl Fn(&str) -> bool,
+) -> Result<String, String> {
+    let mut candidates = ledger.match_tasks(query);
+    if candidates.len() > 1 && candidates.iter().any(|id| prefer(id)) {
+        candidates.retain(|id| prefer(id));
+    }
+    choose_candidate("task", query, candidates, |id| {
+        format!("{id} | {}", task_label(ledger, id))
+    })
+}
+
+fn resolve_project(ledger: &Ledger, query: &str) -> Result<String, String> {
+    choose_candidate("project", query, ledger.match_projects(query), |id| {
+        let name = ledger.project(id).map(|project| project.name.as_str());
+        format!("{id} | {}", name.unwrap_or_default())
+    })
+}
+
+fn resolve_category(ledger: &Ledger, query: &str) -> Result<String, String> {
+    choose_candidate("category", query, ledger.match_categories(query), |id| {
+        let name = ledger.category(id).map(|category| category.name.as_str());
+        format!("{id} | {}", name.unwrap_or_default())
+    })
+}
+
+/// Picks one of several matches: interactively when both stdin and stderr
+/// are terminals, otherwise by failing with the candidate list.
+fn choose_candidate(
+    kind: &str,
+    query: &str,
+    mut candidates: Vec<String>,
+    describe: impl Fn(&str) -> String,
+) -> Result<String, String> {
+    match candidates.len() {
+        0 => return Err(format!("no {kind} matches '{query}'")),
+        1 => return Ok(candidates.remove(0)),
+        _ => {}
     }
 
-    for (index, path) in rows.iter().enumerate() {
-        println!("{:>2}. {}", index + 1, path.display());
+    let listing = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, id)| format!("{:>3}) {}", index + 1, describe(id)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        return Err(format!(
+            "'{query}' matches several {kind}s, be more specific:\n{listing}"
+        ));
     }
 
+    eprintln!("'{query}' matches several {kind}s:\n{listing}");
+    eprint!("choose 1-{} (empty to cancel): ", candidates.len());
+    io::stderr().flush().map_err(|err| err.to_string())?;
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .map_err(|err| err.to_string())?;
+    let answer = answer.trim();
+    if answer.is_empty() {
+        return Err("cancelled".to_string());
+    }
+    answer
+        .parse::<usize>()
+        .ok()
+        .and_then(|choice| choice.checked_sub(1))
+        .and_then(|index| candidates.get(index).cloned())
+        .ok_or_else(|| format!("invalid choice '{answer}'"))
+}
+
+fn task_label(ledger: &Ledger, task_id: &str) -> String {
+    let Some(task) = ledger.task(task_id) else {
+        return task_id.to_string();
+    };
+    let project = ledger
+        .project(&task.project_id)
+        .map(|project| project.name.as_str())
+        .unwrap_or("Unknown project");
+    format!("{project} · {}", task.short_description())
+}
+
+/// Resolves an optional `--at` style flag, defaulting to `now`.
+fn cli_timestamp(
+    input: Option<&str>,
+    flag: &str,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, String> {
+    match input {
+        Some(input) => {
+            let today = now.with_timezone(&Local).date_naive();
+            parse_time_input(input, flag, today, now)
+        }
+        None => Ok(now),
+    }
+}
+
+fn cli_time_suffix(input: Option<&str>, timestamp: DateTime<Utc>) -> String {
+    match input {
+        Some(_) => format!(
+            " at {}",
+            timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+        ),
+        None => String::new(),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum StatusOutput {
+    Format(OutputFormat),
+    Waybar,
+}
+
+const DEFAULT_STATUS_FORMAT: &str = "{project} · {task} {elapsed}";
+
+/// Loads several ledgers (plus every existing recent one with
+/// `all_recent`) and merges them, the first one's day settings winning.
+fn load_combined_ledger(mut paths: Vec<PathBuf>, all_recent: bool) -> Result<Ledger, String> {
+    if all_recent {
+        let recent =
+            recent_ledgers(100).map_err(|err| format!("failed to load recent ledgers: {err}"))?;
+        for path in recent {
+            if path.exists() && !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    let mut paths = paths.into_iter();
+    let first = paths
+        .next()
+        .ok_or("no ledgers to combine: pass --ledger or open one first")?;
+    let mut combined = load_ledger(&first).map_err(|err| err.to_string())?;
+    let now = Utc::now();
+    for path in paths {
+        let ledger = load_ledger(&path).map_err(|err| err.to_string())?;
+        combined.merge_from(&ledger, now);
+    }
+    Ok(combined)
+}
+
+/// `work.ledger` archived before 2026-01-01 becomes
+/// `work-before-2026-01-01.ledger` in the same directory.
+fn default_archive_path(ledger_path: &Path, before: NaiveDate) -> PathBuf {
+    let stem = ledger_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "ledger".to_string());
+    let extension = ledger_path
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+    ledger_path.with_file_name(format!("{stem}-before-{before}{extension}"))
+}
+
+/// Applies an import to a copy of the ledger and only saves it when every
+/// row was read and accepted.
+fn import_sessions(
+    ledger: &mut Ledger,
+    ledger_path: &Path,
+    sessions: Vec<ImportedSession>,
+    mut issues: Vec<ImportIssue>,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let mut updated = ledger.clone();
+    let report = apply_import(&mut updated, sessions, Utc::now());
+    issues.extend(report.errors.iter().cloned());
+
+    let verb = if dry_run || !issues.is_empty() {
+        "would create"
+    } else {
+        "created"
+    };
+    for (kind, names) in [
+        ("projects", &report.projects_created),
+        ("categories", &report.categories_created),
+        ("tasks", &report.tasks_created),
+    ] {
+        if !names.is_empty() {
+            println!("{verb} {kind}: {}", names.join(", "));
+        }
+    }
+    println!(
+        "sessions: {} new, {} running, {} duplicates skipped",
+        report.sessions_added, report.running_started, report.duplicates
+    );
+    for issue in &issues {
+        println!("{}: {}", issue.source, issue.message);
+    }
+
+    if !issues.is_empty() {
+        return Err(format!("{} row(s) failed; nothing was imported", issues.len()).into());
+    }
+    if dry_run {
+        println!("dry run: ledger not changed");
+        return Ok(());
+    }
+    *ledger = updated;
+    save_ledger(ledger_path, ledger)?;
     Ok(())
 }
 
-fn print_tasks(ledger: &Ledger) {
-    if ledger.header.tasks.is_empty() {
-        println!("no tasks yet");
-        return;
+fn print_status(ledger: &Ledger, template: Option<&str>, idle: Option<&str>, output: StatusOutput) {
+    let now = Utc::now();
+    let status = ledger.status(now);
+    let labels = status
+        .running
+        .iter()
+        .map(|session| task_label(ledger, &session.task_id))
+        .collect::<Vec<_>>();
+    let today = format_duration(status.today_total);
+
+    let text = match status.running.last() {
+        Some(latest) => {
+            let (project, task) = ledger
+                .task(&latest.task_id)
+                .map(|task| {
+                    let project = ledger
+                        .project(&task.project_id)
+                        .map(|project| project.name.clone())
+                        .unwrap_or_default();
+                    (project, task.short_description())
+                })
+                .unwrap_or_else(|| (String::new(), latest.task_id.clone()));
+            template
+                .unwrap_or(DEFAULT_STATUS_FORMAT)
+                .replace("{task}", &task)
+                .replace("{project}", &project)
+                .replace("{elapsed}", &format_duration(now - latest.started_at))
+                .replace(
+                    "{started}",
+                    &latest
+                        .started_at
+                        .with_timezone(&Local)
+                        .format("%H:%M")
+                        .to_string(),
+                )
+                .replace("{note}", latest.note.as_deref().unwrap_or_default())
+                .replace("{tasks}", &labels.join(", "))
+                .replace("{count}", &status.running.len().to_string())
+                .replace("{today}", &today)
+        }
+        None => idle.unwrap_or("idle").replace("{today}", &today),
+    };
+
+    match output {
+        StatusOutput::Format(OutputFormat::Table) if template.is_some() => println!("{text}"),
+        StatusOutput::Format(OutputFormat::Table) => {
+            if status.running.is_empty() {
+                println!("{text}");
+            }
+            for (session, label) in status.running.iter().zip(&labels) {
+                println!("{} | {label}", format_duration(now - session.started_at));
+            }
+            println!("today {today}");
+        }
+        StatusOutput::Format(OutputFormat::Json) => {
+            let running = status
+                .running
+                .iter()
+                .map(|session| {
+                    let mut entry = task_fields(ledger, &session.task_id);
+                    entry.insert(
+                        "started_at".to_string(),
+                        json!(session.started_at.to_rfc3339()),
+                    );
+                    entry.insert(
+                        "elapsed".to_string(),
+                        duration_json(now - session.started_at),
+                    );
+                    entry.insert("note".to_string(), json!(session.note));
+                    Value::Object(entry)
+                })
+                .collect::<Vec<_>>();
+            // Single line: status bars read one line per poll.
+            let value = json!({
+                "running": running,
+                "today": duration_json(status.today_total),
+            });
+            println!("{value}");
+        }
+        StatusOutput::Format(format) => {
+            let mut table = Table::new(vec![
+                "task_id",
+                "task",
+                "project",
+                "started_at",
+                "elapsed_seconds",
+                "elapsed",
+                "note",
+            ]);
+            for session in &status.running {
+                let (task, project) = task_and_project_names(ledger, &session.task_id);
+                let elapsed = now - session.started_at;
+                table.push(vec![
+                    session.task_id.clone(),
+                    task,
+                    project,
+                    session.started_at.to_rfc3339(),
+                    elapsed.num_seconds().to_string(),
+                    format_duration(elapsed),
+                    session.note.clone().unwrap_or_default(),
+                ]);
+            }
+            table.print(format);
+        }
+        StatusOutput::Waybar => {
+            let mut tooltip = status
+                .running
+                .iter()
+                .zip(&labels)
+                .map(|(session, label)| {
+                    format!("{} {label}", format_duration(now - session.started_at))
+                })
+                .collect::<Vec<_>>();
+            tooltip.push(format!("today {today}"));
+            let class = if status.running.is_empty() {
+                "idle"
+            } else {
+                "running"
+            };
+            let value = json!({
+                "text": text,
+                "tooltip": tooltip.join("\n"),
+                "class": class,
+                "alt": class,
+            });
+            println!("{value}");
+        }
     }
+}
 
-    for task in &ledger.header.tasks {
-        let project = ledger
-            .project(&task.project_id)
-            .map(|project| project.name.clone())
-            .unwrap_or_else(|| "Unknown project".to_string());
-        let category = task
-            .category_id
-            .as_ref()
-            .and_then(|id| ledger.category(id))
-            .map(|category| category.name.clone())
-            .unwrap_or_else(|| "Uncategorized".to_string());
-        println!(
-            "{} | {} | {} | {}",
-            task.id,
-            project,
-            category,
-            task.short_description()
-        );
+/// Task, project and category references shared by the JSON outputs.
+/// Missing entities keep their ID with a `null` name.
+fn task_fields(ledger: &Ledger, task_id: &str) -> Map<String, Value> {
+    let task = ledger.task(task_id);
+    let project_id = task.map(|task| task.project_id.as_str());
+    let category_id = task.and_then(|task| task.category_id.as_deref());
+    let mut fields = Map::new();
+    fields.insert("task_id".to_string(), json!(task_id));
+    fields.insert(
+        "task".to_string(),
+        json!(task.map(|task| task.short_description())),
+    );
+    fields.insert("project_id".to_string(), json!(project_id));
+    fields.insert(
+        "project".to_string(),
+        json!(
+            project_id
+                .and_then(|id| ledger.project(id))
+                .map(|project| &project.name)
+        ),
+    );
+    fields.insert("category_id".to_string(), json!(category_id));
+    fields.insert(
+        "category".to_string(),
+        json!(
+            category_id
+                .and_then(|id| ledger.category(id))
+                .map(|category| &category.name)
+        ),
+    );
+    fields
+}
+
+fn task_and_project_names(ledger: &Ledger, task_id: &str) -> (String, String) {
+    let task = ledger.task(task_id);
+    let project = task
+        .and_then(|task| ledger.project(&task.project_id))
+        .map(|project| project.name.clone())
+        .unwrap_or_else(|| "Unknown project".to_string());
+    let task = task
+        .map(|task| task.short_description())
+        .unwrap_or_else(|| "Unknown task".to_string());
+    (task, project)
+}
+
+fn print_diff(before: &Ledger, after: &Ledger, diff: &LedgerDiff, format: OutputFormat) {
+    // Removed tasks only have a name in the older ledger.
+    let event_ledger = |event: &EventDiff| {
+        if after.task(&event.task_id).is_some() {
+            after
+        } else {
+            before
+        }
+    };
+    let sections = [
+        ("project", &diff.projects),
+        ("category", &diff.categories),
+        ("task", &diff.tasks),
+    ];
+
+    match format {
+        OutputFormat::Table => {
+            if diff.is_empty() {
+                println!("no differences");
+            }
+            for field in &diff.settings {
+                println!(
+                    "setting {}: {} -> {}",
+                    field.field,
+                    diff_value(&field.before),
+                    diff_value(&field.after)
+                );
+            }
+            for (kind, changes) in sections {
+                for change in changes {
+                    println!(
+                        "{kind} {}: {} ({})",
+                        change.change.as_str(),
+                        change.label,
+                        change.id
+                    );
+                    for field in &change.fields {
+                        println!(
+                            "    {}: {} -> {}",
+                            field.field,
+                            diff_value(&field.before),
+                            diff_value(&field.after)
+                        );
+                    }
+                }
+            }
+            for event in &diff.events {
+                let task = task_label(event_ledger(event), &event.task_id);
+                let detail = match (&event.before, &event.after) {
+                    (Some(old), Some(new)) if old.timestamp == new.timestamp => format!(
+                        "{} note {} -> {}",
+                        new.timestamp.to_rfc3339(),
+                        diff_value(&old.note),
+                        diff_value(&new.note)
+                    ),
+                    (Some(old), Some(new)) => format!(
+                        "{} -> {}",
+                        old.timestamp.to_rfc3339(),
+                        new.timestamp.to_rfc3339()
+                    ),
+                    (Some(side), None) | (None, Some(side)) => side.timestamp.to_rfc3339(),
+                    (None, None) => String::new(),
+                };
+                println!(
+                    "event {}: {} {task} {detail}",
+                    event.change.as_str(),
+                    event.kind
+                );
+            }
+        }
+        OutputFormat::Json => {
+            let fields = |fields: &[FieldChange]| {
+                fields
+                    .iter()
+                    .map(|field| {
+                        json!({ "field": field.field, "before": field.before, "after": field.after })
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let mut output = Map::new();
+            output.insert("settings".to_string(), json!(fields(&diff.settings)));
+            for (kind, changes) in sections {
+                let changes = changes
+                    .iter()
+                    .map(|change| {
+                        json!({
+                            "change": change.change.as_str(),
+                            "id": change.id,
+                            "name": change.label,
+                            "fields": fields(&change.fields),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                output.insert(
+                    if kind == "category" {
+                        "categories".to_string()
+                    } else {
+                        format!("{kind}s")
+                    },
+                    json!(changes),
+                );
+            }
+            let side = |side: &Option<EventSide>| {
+                side.as_ref().map(
+                    |side| json!({ "timestamp": side.timestamp.to_rfc3339(), "note": side.note }),
+                )
+            };
+            let events = diff
+                .events
+                .iter()
+                .map(|event| {
+                    let mut entry = task_fields(event_ledger(event), &event.task_id);
+                    entry.insert("change".to_string(), json!(event.change.as_str()));
+                    entry.insert("type".to_string(), json!(event.kind));
+                    entry.insert("before".to_string(), json!(side(&event.before)));
+                    entry.insert("after".to_string(), json!(side(&event.after)));
+                    Value::Object(entry)
+                })
+                .collect::<Vec<_>>();
+            output.insert("events".to_string(), json!(events));
+            print_json(&Value::Object(output));
+        }
+        OutputFormat::Csv | OutputFormat::Markdown => {
+            let mut table = Table::new(vec![
+                "section", "change", "id", "name", "field", "before", "after",
+            ]);
+            let text = |value: &Option<String>| value.clone().unwrap_or_default();
+            for field in &diff.settings {
+                table.push(vec![
+                    "setting".to_string(),
+                    "modified".to_string(),
+                    String::new(),
+                    String::new(),
+                    field.field.to_string(),
+                    text(&field.before),
+                    text(&field.after),
+                ]);
+            }
+            for (kind, changes) in sections {
+                for change in changes {
+                    let row = |field: &str, before: String, after: String| {
+                        vec![
+                            kind.to_string(),
+                            change.change.as_str().to_string(),
+                            change.id.clone(),
+                            change.label.clone(),
+                            field.to_string(),
+                            before,
+                            after,
+                        ]
+                    };
+                    if change.fields.is_empty() {
+                        table.push(row("", String::new(), String::new()));
+                    }
+                    for field in &change.fields {
+                        table.push(row(field.field, text(&field.before), text(&field.after)));
+                    }
+                }
+            }
+            for event in &diff.events {
+                let timestamp = |side: &Option<EventSide>| {
+                    side.as_ref()
+                        .map(|side| side.timestamp.to_rfc3339())
+                        .unwrap_or_default()
+                };
+                let note = |side: &Option<EventSide>| {
+                    side.as_ref()
+                        .and_then(|side| side.note.clone())
+                        .unwrap_or_default()
+                };
+                let (field, before_value, after_value) = match event.change {
+                    EventChange::NoteChanged => (
+                        format!("{} note", event.kind),
+                        note(&event.before),
+                        note(&event.after),
+                    ),
+                    _ => (
+                        event.kind.to_string(),
+                        timestamp(&event.before),
+                        timestamp(&event.after),
+                    ),
+                };
+                table.push(vec![
+                    "event".to_string(),
+                    event.change.as_str().to_string(),
+                    event.task_id.clone(),
+                    task_label(event_ledger(event), &event.task_id),
+                    field,
+                    before_value,
+                    after_value,
+                ]);
+            }
+            table.print(format);
+        }
     }
 }
 
-fn print_summary(ledger: &Ledger, day: Option<&str>) -> Result<(), Box<dyn Error>> {
+/// Quoted with escapes so multi-line descriptions stay on one line.
+fn diff_value(value: &Option<String>) -> String {
+    match value {
+        Some(value) => format!("{value:?}"),
+        None => "(none)".to_string(),
+    }
+}
+
+fn print_recent_ledgers(limit: usize, format: OutputFormat) -> Result<(), Box<dyn Error>> {
+    let rows = recent_ledgers(limit)?;
+    match format {
+        OutputFormat::Table => {
+            if rows.is_empty() {
+                println!("no recent ledgers");
+            }
+            for (index, path) in rows.iter().enumerate() {
+                println!("{:>2}. {}", index + 1, path.display());
+            }
+        }
+        OutputFormat::Json => {
+            let ledgers = rows
+                .iter()
+                .enumerate()
+                .map(|(index, path)| json!({"rank": index + 1, "path": path.display().to_string()}))
+                .collect::<Vec<_>>();
+            print_json(&json!({ "ledgers": ledgers }));
+        }
+        OutputFormat::Csv | OutputFormat::Markdown => {
+            let mut table = Table::new(vec!["rank", "path"]);
+            for (index, path) in rows.iter().enumerate() {
+                table.push(vec![(index + 1).to_string(), path.display().to_string()]);
+            }
+            table.print(format);
+        }
+    }
+
+    Ok(())
+}
+
+fn print_tasks(ledger: &Ledger, format: OutputFormat) {
+    match format {
+        OutputFormat::Table => {
+            if ledger.header.tasks.is_empty() {
+                println!("no tasks yet");
+                return;
+            }
+
+            for task in &ledger.header.tasks {
+                let project = ledger
+                    .project(&task.project_id)
+                    .map(|project| project.name.clone())
+                    .unwrap_or_else(|| "Unknown project".to_string());
+                let category = task
+                    .category_id
+                    .as_ref()
+                    .and_then(|id| ledger.category(id))
+                    .map(|category| category.name.clone())
+                    .unwrap_or_else(|| "Uncategorized".to_string());
+                println!(
+                    "{} | {} | {} | {}",
+                    task.id,
+                    project,
+                    category,
+                    task.short_description()
+                );
+            }
+        }
+        OutputFormat::Json => {
+            let tasks = ledger
+                .header
+                .tasks
+                .iter()
+                .map(|task| {
+                    let mut entry = task_fields(ledger, &task.id);
+                    entry.insert("description".to_string(), json!(task.description));
+                    entry.insert("archived".to_string(), json!(task.archived));
+                    Value::Object(entry)
+                })
+                .collect::<Vec<_>>();
+            print_json(&json!({ "tasks": tasks }));
+        }
+        OutputFormat::Csv | OutputFormat::Markdown => {
+            let mut table = Table::new(vec![
+                "task_id",
+                "task",
+                "project_id",
+                "project",
+                "category_id",
+                "category",
+                "archived",
+            ]);
+            for task in &ledger.header.tasks {
+                let category = task.category_id.as_ref().and_then(|id| ledger.category(id));
+                table.push(vec![
+                    task.id.clone(),
+                    task.short_description(),
+                    task.project_id.clone(),
+                    ledger
+                        .project(&task.project_id)
+                        .map(|project| project.name.clone())
+                        .unwrap_or_default(),
+                    task.category_id.clone().unwrap_or_default(),
+                    category
+                        .map(|category| category.name.clone())
+                        .unwrap_or_default(),
+                    task.archived.to_string(),
+                ]);
+            }
+            table.print(format);
+        }
+    }
+}
+
+fn print_summary(
+    ledger: &Ledger,
+    day: Option<&str>,
+    format: OutputFormat,
+) -> Result<(), Box<dyn Error>> {
     let now = Utc::now();
     let day = if let Some(raw) = day {
         NaiveDate::parse_from_str(raw, "%Y-%m-%d")?
@@ -199,28 +1490,9 @@ fn print_summary(ledger: &Ledger, day: Option<&str>) -> Result<(), Box<dyn Error
     let snapshot = ledger.snapshot(now);
     let task_totals = snapshot.totals_for_day(day);
 
-    println!("summary for {}", day.format("%Y-%m-%d"));
-    if task_totals.is_empty() {
-        println!("no tracked sessions for this day");
-        return Ok(());
-    }
-
-    println!("\nby task:");
-    for (task_id, duration) in &task_totals {
-        let task_name = ledger
-            .task(task_id)
-            .map(|task| task.short_description())
-            .unwrap_or_else(|| "Unknown task".to_string());
-        println!(
-            "{} | {} | {}",
-            format_duration(*duration),
-            task_id,
-            task_name
-        );
-    }
-
-    let mut by_project: HashMap<String, Duration> = HashMap::new();
-    let mut by_category: HashMap<String, Duration> = HashMap::new();
+    // Keyed by (name, id) so equal totals sort by name.
+    let mut by_project: HashMap<(String, Option<String>), Duration> = HashMap::new();
+    let mut by_category: HashMap<(String, Option<String>), Duration> = HashMap::new();
 
     for (task_id, duration) in &task_totals {
         if let Some(task) = ledger.task(task_id) {
@@ -228,32 +1500,394 @@ fn print_summary(ledger: &Ledger, day: Option<&str>) -> Result<(), Box<dyn Error
                 .project(&task.project_id)
                 .map(|project| project.name.clone())
                 .unwrap_or_else(|| "Unknown project".to_string());
-            *by_project.entry(project).or_insert_with(Duration::zero) += *duration;
+            *by_project
+                .entry((project, Some(task.project_id.clone())))
+                .or_insert_with(Duration::zero) += *duration;
 
             let category = task
                 .category_id
                 .as_ref()
                 .and_then(|id| ledger.category(id))
-                .map(|category| category.name.clone())
-                .unwrap_or_else(|| "Uncategorized".to_string());
+                .map(|category| (category.name.clone(), Some(category.id.clone())))
+                .unwrap_or_else(|| ("Uncategorized".to_string(), None));
             *by_category.entry(category).or_insert_with(Duration::zero) += *duration;
         }
     }
+    let by_project = sort_duration_map(by_project);
+    let by_category = sort_duration_map(by_category);
+
+    match format {
+        OutputFormat::Table => {
+            println!("summary for {}", day.format("%Y-%m-%d"));
+            if task_totals.is_empty() {
+                println!("no tracked sessions for this day");
+                return Ok(());
+            }
 
-    println!("\nby project:");
-    for (name, duration) in sort_duration_map(by_project) {
-        println!("{} | {}", format_duration(duration), name);
+            println!("\nby task:");
+            for (task_id, duration) in &task_totals {
+                let task_name = ledger
+                    .task(task_id)
+                    .map(|task| task.short_description())
+                    .unwrap_or_else(|| "Unknown task".to_string());
+                println!(
+                    "{} | {} | {}",
+                    format_duration(*duration),
+                    task_id,
+                    task_name
+                );
+            }
+
+            println!("\nby project:");
+            for ((name, _), duration) in &by_project {
+                println!("{} | {}", format_duration(*duration), name);
+            }
+
+            println!("\nby category:");
+            for ((name, _), duration) in &by_category {
+                println!("{} | {}", format_duration(*duration), name);
+            }
+        }
+        OutputFormat::Json => {
+            let total = task_totals
+                .iter()
+                .fold(Duration::zero(), |sum, (_, duration)| sum + *duration);
+            let tasks = task_totals
+                .iter()
+                .map(|(task_id, duration)| {
+                    let mut entry = task_fields(ledger, task_id);
+                    entry.insert("duration".to_string(), duration_json(*duration));
+                    Value::Object(entry)
+                })
+                .collect::<Vec<_>>();
+            let projects = by_project
+                .iter()
+                .map(|((name, id), duration)| {
+                    json!({"project_id": id, "project": name, "duration": duration_json(*duration)})
+                })
+                .collect::<Vec<_>>();
+            let categories = by_category
+                .iter()
+                .map(|((name, id), duration)| {
+                    json!({"category_id": id, "category": name, "duration": duration_json(*duration)})
+                })
+                .collect::<Vec<_>>();
+            print_json(&json!({
+                "day": day.format("%Y-%m-%d").to_string(),
+                "total": duration_json(total),
+                "tasks": tasks,
+                "projects": projects,
+                "categories": categories,
+            }));
+        }
+        OutputFormat::Csv | OutputFormat::Markdown => {
+            let mut table = Table::new(vec!["group", "id", "name", "seconds", "duration"]);
+            let mut push = |group: &str, id: Option<&String>, name: String, duration: Duration| {
+                table.push(vec![
+                    group.to_string(),
+                    id.cloned().unwrap_or_default(),
+                    name,
+                    duration.num_seconds().to_string(),
+                    format_duration(duration),
+                ]);
+            };
+            for (task_id, duration) in &task_totals {
+                let (task, _) = task_and_project_names(ledger, task_id);
+                push("task", Some(task_id), task, *duration);
+            }
+            for ((name, id), duration) in &by_project {
+                push("project", id.as_ref(), name.clone(), *duration);
+            }
+            for ((name, id), duration) in &by_category {
+                push("category", id.as_ref(), name.clone(), *duration);
+            }
+            table.print(format);
+        }
     }
 
-    println!("\nby category:");
-    for (name, duration) in sort_duration_map(by_category) {
-        println!("{} | {}", format_duration(duration), name);
+    Ok(())
+}
+
+/// Runs `report` with its defaults: a single day (today, or `--to` alone).
+fn print_range_report(
+    ledger: &Ledger,
+    range: &RangeArgs,
+    by: &[ReportGroup],
+    filter: &FilterArgs,
+    format: OutputFormat,
+) -> Result<(), Box<dyn Error>> {
+    let now = Utc::now();
+    let today = ledger.day_for_timestamp(now);
+    let (from, to) = range.resolve(today)?;
+    let to = to.unwrap_or(today);
+    let from = from.unwrap_or(to);
+    if from > to {
+        return Err("--from must not be after --to".into());
     }
 
+    let filter = filter.resolve(ledger)?;
+    let snapshot = ledger.snapshot(now);
+    let report = build_report(ledger, &snapshot, from, to, by, &filter);
+    print_report(&report, format);
     Ok(())
 }
 
-fn sort_duration_map(map: HashMap<String, Duration>) -> Vec<(String, Duration)> {
+fn print_report(report: &Report, format: OutputFormat) {
+    let groups = report
+        .groups
+        .iter()
+        .map(|group| group.name())
+        .collect::<Vec<_>>();
+    match format {
+        OutputFormat::Table => {}
+        OutputFormat::Json => {
+            let rows = report
+                .rows
+                .iter()
+                .map(|row| {
+                    let keys = report
+                        .groups
+                        .iter()
+                        .zip(&row.keys)
+                        .map(|(group, key)| {
+                            json!({"group": group.name(), "id": key.id, "label": key.label})
+                        })
+                        .collect::<Vec<_>>();
+                    json!({"keys": keys, "duration": duration_json(row.total)})
+                })
+                .collect::<Vec<_>>();
+            print_json(&json!({
+                "from": report.from.format("%Y-%m-%d").to_string(),
+                "to": report.to.format("%Y-%m-%d").to_string(),
+                "group_by": groups,
+                "rows": rows,
+                "total": duration_json(report.total),
+                "days": report.days(),
+                "active_days": report.active_days,
+                "average_per_day": duration_json(report.average_per_day()),
+                "average_per_active_day": duration_json(report.average_per_active_day()),
+            }));
+            return;
+        }
+        OutputFormat::Csv | OutputFormat::Markdown => {
+            // One label column per group, plus an ID column for entities.
+            let mut columns = Vec::new();
+            for group in &report.groups {
+                columns.push(group.name());
+                if let Some(id_column) = group.id_column() {
+                    columns.push(id_column);
+                }
+            }
+            columns.extend(["seconds", "duration"]);
+            let mut table = Table::new(columns);
+            for row in &report.rows {
+                let mut cells = Vec::new();
+                for (group, key) in report.groups.iter().zip(&row.keys) {
+                    cells.push(key.label.clone());
+                    if group.id_column().is_some() {
+                        cells.push(key.id.clone().unwrap_or_default());
+                    }
+                }
+                cells.push(row.total.num_seconds().to_string());
+                cells.push(format_duration(row.total));
+                table.push(cells);
+            }
+            table.print(format);
+            return;
+        }
+    }
+
+    println!(
+        "report {} .. {} by {}",
+        report.from.format("%Y-%m-%d"),
+        report.to.format("%Y-%m-%d"),
+        groups.join(", ")
+    );
+    if report.rows.is_empty() {
+        println!("no tracked sessions in this range");
+        return;
+    }
+
+    println!();
+    for row in &report.rows {
+        let keys = row
+            .keys
+            .iter()
+            .map(|key| key.label.as_str())
+            .collect::<Vec<_>>();
+        println!("{} | {}", format_duration(row.total), keys.join(" | "));
+    }
+
+    println!(
+        "\ntotal {} | {} days, {} active | avg/day {} | avg/active day {}",
+        format_duration(report.total),
+        report.days(),
+        report.active_days,
+        format_duration(report.average_per_day()),
+        format_duration(report.average_per_active_day())
+    );
+}
+
+fn print_event_log(ledger: &Ledger, limit: usize, format: OutputFormat) {
+    let events = ledger
+        .events
+        .iter()
+        .enumerate()
+        .rev()
+        .take(limit)
+        .map(|(index, event)| {
+            let (kind, task_id, note) = match &event.kind {
+                EventKind::Start { task_id, note } => ("start", task_id, note),
+                EventKind::Stop { task_id, note } => ("stop", task_id, note),
+            };
+            (index, event.timestamp, kind, task_id, note)
+        })
+        .collect::<Vec<_>>();
+
+    match format {
+        OutputFormat::Table => {
+            for (_, timestamp, kind, task_id, note) in events {
+                let (task, _) = task_and_project_names(ledger, task_id);
+                println!(
+                    "{} {kind} {task}{}",
+                    timestamp.to_rfc3339(),
+                    note.as_ref()
+                        .map(|value| format!(" note={value}"))
+                        .unwrap_or_default()
+                );
+            }
+        }
+        OutputFormat::Json => {
+            let events = events
+                .into_iter()
+                .map(|(index, timestamp, kind, task_id, note)| {
+                    let mut entry = task_fields(ledger, task_id);
+                    entry.insert("index".to_string(), json!(index));
+                    entry.insert("timestamp".to_string(), json!(timestamp.to_rfc3339()));
+                    entry.insert("type".to_string(), json!(kind));
+                    entry.insert("note".to_string(), json!(note));
+                    Value::Object(entry)
+                })
+                .collect::<Vec<_>>();
+            print_json(&json!({ "events": events }));
+        }
+        OutputFormat::Csv | OutputFormat::Markdown => {
+            let mut table = Table::new(vec![
+                "index",
+                "timestamp",
+                "type",
+                "task_id",
+                "task",
+                "project",
+                "note",
+            ]);
+            for (index, timestamp, kind, task_id, note) in events {
+                let (task, project) = task_and_project_names(ledger, task_id);
+                table.push(vec![
+                    index.to_string(),
+                    timestamp.to_rfc3339(),
+                    kind.to_string(),
+                    task_id.clone(),
+                    task,
+                    project,
+                    note.clone().unwrap_or_default(),
+                ]);
+            }
+            table.print(format);
+        }
+    }
+}
+
+fn print_sessions(ledger: &Ledger, sessions: &[ExportedSession], format: OutputFormat) {
+    match format {
+        OutputFormat::Table => {
+            if sessions.is_empty() {
+                println!("no tracked sessions");
+            }
+            for session in sessions {
+                println!(
+                    "{} -> {} | {} | {}{}{}",
+                    session.start.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+                    session.end.with_timezone(&Local).format("%H:%M"),
+                    format_duration(session.end - session.start),
+                    task_label(ledger, &session.task_id),
+                    session
+                        .note
+                        .as_ref()
+                        .map(|note| format!(" | {note}"))
+                        .unwrap_or_default(),
+                    if session.running { " (running)" } else { "" }
+                );
+            }
+        }
+        OutputFormat::Json => {
+            let sessions = sessions
+                .iter()
+                .map(|session| {
+                    let mut entry = task_fields(ledger, &session.task_id);
+                    entry.insert(
+                        "day".to_string(),
+                        json!(session.day.format("%Y-%m-%d").to_string()),
+                    );
+                    entry.insert("start".to_string(), json!(session.start.to_rfc3339()));
+                    entry.insert("end".to_string(), json!(session.end.to_rfc3339()));
+                    entry.insert(
+                        "duration".to_string(),
+                        duration_json(session.end - session.start),
+                    );
+                    entry.insert("note".to_string(), json!(session.note));
+                    entry.insert("running".to_string(), json!(session.running));
+                    Value::Object(entry)
+                })
+                .collect::<Vec<_>>();
+            print_json(&json!({ "sessions": sessions }));
+        }
+        OutputFormat::Csv | OutputFormat::Markdown => {
+            let mut table = Table::new(vec![
+                "task_id",
+                "task",
+                "project_id",
+                "project",
+                "category_id",
+                "category",
+                "day",
+                "start",
+                "end",
+                "seconds",
+                "duration",
+                "note",
+                "running",
+            ]);
+            for session in sessions {
+                let task = ledger.task(&session.task_id);
+                let (task_name, project) = task_and_project_names(ledger, &session.task_id);
+                let category_id = task.and_then(|task| task.category_id.clone());
+                let duration = session.end - session.start;
+                table.push(vec![
+                    session.task_id.clone(),
+                    task_name,
+                    task.map(|task| task.project_id.clone()).unwrap_or_default(),
+                    project,
+                    category_id.clone().unwrap_or_default(),
+                    category_id
+                        .and_then(|id| ledger.category(&id))
+                        .map(|category| category.name.clone())
+                        .unwrap_or_default(),
+                    session.day.format("%Y-%m-%d").to_string(),
+                    session.start.to_rfc3339(),
+                    session.end.to_rfc3339(),
+                    duration.num_seconds().to_string(),
+                    format_duration(duration),
+                    session.note.clone().unwrap_or_default(),
+                    session.running.to_string(),
+                ]);
+            }
+            table.print(format);
+        }
+    }
+}
+
+fn sort_duration_map<K: Ord>(map: HashMap<K, Duration>) -> Vec<(K, Duration)> {
     let mut rows = map.into_iter().collect::<Vec<_>>();
     rows.sort_by(|left, right| right.1.cmp(&left.1).then_with(|| left.0.cmp(&right.0)));
     rows
@@ -0,0 +1,134 @@
+use chrono::Duration;
+use clap::ValueEnum;
+use serde_json::{Value, json};
+
+use crate::domain::format_duration;
+
+/// Output format for CLI listings. `table` is the human-readable text the
+/// commands have always printed; the JSON shapes are documented in
+/// `contracts/spec/cli-output-v1.md`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+    Markdown,
+}
+
+/// Flat rows for the CSV and Markdown renderings of a command.
+#[derive(Debug, Clone)]
+pub struct Table {
+    columns: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(columns: Vec<&'static str>) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        debug_assert_eq!(row.len(), self.columns.len());
+        self.rows.push(row);
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = format!("{}\n", self.columns.join(","));
+        for row in &self.rows {
+            let cells = row.iter().map(|cell| csv_cell(cell)).collect::<Vec<_>>();
+            out.push_str(&cells.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("| {} |\n", self.columns.join(" | "));
+        out.push_str(&format!(
+            "|{}\n",
+            self.columns.iter().map(|_| " --- |").collect::<String>()
+        ));
+        for row in &self.rows {
+            let cells = row
+                .iter()
+                .map(|cell| markdown_cell(cell))
+                .collect::<Vec<_>>();
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        out
+    }
+
+    /// Prints the table as CSV or Markdown; other formats are handled by
+    /// the caller.
+    pub fn print(&self, format: OutputFormat) {
+        match format {
+            OutputFormat::Csv => print!("{}", self.to_csv()),
+            OutputFormat::Markdown => print!("{}", self.to_markdown()),
+            OutputFormat::Table | OutputFormat::Json => {}
+        }
+    }
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break.
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Keeps a value inside one Markdown table cell.
+fn markdown_cell(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+/// Durations appear in JSON as `{"seconds": 5400, "formatted": "01:30:00"}`.
+pub fn duration_json(duration: Duration) -> Value {
+    json!({
+        "seconds": duration.num_seconds().max(0),
+        "formatted": format_duration(duration),
+    })
+}
+
+pub fn print_json(value: &Value) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::{Table, duration_json};
+
+    #[test]
+    fn renders_csv_and_markdown_with_escaping() {
+        let mut table = Table::new(vec!["id", "name"]);
+        table.push(vec!["T1".to_string(), "Docs, \"v2\"".to_string()]);
+        table.push(vec!["T2".to_string(), "a|b\nc".to_string()]);
+
+        assert_eq!(
+            table.to_csv(),
+            "id,name\nT1,\"Docs, \"\"v2\"\"\"\nT2,\"a|b\nc\"\n"
+        );
+        assert_eq!(
+            table.to_markdown(),
+            "| id | name |\n| --- | --- |\n| T1 | Docs, \"v2\" |\n| T2 | a\\|b<br>c |\n"
+        );
+        assert_eq!(
+            duration_json(Duration::minutes(90)),
+            serde_json::json!({"seconds": 5400, "formatted": "01:30:00"})
+        );
+    }
+}
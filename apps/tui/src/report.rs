@@ -0,0 +1,329 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use clap::ValueEnum;
+
+use crate::domain::{Ledger, LedgerSnapshot, Task};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportGroup {
+    Day,
+    Week,
+    Month,
+    Project,
+    Category,
+    Task,
+}
+
+impl ReportGroup {
+    pub fn name(self) -> &'static str {
+        match self {
+            ReportGroup::Day => "day",
+            ReportGroup::Week => "week",
+            ReportGroup::Month => "month",
+            ReportGroup::Project => "project",
+            ReportGroup::Category => "category",
+            ReportGroup::Task => "task",
+        }
+    }
+
+    /// Column holding the entity ID in CSV/Markdown reports.
+    pub fn id_column(self) -> Option<&'static str> {
+        match self {
+            ReportGroup::Project => Some("project_id"),
+            ReportGroup::Category => Some("category_id"),
+            ReportGroup::Task => Some("task_id"),
+            ReportGroup::Day | ReportGroup::Week | ReportGroup::Month => None,
+        }
+    }
+
+    fn is_period(self) -> bool {
+        matches!(
+            self,
+            ReportGroup::Day | ReportGroup::Week | ReportGroup::Month
+        )
+    }
+}
+
+/// Restricts a report or export to the listed IDs; an empty list means
+/// "all".
+#[derive(Debug, Clone, Default)]
+pub struct ReportFilter {
+    pub projects: Vec<String>,
+    pub categories: Vec<String>,
+    pub tasks: Vec<String>,
+}
+
+impl ReportFilter {
+    pub fn matches(&self, task: &Task) -> bool {
+        matches_filter(&self.projects, Some(&task.project_id))
+            && matches_filter(&self.categories, task.category_id.as_ref())
+            && matches_filter(&self.tasks, Some(&task.id))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ReportKey {
+    pub label: String,
+    /// Entity ID for project/category/task groups, `None` for periods and
+    /// for "Uncategorized".
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportRow {
+    pub keys: Vec<ReportKey>,
+    pub total: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub groups: Vec<ReportGroup>,
+    pub rows: Vec<ReportRow>,
+    pub total: Duration,
+    pub active_days: usize,
+}
+
+impl Report {
+    /// Number of ledger days in the inclusive range.
+    pub fn days(&self) -> i64 {
+        (self.to - self.from).num_days() + 1
+    }
+
+    pub fn average_per_day(&self) -> Duration {
+        self.total / self.days().max(1) as i32
+    }
+
+    pub fn average_per_active_day(&self) -> Duration {
+        self.total / self.active_days.max(1) as i32
+    }
+}
+
+/// Aggregates `snapshot.daily_task_totals` over the ledger days `from..=to`,
+/// so day boundaries follow the ledger's `day_start_offset_hours`.
+pub fn build_report(
+    ledger: &Ledger,
+    snapshot: &LedgerSnapshot,
+    from: NaiveDate,
+    to: NaiveDate,
+    groups: &[ReportGroup],
+    filter: &ReportFilter,
+) -> Report {
+    let mut totals: BTreeMap<Vec<ReportKey>, Duration> = BTreeMap::new();
+    let mut total = Duration::zero();
+    let mut active_days = 0;
+
+    for (day, task_totals) in snapshot.daily_task_totals.range(from..=to) {
+        let mut day_total = Duration::zero();
+        for (task_id, duration) in task_totals {
+            let Some(task) = ledger.task(task_id) else {
+                continue;
+            };
+            if !filter.matches(task) {
+                continue;
+            }
+
+            let keys = groups
+                .iter()
+                .map(|group| report_key(ledger, *group, *day, task_id))
+                .collect::<Vec<_>>();
+            *totals.entry(keys).or_insert_with(Duration::zero) += *duration;
+            day_total += *duration;
+        }
+        if day_total > Duration::zero() {
+            active_days += 1;
+            total += day_total;
+        }
+    }
+
+    let mut rows = totals
+        .into_iter()
+        .map(|(keys, total)| ReportRow { keys, total })
+        .collect::<Vec<_>>();
+    // Periods read best in calendar order; pure entity breakdowns read best
+    // with the biggest share first.
+    if !groups.iter().any(|group| group.is_period()) {
+        rows.sort_by(|left, right| {
+            right
+                .total
+                .cmp(&left.total)
+                .then_with(|| left.keys.cmp(&right.keys))
+        });
+    }
+
+    Report {
+        from,
+        to,
+        groups: groups.to_vec(),
+        rows,
+        total,
+        active_days,
+    }
+}
+
+fn matches_filter(allowed: &[String], id: Option<&String>) -> bool {
+    allowed.is_empty() || id.is_some_and(|id| allowed.contains(id))
+}
+
+fn report_key(ledger: &Ledger, group: ReportGroup, day: NaiveDate, task_id: &str) -> ReportKey {
+    let period = |label: String| ReportKey { id: None, label };
+    let task = ledger.task(task_id);
+    match group {
+        ReportGroup::Day => period(day.format("%Y-%m-%d").to_string()),
+        ReportGroup::Week => {
+            let week = day.iso_week();
+            period(format!("{}-W{:02}", week.year(), week.week()))
+        }
+        ReportGroup::Month => period(day.format("%Y-%m").to_string()),
+        ReportGroup::Project => {
+            let project_id = task.map(|task| task.project_id.clone());
+            let label = project_id
+                .as_deref()
+                .and_then(|id| ledger.project(id))
+                .map(|project| project.name.clone())
+                .unwrap_or_else(|| "Unknown project".to_string());
+            ReportKey {
+                id: project_id,
+                label,
+            }
+        }
+        ReportGroup::Category => {
+            match task
+                .and_then(|task| task.category_id.as_deref())
+                .and_then(|id| ledger.category(id))
+            {
+                Some(category) => ReportKey {
+                    id: Some(category.id.clone()),
+                    label: category.name.clone(),
+                },
+                None => period("Uncategorized".to_string()),
+            }
+        }
+        ReportGroup::Task => ReportKey {
+            id: Some(task_id.to_string()),
+            label: task
+                .map(|task| task.short_description())
+                .unwrap_or_else(|| "Unknown task".to_string()),
+        },
+    }
+}
+
+/// First day (Monday) of the week containing `day`.
+pub fn start_of_week(day: NaiveDate) -> NaiveDate {
+    day - Duration::days(i64::from(day.weekday().num_days_from_monday()))
+}
+
+/// Last day of the month containing `day`.
+pub fn end_of_month(day: NaiveDate) -> NaiveDate {
+    let (year, month) = if day.month() == 12 {
+        (day.year() + 1, 1)
+    } else {
+        (day.year(), day.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|first| first.pred_opt())
+        .expect("month end must be valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate, TimeZone, Utc};
+
+    use super::{ReportFilter, ReportGroup, build_report, end_of_month, start_of_week};
+    use crate::domain::Ledger;
+
+    #[test]
+    fn groups_daily_totals_by_period_and_entity() {
+        let mut ledger = Ledger::new();
+        let work = ledger.add_project("Work".to_string(), None);
+        let home = ledger.add_project("Home".to_string(), None);
+        let docs = ledger
+            .add_task(work.clone(), None, "Docs".to_string())
+            .expect("task should be created");
+        let garden = ledger
+            .add_task(home, None, "Garden".to_string())
+            .expect("task should be created");
+        let noon = |day: u32| Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap();
+        let now = noon(20);
+        for (task_id, day, hours) in [(&docs, 2, 2), (&garden, 2, 1), (&docs, 10, 3)] {
+            ledger
+                .log_session(
+                    task_id,
+                    noon(day),
+                    noon(day) + Duration::hours(hours),
+                    None,
+                    now,
+                )
+                .expect("log should work");
+        }
+        let snapshot = ledger.snapshot(now);
+        let from = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+
+        let report = build_report(
+            &ledger,
+            &snapshot,
+            from,
+            to,
+            &[ReportGroup::Week, ReportGroup::Project],
+            &ReportFilter::default(),
+        );
+        let rows = report
+            .rows
+            .iter()
+            .map(|row| {
+                (
+                    row.keys[0].label.as_str(),
+                    row.keys[1].label.as_str(),
+                    row.total.num_hours(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            [
+                ("2026-W10", "Home", 1),
+                ("2026-W10", "Work", 2),
+                ("2026-W11", "Work", 3)
+            ]
+        );
+        assert_eq!(report.total, Duration::hours(6));
+        assert_eq!(report.active_days, 2);
+        assert_eq!(report.average_per_day(), Duration::hours(6) / 14);
+
+        let filtered = build_report(
+            &ledger,
+            &snapshot,
+            from,
+            to,
+            &[ReportGroup::Task],
+            &ReportFilter {
+                projects: vec![work],
+                ..ReportFilter::default()
+            },
+        );
+        assert_eq!(filtered.rows.len(), 1);
+        assert_eq!(filtered.rows[0].keys[0].id.as_deref(), Some(docs.as_str()));
+        assert_eq!(filtered.total, Duration::hours(5));
+    }
+
+    #[test]
+    fn computes_week_and_month_bounds() {
+        let day = NaiveDate::from_ymd_opt(2024, 2, 15).unwrap();
+        assert_eq!(
+            start_of_week(day),
+            NaiveDate::from_ymd_opt(2024, 2, 12).unwrap()
+        );
+        assert_eq!(
+            end_of_month(day),
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+        let december = NaiveDate::from_ymd_opt(2026, 12, 3).unwrap();
+        assert_eq!(
+            end_of_month(december),
+            NaiveDate::from_ymd_opt(2026, 12, 31).unwrap()
+        );
+    }
+}
@@ -60,10 +60,10 @@ pub fn load_ledger(path: &Path) -> Result<Ledger, StorageError> {
 }
 
 pub fn save_ledger(path: &Path, ledger: &Ledger) -> Result<(), StorageError> {
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent).map_err(StorageError::Io)?;
-        }
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent).map_err(StorageError::Io)?;
     }
 
     let header = toml::to_string_pretty(&ledger.header).map_err(StorageError::TomlEncode)?;
@@ -99,11 +99,13 @@ mod tests {
         let task_id = ledger
             .add_task(project_id, None, "Write spec".to_string())
             .expect("task should be created");
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 10, 0, 0).unwrap();
         ledger
             .start_task(
                 &task_id,
                 Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap(),
                 Some("deep work".to_string()),
+                now,
             )
             .expect("start should work");
         ledger
@@ -111,6 +113,7 @@ mod tests {
                 &task_id,
                 Utc.with_ymd_and_hms(2026, 1, 1, 10, 0, 0).unwrap(),
                 None,
+                now,
             )
             .expect("stop should work");
 
@@ -0,0 +1,272 @@
+use std::env;
+
+use clap::ValueEnum;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier, Style};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ThemeName {
+    Dark,
+    Light,
+    HighContrast,
+    NoColor,
+}
+
+impl ThemeName {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "dark" => Some(ThemeName::Dark),
+            "light" => Some(ThemeName::Light),
+            "high-contrast" => Some(ThemeName::HighContrast),
+            "no-color" | "none" => Some(ThemeName::NoColor),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub name: ThemeName,
+    pub focused_border: Color,
+    pub inactive_border: Color,
+    pub highlight_background: Color,
+    pub muted: Color,
+    pub accent: Color,
+    pub elapsed: Color,
+    pub running: Color,
+    pub selected_day: Style,
+    pub active_day: Color,
+    truecolor: bool,
+}
+
+impl Theme {
+    /// Resolves the theme from the CLI flag, `CHRONOS_THEME`, and `NO_COLOR`.
+    pub fn resolve(requested: Option<ThemeName>) -> Self {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let name = if no_color {
+            ThemeName::NoColor
+        } else if let Some(name) = requested {
+            name
+        } else {
+            env::var("CHRONOS_THEME")
+                .ok()
+                .and_then(|value| ThemeName::from_name(&value))
+                .unwrap_or(ThemeName::Dark)
+        };
+
+        Self::new(name, terminal_supports_truecolor())
+    }
+
+    pub fn new(name: ThemeName, truecolor: bool) -> Self {
+        match name {
+            ThemeName::Dark => Self {
+                name,
+                focused_border: Color::Yellow,
+                inactive_border: Color::DarkGray,
+                highlight_background: Color::Rgb(42, 45, 52),
+                muted: Color::DarkGray,
+                accent: Color::Yellow,
+                elapsed: Color::LightYellow,
+                running: Color::LightGreen,
+                selected_day: Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+                active_day: Color::LightYellow,
+                truecolor,
+            },
+            ThemeName::Light => Self {
+                name,
+                focused_border: Color::Blue,
+                inactive_border: Color::Gray,
+                highlight_background: Color::Rgb(222, 226, 232),
+                muted: Color::Gray,
+                accent: Color::Blue,
+                elapsed: Color::Magenta,
+                running: Color::Green,
+                selected_day: Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+                active_day: Color::Blue,
+                truecolor,
+            },
+            ThemeName::HighContrast => Self {
+                name,
+                focused_border: Color::LightYellow,
+                inactive_border: Color::White,
+                highlight_background: Color::Blue,
+                muted: Color::Gray,
+                accent: Color::LightYellow,
+                elapsed: Color::LightCyan,
+                running: Color::LightGreen,
+                selected_day: Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+                active_day: Color::LightCyan,
+                truecolor,
+            },
+            ThemeName::NoColor => Self {
+                name,
+                focused_border: Color::Reset,
+                inactive_border: Color::Reset,
+                highlight_background: Color::Reset,
+                muted: Color::Reset,
+                accent: Color::Reset,
+                elapsed: Color::Reset,
+                running: Color::Reset,
+                selected_day: Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD),
+                active_day: Color::Reset,
+                truecolor,
+            },
+        }
+    }
+
+    pub fn border_style(&self, focused: bool) -> Style {
+        if focused {
+            Style::default()
+                .fg(self.focused_border)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(self.inactive_border)
+        }
+    }
+
+    pub fn highlight_style(&self) -> Style {
+        if self.name == ThemeName::NoColor {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().bg(self.highlight_background)
+        }
+    }
+
+    pub fn muted_style(&self) -> Style {
+        Style::default().fg(self.muted)
+    }
+
+    /// Post-processes a rendered frame so widgets that were styled with
+    /// project colors still respect `no-color` and non-truecolor terminals.
+    pub fn apply_to_buffer(&self, buffer: &mut Buffer) {
+        if self.name == ThemeName::NoColor {
+            for cell in &mut buffer.content {
+                cell.fg = Color::Reset;
+                cell.bg = Color::Reset;
+            }
+            return;
+        }
+
+        if self.truecolor {
+            return;
+        }
+
+        for cell in &mut buffer.content {
+            cell.fg = downgrade_color(cell.fg);
+            cell.bg = downgrade_color(cell.bg);
+        }
+    }
+}
+
+/// Parses a project color: one of the 16 named terminal colors, `#rrggbb`,
+/// or a 256-color palette index (`0`-`255`).
+pub fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return None;
+        }
+        let red = u8::from_str_radix(&hex[0..2], 16).ok()?;
+        let green = u8::from_str_radix(&hex[2..4], 16).ok()?;
+        let blue = u8::from_str_radix(&hex[4..6], 16).ok()?;
+        return Some(Color::Rgb(red, green, blue));
+    }
+
+    if !value.is_empty() && value.chars().all(|ch| ch.is_ascii_digit()) {
+        return value.parse::<u8>().ok().map(Color::Indexed);
+    }
+
+    match value {
+        "black" => Some(Color::Black),
+        "red" => Some(Color::Red),
+        "green" => Some(Color::Green),
+        "yellow" => Some(Color::Yellow),
+        "blue" => Some(Color::Blue),
+        "magenta" => Some(Color::Magenta),
+        "cyan" => Some(Color::Cyan),
+        "gray" => Some(Color::Gray),
+        "dark_gray" => Some(Color::DarkGray),
+        "light_red" => Some(Color::LightRed),
+        "light_green" => Some(Color::LightGreen),
+        "light_yellow" => Some(Color::LightYellow),
+        "light_blue" => Some(Color::LightBlue),
+        "light_magenta" => Some(Color::LightMagenta),
+        "light_cyan" => Some(Color::LightCyan),
+        "white" => Some(Color::White),
+        _ => None,
+    }
+}
+
+fn terminal_supports_truecolor() -> bool {
+    env::var("COLORTERM")
+        .map(|value| {
+            let value = value.to_ascii_lowercase();
+            value.contains("truecolor") || value.contains("24bit")
+        })
+        .unwrap_or(false)
+}
+
+fn downgrade_color(color: Color) -> Color {
+    match color {
+        Color::Rgb(red, green, blue) => Color::Indexed(rgb_to_ansi256(red, green, blue)),
+        other => other,
+    }
+}
+
+fn rgb_to_ansi256(red: u8, green: u8, blue: u8) -> u8 {
+    if red == green && green == blue {
+        if red < 8 {
+            return 16;
+        }
+        if red > 248 {
+            return 231;
+        }
+        return 232 + ((u16::from(red) - 8) * 24 / 247) as u8;
+    }
+
+    let scale = |channel: u8| ((u16::from(channel) * 5 + 127) / 255) as u8;
+    16 + 36 * scale(red) + 6 * scale(green) + scale(blue)
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Color;
+
+    use super::{ThemeName, parse_color, rgb_to_ansi256};
+
+    #[test]
+    fn parses_named_hex_and_indexed_colors() {
+        assert_eq!(parse_color("light_cyan"), Some(Color::LightCyan));
+        assert_eq!(parse_color("#ff8800"), Some(Color::Rgb(255, 136, 0)));
+        assert_eq!(parse_color("208"), Some(Color::Indexed(208)));
+        assert_eq!(parse_color("#ff88"), None);
+        assert_eq!(parse_color("256"), None);
+        assert_eq!(parse_color("orange"), None);
+    }
+
+    #[test]
+    fn downgrades_rgb_to_nearest_palette_entry() {
+        assert_eq!(rgb_to_ansi256(255, 0, 0), 196);
+        assert_eq!(rgb_to_ansi256(0, 0, 0), 16);
+        assert_eq!(rgb_to_ansi256(42, 42, 42), 235);
+    }
+
+    #[test]
+    fn resolves_theme_names() {
+        assert_eq!(
+            ThemeName::from_name("high_contrast"),
+            Some(ThemeName::HighContrast)
+        );
+        assert_eq!(ThemeName::from_name("No-Color"), Some(ThemeName::NoColor));
+        assert_eq!(ThemeName::from_name("solarized"), None);
+    }
+}
@@ -0,0 +1,341 @@
+use chrono::{
+    DateTime, Duration, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
+
+const TIME_INPUT_HINT: &str =
+    "expected e.g. 0930, 9:30am, -15m, 15 minutes ago, yesterday 17:00 or 2026-03-08 09:15";
+const END_TIME_INPUT_HINT: &str = "expected a time like 17:30 or a duration like +45m or 1h30";
+const TIME_OUT_OF_RANGE: &str = "time out of range";
+
+/// A parsed time input before it is anchored to a reference instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Moment {
+    /// Offset from "now", e.g. `-15m` or `15 minutes ago`.
+    FromNow(Duration),
+    /// Wall-clock time in the local time zone.
+    Local(NaiveDateTime),
+    /// Fully qualified RFC 3339 timestamp.
+    Exact(DateTime<Utc>),
+}
+
+/// Resolves a start-style time input. Bare clock times land on `day`;
+/// relative forms are measured from `now`.
+pub fn parse_time_input(
+    input: &str,
+    field_name: &str,
+    day: NaiveDate,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, String> {
+    let value = input.trim();
+    if value.is_empty() {
+        return Err(format!("{field_name} is required"));
+    }
+    let today = now.with_timezone(&Local).date_naive();
+    let moment = parse_moment(value, day, today)
+        .ok_or_else(|| format!("invalid {field_name} '{value}', {TIME_INPUT_HINT}"))?;
+    resolve_moment(moment, now)
+}
+
+/// Resolves an end-style time input. On top of everything
+/// [`parse_time_input`] accepts, `+45m` and unsigned durations such as
+/// `1h30` are measured from `start`.
+pub fn parse_end_time_input(
+    input: &str,
+    field_name: &str,
+    start: DateTime<Utc>,
+    day: NaiveDate,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, String> {
+    let value = input.trim();
+    if value.is_empty() {
+        return Err(format!("{field_name} is required"));
+    }
+    let lowered = value.to_ascii_lowercase();
+    let duration = match lowered.strip_prefix('+') {
+        Some(rest) => parse_duration(rest, true),
+        None => parse_duration(&lowered, false),
+    };
+    if let Some(duration) = duration {
+        return start
+            .checked_add_signed(duration)
+            .ok_or_else(|| TIME_OUT_OF_RANGE.to_string());
+    }
+    parse_time_input(value, field_name, day, now)
+        .map_err(|_| format!("invalid {field_name} '{value}', {END_TIME_INPUT_HINT}"))
+}
+
+/// Parses a positive duration such as `45m`, `1h30` or `90` (minutes).
+pub fn parse_duration_input(input: &str, field_name: &str) -> Result<Duration, String> {
+    let value = input.trim();
+    let lowered = value.to_ascii_lowercase();
+    parse_duration(lowered.strip_prefix('+').unwrap_or(&lowered), true)
+        .filter(|duration| *duration > Duration::zero())
+        .ok_or_else(|| format!("invalid {field_name} '{value}', expected e.g. 45m or 1h30"))
+}
+
+/// Parses a calendar date: `today`, `yesterday`, `tomorrow` or `YYYY-MM-DD`.
+pub fn parse_date_input(
+    input: &str,
+    field_name: &str,
+    today: NaiveDate,
+) -> Result<NaiveDate, String> {
+    let value = input.trim();
+    parse_date(&value.to_ascii_lowercase(), today).ok_or_else(|| {
+        format!("invalid {field_name} '{value}', expected YYYY-MM-DD, today or yesterday")
+    })
+}
+
+/// Short human description of a resolved timestamp relative to `now`, used
+/// to preview what a time input means before it is submitted.
+pub fn describe_timestamp(timestamp: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let local = timestamp.with_timezone(&Local);
+    let today = now.with_timezone(&Local).date_naive();
+    let day = match (local.date_naive() - today).num_days() {
+        0 => "today".to_string(),
+        -1 => "yesterday".to_string(),
+        1 => "tomorrow".to_string(),
+        _ => local.format("%a %Y-%m-%d").to_string(),
+    };
+    let distance = describe_distance(timestamp - now);
+    format!("{day} {} ({distance})", local.format("%H:%M"))
+}
+
+fn describe_distance(delta: Duration) -> String {
+    let minutes = delta.num_minutes();
+    if minutes == 0 {
+        return "now".to_string();
+    }
+    let amount = minutes.unsigned_abs();
+    let text = match (amount / 60, amount % 60) {
+        (0, minutes) => format!("{minutes}m"),
+        (hours, 0) => format!("{hours}h"),
+        (hours, minutes) => format!("{hours}h {minutes}m"),
+    };
+    if minutes < 0 {
+        format!("{text} ago")
+    } else {
+        format!("in {text}")
+    }
+}
+
+fn resolve_moment(moment: Moment, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    match moment {
+        Moment::FromNow(offset) => now
+            .checked_add_signed(offset)
+            .ok_or_else(|| TIME_OUT_OF_RANGE.to_string()),
+        Moment::Exact(timestamp) => Ok(timestamp),
+        Moment::Local(naive) => match Local.from_local_datetime(&naive) {
+            LocalResult::Single(local) => Ok(local.with_timezone(&Utc)),
+            LocalResult::Ambiguous(first, second) => Ok(first.min(second).with_timezone(&Utc)),
+            LocalResult::None => Err(format!(
+                "local time {} does not exist",
+                naive.format("%Y-%m-%d %H:%M")
+            )),
+        },
+    }
+}
+
+fn parse_moment(value: &str, day: NaiveDate, today: NaiveDate) -> Option<Moment> {
+    let value = value.trim().to_ascii_lowercase();
+    if value == "now" {
+        return Some(Moment::FromNow(Duration::zero()));
+    }
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(&value) {
+        return Some(Moment::Exact(timestamp.with_timezone(&Utc)));
+    }
+    if let Some(rest) = value.strip_suffix("ago") {
+        return parse_duration(rest, false).map(|duration| Moment::FromNow(-duration));
+    }
+    if let Some(rest) = value.strip_prefix('-') {
+        return parse_duration(rest, true).map(|duration| Moment::FromNow(-duration));
+    }
+    if let Some(rest) = value.strip_prefix('+') {
+        return parse_duration(rest, true).map(Moment::FromNow);
+    }
+
+    // `2026-03-08T09:15` reads the same as `2026-03-08 09:15`.
+    let value = match value.get(..11) {
+        Some(prefix) if prefix.ends_with('t') && parse_date(&prefix[..10], today).is_some() => {
+            format!("{} {}", &value[..10], &value[11..])
+        }
+        _ => value,
+    };
+    if let Some((first, rest)) = value.split_once(char::is_whitespace)
+        && let Some(date) = parse_date(first, today)
+    {
+        return parse_clock(rest).map(|time| Moment::Local(date.and_time(time)));
+    }
+    parse_clock(&value).map(|time| Moment::Local(day.and_time(time)))
+}
+
+fn parse_date(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match value {
+        "today" => Some(today),
+        "yesterday" => today.pred_opt(),
+        "tomorrow" => today.succ_opt(),
+        _ => NaiveDate::parse_from_str(value, "%Y-%m-%d").ok(),
+    }
+}
+
+/// Parses `17:00`, `1700`, `930`, `9:30am`, `9 pm` and similar clock times.
+fn parse_clock(value: &str) -> Option<NaiveTime> {
+    let value = value.trim();
+    let (body, meridiem) = if let Some(body) = value.strip_suffix("am") {
+        (body.trim_end(), Some(false))
+    } else if let Some(body) = value.strip_suffix("pm") {
+        (body.trim_end(), Some(true))
+    } else {
+        (value, None)
+    };
+    if body.is_empty() || !body.chars().all(|ch| ch.is_ascii_digit() || ch == ':') {
+        return None;
+    }
+
+    let (hour, minute) = match body.split_once(':') {
+        Some((hour, minute)) if (1..=2).contains(&hour.len()) && minute.len() == 2 => {
+            (hour, minute)
+        }
+        Some(_) => return None,
+        None if body.len() <= 2 && meridiem.is_some() => (body, "00"),
+        None if (3..=4).contains(&body.len()) => body.split_at(body.len() - 2),
+        None => return None,
+    };
+    let mut hour = hour.parse::<u32>().ok()?;
+    let minute = minute.parse::<u32>().ok()?;
+    if let Some(is_pm) = meridiem {
+        if !(1..=12).contains(&hour) {
+            return None;
+        }
+        hour = hour % 12 + if is_pm { 12 } else { 0 };
+    }
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// Parses `45m`, `1h30`, `1h 30m`, `90 minutes` and so on. A lone number
+/// counts as minutes only when `bare_minutes` is set (after a `+`/`-`).
+fn parse_duration(value: &str, bare_minutes: bool) -> Option<Duration> {
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return None;
+    }
+
+    let mut total_minutes = 0i64;
+    let mut last_unit = None;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            return None;
+        }
+        let amount = rest[..digits].parse::<i64>().ok()?;
+        rest = rest[digits..].trim_start();
+        let unit_len = rest
+            .find(|ch: char| !ch.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let unit = &rest[..unit_len];
+        rest = rest[unit_len..].trim_start();
+
+        let scale = match unit {
+            "h" | "hr" | "hrs" | "hour" | "hours" => 60,
+            "m" | "min" | "mins" | "minute" | "minutes" => 1,
+            "" if rest.is_empty() && (last_unit == Some(60) || bare_minutes) => 1,
+            _ => return None,
+        };
+        total_minutes = total_minutes.checked_add(amount.checked_mul(scale)?)?;
+        last_unit = Some(scale);
+    }
+    Duration::try_minutes(total_minutes)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate, TimeZone, Utc};
+
+    use super::{
+        Moment, describe_timestamp, parse_clock, parse_duration, parse_end_time_input,
+        parse_moment, parse_time_input,
+    };
+
+    fn local(day: NaiveDate, hour: u32, minute: u32) -> Option<Moment> {
+        Some(Moment::Local(day.and_hms_opt(hour, minute, 0).unwrap()))
+    }
+
+    #[test]
+    fn parses_clock_and_dated_inputs() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 3, 12).unwrap();
+
+        assert_eq!(parse_moment("0930", day, today), local(day, 9, 30));
+        assert_eq!(parse_moment("9:30am", day, today), local(day, 9, 30));
+        assert_eq!(parse_moment("12 am", day, today), local(day, 0, 0));
+        assert_eq!(parse_moment("5pm", day, today), local(day, 17, 0));
+        assert_eq!(
+            parse_moment("yesterday 17:00", day, today),
+            local(today.pred_opt().unwrap(), 17, 0)
+        );
+        let march_8 = NaiveDate::from_ymd_opt(2026, 3, 8).unwrap();
+        assert_eq!(
+            parse_moment("2026-03-08 09:15", day, today),
+            local(march_8, 9, 15)
+        );
+        assert_eq!(
+            parse_moment("2026-03-08T09:15", day, today),
+            local(march_8, 9, 15)
+        );
+        assert_eq!(parse_moment("25:00", day, today), None);
+        assert_eq!(parse_moment("13pm", day, today), None);
+        assert_eq!(parse_moment("yesterday", day, today), None);
+        assert_eq!(parse_clock("9"), None);
+    }
+
+    #[test]
+    fn parses_relative_inputs_and_durations() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+
+        assert_eq!(
+            parse_moment("-15m", day, day),
+            Some(Moment::FromNow(Duration::minutes(-15)))
+        );
+        assert_eq!(
+            parse_moment("15 minutes ago", day, day),
+            Some(Moment::FromNow(Duration::minutes(-15)))
+        );
+        assert_eq!(
+            parse_moment("now", day, day),
+            Some(Moment::FromNow(Duration::zero()))
+        );
+        assert_eq!(parse_duration("1h30", false), Some(Duration::minutes(90)));
+        assert_eq!(
+            parse_duration("2 hours 5 min", false),
+            Some(Duration::minutes(125))
+        );
+        assert_eq!(parse_duration("45", true), Some(Duration::minutes(45)));
+        assert_eq!(parse_duration("45", false), None);
+        assert_eq!(parse_duration("h", true), None);
+        assert_eq!(parse_duration("99999999999999999999m", true), None);
+    }
+
+    #[test]
+    fn offsets_beyond_the_calendar_are_errors() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let day = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+
+        assert_eq!(
+            parse_time_input("-10000000000000m", "start", day, now),
+            Err("time out of range".to_string())
+        );
+        assert_eq!(
+            parse_time_input("+10000000000000m", "start", day, now),
+            Err("time out of range".to_string())
+        );
+        assert_eq!(
+            parse_end_time_input("+10000000000000m", "end", now, day, now),
+            Err("time out of range".to_string())
+        );
+        // Far but representable times still resolve, and the prompt
+        // preview can describe them.
+        let far = parse_time_input("+100000000000m", "start", day, now).unwrap();
+        assert!(describe_timestamp(far, now).contains("in "));
+    }
+}
@@ -2,24 +2,33 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error;
 use std::io;
 use std::path::{Path, PathBuf};
-use std::time::Duration as StdDuration;
+use std::time::{Duration as StdDuration, Instant};
 
-use chrono::{DateTime, Datelike, Duration, Local, LocalResult, NaiveDate, TimeZone, Utc};
-use crossterm::event::{self, Event as CEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use chrono::{
+    DateTime, Datelike, Duration, Local, LocalResult, NaiveDate, NaiveTime, TimeZone, Timelike, Utc,
+};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event as CEvent, KeyCode, KeyEvent,
+    KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
 use crossterm::{ExecutableCommand, execute};
 use ratatui::backend::CrosstermBackend;
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::layout::{Constraint, Direction, Layout, Margin, Position, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
 use ratatui::{Frame, Terminal};
 
-use crate::domain::{EventKind, Ledger, LedgerSnapshot, Task, TimeEvent, format_duration};
+use crate::domain::{
+    CopyReport, EntitySelection, EventKind, Ledger, LedgerSnapshot, Task, format_duration,
+};
 use crate::ledgers::{forget_ledger, ledger_path_from_input, recent_ledgers, remember_ledger};
 use crate::storage::{load_ledger, save_ledger};
+use crate::theme::{Theme, ThemeName, parse_color};
+use crate::timeinput::{describe_timestamp, parse_end_time_input, parse_time_input};
 
 const TERMINAL_COLORS: [&str; 16] = [
     "black",
@@ -39,23 +48,36 @@ const TERMINAL_COLORS: [&str; 16] = [
     "light_cyan",
     "white",
 ];
-const FOCUSED_PANEL_BORDER_COLOR: Color = Color::Yellow;
-const INACTIVE_PANEL_BORDER_COLOR: Color = Color::DarkGray;
-const HIGHLIGHT_BACKGROUND_COLOR: Color = Color::Rgb(42, 45, 52);
 const COLOR_SWATCH: &str = "████████████████";
 const NO_COLOR_SWATCH: &str = "░░░░░░░░░░░░░░░░";
-
-pub fn run_dashboard(ledger: &mut Ledger, ledger_path: &mut PathBuf) -> Result<(), Box<dyn Error>> {
+const CUSTOM_COLOR_OPTION: &str = "__custom";
+const HELP_PAGE_ROWS: usize = 10;
+const DETAIL_SPARKLINE_DAYS: i64 = 56;
+const SPARKLINE_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const DOUBLE_CLICK_WINDOW: StdDuration = StdDuration::from_millis(400);
+const TIMELINE_LABEL_WIDTH: u16 = 16;
+const MIN_GAP_MINUTES: i64 = 1;
+
+pub fn run_dashboard(
+    ledger: &mut Ledger,
+    ledger_path: &mut PathBuf,
+    theme: Theme,
+) -> Result<(), Box<dyn Error>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     stdout.execute(EnterAlternateScreen)?;
+    stdout.execute(EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let result = run_event_loop(&mut terminal, ledger, ledger_path);
+    let result = run_event_loop(&mut terminal, ledger, ledger_path, theme);
 
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableMouseCapture,
+        LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
 
     result
@@ -65,36 +87,54 @@ fn run_event_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     ledger: &mut Ledger,
     ledger_path: &mut PathBuf,
+    theme: Theme,
 ) -> Result<(), Box<dyn Error>> {
-    let mut app = App::new(ledger, Utc::now());
+    let mut app = App::new(ledger, Utc::now(), theme);
 
     loop {
         let now = Utc::now();
         let snapshot = ledger.snapshot(now);
         let view = build_view(&app, ledger, &snapshot, now);
         app.clamp_selection(&view);
-        terminal.draw(|frame| draw_dashboard(frame, &app, &view))?;
+        let mut hits = DashboardHits::default();
+        terminal.draw(|frame| hits = draw_dashboard(frame, &app, &view))?;
 
-        if event::poll(StdDuration::from_millis(250))? {
-            if let CEvent::Key(key) = event::read()? {
-                if key.kind != KeyEventKind::Press {
-                    continue;
-                }
+        if !event::poll(StdDuration::from_millis(250))? {
+            flush_pending_persist(&mut app, ledger, ledger_path);
+        } else {
+            let event = event::read()?;
+            if !is_nudge_event(&event) {
+                flush_pending_persist(&mut app, ledger, ledger_path);
+            }
 
-                let should_quit = match &app.mode {
+            let should_quit = match event {
+                CEvent::Key(key) if key.kind == KeyEventKind::Press => match &app.mode {
                     InputMode::Prompt(_) => handle_prompt_key(&mut app, key, ledger, ledger_path),
-                    InputMode::Select(_) => {
-                        handle_select_key(&mut app, key.code, ledger, ledger_path)
-                    }
+                    InputMode::Select(_) => handle_select_key(&mut app, key, ledger, ledger_path),
                     InputMode::Edit(_) => handle_edit_key(&mut app, key, ledger, ledger_path),
+                    InputMode::Help(_) => handle_help_key(&mut app, key),
+                    InputMode::Detail(_) => handle_detail_key(&mut app, key),
                     InputMode::Normal => {
-                        handle_normal_key(&mut app, key.code, ledger, ledger_path, &snapshot, &view)
+                        handle_normal_key(&mut app, key, ledger, ledger_path, &snapshot, &view)
                     }
-                };
-
-                if should_quit {
-                    break;
+                },
+                CEvent::Mouse(mouse) => {
+                    handle_mouse(
+                        &mut app,
+                        mouse,
+                        ledger,
+                        ledger_path,
+                        &snapshot,
+                        &view,
+                        &hits,
+                    );
+                    false
                 }
+                _ => false,
+            };
+
+            if should_quit {
+                break;
             }
         }
     }
@@ -102,7 +142,7 @@ fn run_event_loop(
     Ok(())
 }
 
-fn draw_dashboard(frame: &mut Frame, app: &App, view: &ViewModel) {
+fn draw_dashboard(frame: &mut Frame, app: &App, view: &ViewModel) -> DashboardHits {
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(12), Constraint::Length(6)])
@@ -123,11 +163,14 @@ fn draw_dashboard(frame: &mut Frame, app: &App, view: &ViewModel) {
         .split(body[0]);
 
     render_calendar_panel(frame, left[0], app, &view.calendar_active_days);
-    render_explorer_panel(frame, left[1], app, view);
-    render_week_stats_panel(frame, body[2], view);
+    let explorer = render_explorer_panel(frame, left[1], app, view);
+    match &view.period_stats {
+        Some(stats) => render_period_stats_panel(frame, body[2], &app.theme, stats),
+        None => render_week_stats_panel(frame, body[2], &app.theme, view),
+    }
 
-    if view.running_rows.is_empty() {
-        render_selected_day_panel(frame, body[1], app, view);
+    let (day_area, running) = if view.running_rows.is_empty() {
+        (body[1], None)
     } else {
         let center_layout = Layout::default()
             .direction(Direction::Vertical)
@@ -136,16 +179,44 @@ fn draw_dashboard(frame: &mut Frame, app: &App, view: &ViewModel) {
                 Constraint::Length(running_panel_height(view.running_rows.len())),
             ])
             .split(body[1]);
-        render_selected_day_panel(frame, center_layout[0], app, view);
-        render_running_panel(frame, center_layout[1], app, view);
-    }
+        (
+            center_layout[0],
+            Some(render_running_panel(frame, center_layout[1], app, view)),
+        )
+    };
+    let (day, timeline) = match app.day_layout {
+        DayLayout::List => (render_selected_day_panel(frame, day_area, app, view), None),
+        DayLayout::Timeline => (
+            ListHit::panel(day_area, 0, Vec::new()),
+            Some(render_day_timeline(frame, day_area, app, view)),
+        ),
+    };
     render_footer(frame, layout[1], app);
+    let popup = render_mode_popup(frame, &app.theme, &app.mode);
+
+    app.theme.apply_to_buffer(frame.buffer_mut());
+
+    DashboardHits {
+        calendar: left[0],
+        day,
+        timeline,
+        running,
+        explorer,
+        popup,
+    }
+}
 
-    match &app.mode {
-        InputMode::Select(select) => render_select_popup(frame, select),
-        InputMode::Prompt(prompt) => render_prompt_popup(frame, prompt),
-        InputMode::Edit(edit) => render_edit_popup(frame, edit),
-        InputMode::Normal => {}
+fn render_mode_popup(frame: &mut Frame, theme: &Theme, mode: &InputMode) -> Option<ListHit> {
+    match mode {
+        InputMode::Select(select) => Some(render_select_popup(frame, theme, select)),
+        InputMode::Prompt(prompt) => Some(render_prompt_popup(frame, theme, prompt)),
+        InputMode::Edit(edit) => Some(render_edit_popup(frame, theme, edit)),
+        InputMode::Help(help) => {
+            render_mode_popup(frame, theme, &help.return_mode);
+            Some(render_help_popup(frame, theme, help))
+        }
+        InputMode::Detail(detail) => Some(render_detail_popup(frame, theme, detail)),
+        InputMode::Normal => None,
     }
 }
 
@@ -182,12 +253,9 @@ fn render_calendar_panel(
                 .expect("calendar day must be valid");
             let mut style = Style::default();
             if date == selected_day {
-                style = style
-                    .fg(Color::Black)
-                    .bg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD);
+                style = app.theme.selected_day;
             } else if active_days.contains(&date) {
-                style = style.fg(Color::LightYellow).add_modifier(Modifier::BOLD);
+                style = style.fg(app.theme.active_day).add_modifier(Modifier::BOLD);
             }
 
             spans.push(Span::styled(format!("{:>2} ", day_counter), style));
@@ -199,12 +267,12 @@ fn render_calendar_panel(
     let block = Block::default()
         .borders(Borders::ALL)
         .title("Calendar")
-        .border_style(border_style(app.focus == FocusPane::Calendar));
+        .border_style(app.theme.border_style(app.focus == FocusPane::Calendar));
     let calendar = Paragraph::new(lines).block(block);
     frame.render_widget(calendar, area);
 }
 
-fn render_explorer_panel(frame: &mut Frame, area: Rect, app: &App, view: &ViewModel) {
+fn render_explorer_panel(frame: &mut Frame, area: Rect, app: &App, view: &ViewModel) -> ListHit {
     let title = match &app.explorer_mode {
         ExplorerMode::Projects => Line::from("Explorer: Projects"),
         ExplorerMode::Categories => Line::from("Explorer: Categories"),
@@ -223,6 +291,7 @@ fn render_explorer_panel(frame: &mut Frame, area: Rect, app: &App, view: &ViewMo
         .iter()
         .map(|row| ListItem::new(row.line.clone()))
         .collect::<Vec<_>>();
+    let heights = list_item_heights(&items);
 
     let mut state = ListState::default();
     if !view.explorer_rows.is_empty() {
@@ -232,23 +301,25 @@ fn render_explorer_panel(frame: &mut Frame, area: Rect, app: &App, view: &ViewMo
     let block = Block::default()
         .borders(Borders::ALL)
         .title(title)
-        .border_style(border_style(app.focus == FocusPane::Explorer));
+        .border_style(app.theme.border_style(app.focus == FocusPane::Explorer));
     let list = List::new(if items.is_empty() {
         vec![ListItem::new("(empty)")]
     } else {
         items
     })
     .block(block)
-    .highlight_style(
-        Style::default()
-            .bg(HIGHLIGHT_BACKGROUND_COLOR)
-            .add_modifier(Modifier::BOLD),
-    );
+    .highlight_style(app.theme.highlight_style().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, area, &mut state);
+    ListHit::panel(area, state.offset(), heights)
 }
 
-fn render_selected_day_panel(frame: &mut Frame, area: Rect, app: &App, view: &ViewModel) {
+fn render_selected_day_panel(
+    frame: &mut Frame,
+    area: Rect,
+    app: &App,
+    view: &ViewModel,
+) -> ListHit {
     let mut items = Vec::new();
     let mut previous_project_id: Option<&str> = None;
     let mut previous_task_title: Option<&str> = None;
@@ -257,6 +328,7 @@ fn render_selected_day_panel(frame: &mut Frame, area: Rect, app: &App, view: &Vi
         let show_task_label =
             show_project_header || previous_task_title != Some(row.task_title.as_str());
         items.push(render_day_row_item(
+            &app.theme,
             row,
             show_project_header,
             show_task_label,
@@ -267,6 +339,7 @@ fn render_selected_day_panel(frame: &mut Frame, area: Rect, app: &App, view: &Vi
         previous_task_title = Some(row.task_title.as_str());
     }
 
+    let heights = list_item_heights(&items);
     if items.is_empty() {
         items.push(ListItem::new("(no sessions for selected day)"));
     }
@@ -282,26 +355,222 @@ fn render_selected_day_panel(frame: &mut Frame, area: Rect, app: &App, view: &Vi
             Block::default()
                 .borders(Borders::ALL)
                 .title(title)
-                .border_style(border_style(app.focus == FocusPane::Day)),
+                .border_style(app.theme.border_style(app.focus == FocusPane::Day)),
         )
-        .highlight_style(
-            Style::default()
-                .bg(HIGHLIGHT_BACKGROUND_COLOR)
-                .add_modifier(Modifier::BOLD),
-        );
+        .highlight_style(app.theme.highlight_style().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, area, &mut state);
+    ListHit::panel(area, state.offset(), heights)
 }
 
-fn render_running_panel(frame: &mut Frame, area: Rect, app: &App, view: &ViewModel) {
-    let items = if view.running_rows.is_empty() {
-        vec![ListItem::new("(no running tasks)")]
-    } else {
-        view.running_rows
-            .iter()
-            .map(render_running_row_item)
-            .collect::<Vec<_>>()
+fn render_day_timeline(frame: &mut Frame, area: Rect, app: &App, view: &ViewModel) -> TimelineHit {
+    let title = format!("{} · timeline", app.selected_day.format("%A, %d %B %Y"));
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(app.theme.border_style(app.focus == FocusPane::Day));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut hit = TimelineHit::default();
+    let label_width = TIMELINE_LABEL_WIDTH.min(inner.width / 3);
+    let track_width = inner.width.saturating_sub(label_width + 1);
+    if view.day_rows.is_empty() || track_width == 0 || inner.height < 3 {
+        frame.render_widget(Paragraph::new("(no sessions for selected day)"), inner);
+        return hit;
+    }
+
+    let (day_start, day_end) = view.day_bounds;
+    let track = TimelineTrack {
+        day_start,
+        day_seconds: (day_end - day_start).num_seconds().max(1),
+        width: track_width,
     };
+    let now_column = (view.now >= day_start && view.now < day_end).then(|| track.column(view.now));
+    let muted = app.theme.muted_style();
+    let now_style = Style::default()
+        .fg(app.theme.accent)
+        .add_modifier(Modifier::BOLD);
+
+    // One lane per task, ordered by the task's first session of the day.
+    let mut lanes: Vec<(&DaySessionRow, Vec<usize>)> = Vec::new();
+    for (index, row) in view.day_rows.iter().enumerate() {
+        match lanes
+            .iter_mut()
+            .find(|(first, _)| first.task_id == row.task_id)
+        {
+            Some((_, indices)) => indices.push(index),
+            None => lanes.push((row, vec![index])),
+        }
+    }
+
+    let mut lines = vec![timeline_axis_line(
+        &track,
+        label_width,
+        now_column,
+        muted,
+        now_style,
+    )];
+
+    let visible_lanes = usize::from(inner.height - 2);
+    let selected_lane = lanes
+        .iter()
+        .position(|(_, indices)| indices.contains(&app.day_index))
+        .unwrap_or(0);
+    let first_lane = selected_lane.saturating_sub(visible_lanes.saturating_sub(1));
+    for (lane_offset, (first, indices)) in lanes
+        .iter()
+        .skip(first_lane)
+        .take(visible_lanes)
+        .enumerate()
+    {
+        let mut cells = vec![('·', muted); usize::from(track_width)];
+        if let Some(column) = now_column {
+            cells[usize::from(column)] = ('│', now_style);
+        }
+
+        let lane_y = inner.y + 1 + lane_offset as u16;
+        for &index in indices {
+            let row = &view.day_rows[index];
+            let (start, end) = track.span(row.display_start, row.display_stop);
+            let (symbol, style) = if index == app.day_index {
+                ('━', app.theme.selected_day)
+            } else {
+                ('█', row.project_style)
+            };
+            for cell in &mut cells[usize::from(start)..usize::from(end)] {
+                *cell = (symbol, style);
+            }
+            hit.segments.push((
+                Rect::new(inner.x + label_width + 1 + start, lane_y, end - start, 1),
+                index,
+            ));
+        }
+
+        let mut label_style = first.project_style;
+        if indices.contains(&app.day_index) {
+            label_style = label_style.add_modifier(Modifier::BOLD);
+        }
+        let label = first
+            .task_title
+            .chars()
+            .take(usize::from(label_width))
+            .collect::<String>();
+        let mut spans = vec![
+            Span::styled(
+                format!("{label:<width$}", width = usize::from(label_width)),
+                label_style,
+            ),
+            Span::raw(" "),
+        ];
+        spans.extend(timeline_cell_spans(&cells));
+        lines.push(Line::from(spans));
+    }
+
+    while lines.len() < usize::from(inner.height - 1) {
+        lines.push(Line::from(""));
+    }
+    if let Some(row) = view.day_rows.get(app.day_index) {
+        let mut detail = day_row_timing_line(&app.theme, row, true, app.day_field, true).spans;
+        detail.push(Span::raw(" · "));
+        detail.push(Span::styled(row.project_name.clone(), row.project_style));
+        lines.push(Line::from(detail));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+    hit
+}
+
+fn timeline_axis_line(
+    track: &TimelineTrack,
+    label_width: u16,
+    now_column: Option<u16>,
+    muted: Style,
+    now_style: Style,
+) -> Line<'static> {
+    let hours = (track.day_seconds / 3600).max(1);
+    let step = [1, 2, 3, 4, 6, 12]
+        .into_iter()
+        .find(|step| i64::from(track.width) * step / hours >= 3)
+        .unwrap_or(24);
+
+    let mut cells = vec![(' ', muted); usize::from(track.width)];
+    for hour in (0..hours).step_by(step as usize) {
+        let timestamp = track.day_start + Duration::hours(hour);
+        let column = usize::from(track.column(timestamp));
+        let label = timestamp.with_timezone(&Local).format("%H").to_string();
+        for (offset, ch) in label.chars().enumerate() {
+            if let Some(cell) = cells.get_mut(column + offset) {
+                *cell = (ch, muted);
+            }
+        }
+    }
+    if let Some(column) = now_column {
+        cells[usize::from(column)] = ('▼', now_style);
+    }
+
+    let mut spans = vec![Span::raw(" ".repeat(usize::from(label_width) + 1))];
+    spans.extend(timeline_cell_spans(&cells));
+    Line::from(spans)
+}
+
+fn timeline_cell_spans(cells: &[(char, Style)]) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut run_style = None;
+    for &(symbol, style) in cells {
+        if run_style != Some(style) {
+            if let Some(previous) = run_style {
+                spans.push(Span::styled(std::mem::take(&mut run), previous));
+            }
+            run_style = Some(style);
+        }
+        run.push(symbol);
+    }
+    if let Some(style) = run_style {
+        spans.push(Span::styled(run, style));
+    }
+    spans
+}
+
+/// Maps timestamps within a ledger day onto timeline columns.
+struct TimelineTrack {
+    day_start: DateTime<Utc>,
+    day_seconds: i64,
+    width: u16,
+}
+
+impl TimelineTrack {
+    fn column(&self, timestamp: DateTime<Utc>) -> u16 {
+        let offset = (timestamp - self.day_start)
+            .num_seconds()
+            .clamp(0, self.day_seconds);
+        (offset * i64::from(self.width) / self.day_seconds).min(i64::from(self.width) - 1) as u16
+    }
+
+    /// Returns the half-open column range covered by a session; every session
+    /// gets at least one column so short entries stay visible and clickable.
+    fn span(&self, start: DateTime<Utc>, stop: DateTime<Utc>) -> (u16, u16) {
+        let first = self.column(start);
+        let offset = (stop - self.day_start)
+            .num_seconds()
+            .clamp(0, self.day_seconds);
+        let last =
+            ((offset * i64::from(self.width) + self.day_seconds - 1) / self.day_seconds) as u16;
+        (first, last.clamp(first + 1, self.width))
+    }
+}
+
+fn render_running_panel(frame: &mut Frame, area: Rect, app: &App, view: &ViewModel) -> ListHit {
+    let mut items = view
+        .running_rows
+        .iter()
+        .map(|row| render_running_row_item(&app.theme, row))
+        .collect::<Vec<_>>();
+    let heights = list_item_heights(&items);
+    if items.is_empty() {
+        items.push(ListItem::new("(no running tasks)"));
+    }
 
     let mut state = ListState::default();
     if !view.running_rows.is_empty() {
@@ -317,18 +586,15 @@ fn render_running_panel(frame: &mut Frame, area: Rect, app: &App, view: &ViewMod
             Block::default()
                 .borders(Borders::ALL)
                 .title(title)
-                .border_style(border_style(app.focus == FocusPane::Running)),
+                .border_style(app.theme.border_style(app.focus == FocusPane::Running)),
         )
-        .highlight_style(
-            Style::default()
-                .bg(HIGHLIGHT_BACKGROUND_COLOR)
-                .add_modifier(Modifier::BOLD),
-        );
+        .highlight_style(app.theme.highlight_style().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, area, &mut state);
+    ListHit::panel(area, state.offset(), heights)
 }
 
-fn render_week_stats_panel(frame: &mut Frame, area: Rect, view: &ViewModel) {
+fn render_week_stats_panel(frame: &mut Frame, area: Rect, theme: &Theme, view: &ViewModel) {
     let week = &view.week_stats;
     let mut lines = Vec::new();
     lines.push(Line::from(format!(
@@ -414,13 +680,175 @@ fn render_week_stats_panel(frame: &mut Frame, area: Rect, view: &ViewModel) {
         Block::default()
             .borders(Borders::ALL)
             .title("Week Stats")
-            .border_style(Style::default().fg(INACTIVE_PANEL_BORDER_COLOR)),
+            .border_style(theme.border_style(false)),
+    );
+    frame.render_widget(panel, area);
+}
+
+fn render_period_stats_panel(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    stats: &PeriodStatsView,
+) {
+    let mut lines = Vec::new();
+    lines.push(Line::from(stats.title.clone()));
+    lines.push(Line::from(format!(
+        "Total: {}",
+        format_duration(stats.total)
+    )));
+    lines.push(Line::from(period_comparison(
+        &stats.previous_title,
+        stats.total,
+        stats.previous_total,
+    )));
+    let avg_per_active_day = if stats.active_days == 0 {
+        Duration::zero()
+    } else {
+        stats.total / stats.active_days as i32
+    };
+    lines.push(Line::from(format!(
+        "Avg/active day: {}",
+        format_duration(avg_per_active_day)
+    )));
+    lines.push(Line::from(match stats.busiest_day {
+        Some((day, duration)) => format!(
+            "Busiest day: {} ({})",
+            day.format("%a %d %b"),
+            format_duration(duration)
+        ),
+        None => "Busiest day: -".to_string(),
+    }));
+    lines.push(Line::from(format!("Active days: {}", stats.active_days)));
+    lines.push(Line::from(format!(
+        "Longest streak: {} {}",
+        stats.longest_streak,
+        if stats.longest_streak == 1 {
+            "day"
+        } else {
+            "days"
+        }
+    )));
+    for (heading, rows) in [
+        ("Top Projects", &stats.top_projects),
+        ("Top Categories", &stats.top_categories),
+    ] {
+        lines.push(Line::from(""));
+        lines.push(Line::from(heading));
+        if rows.is_empty() {
+            lines.push(Line::from("(none)"));
+        }
+        for row in rows.iter().take(5) {
+            lines.push(Line::from(vec![
+                Span::styled(row.name.clone(), row.style),
+                Span::raw(format!(" | {}", format_duration(row.duration))),
+            ]));
+        }
+    }
+
+    // The bars take whatever height is left below the summary and top
+    // lists, merging neighbouring days when a month does not fit.
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let bar_rows = inner_height.saturating_sub(lines.len() + 2);
+    let buckets = fit_stats_buckets(stats.span, &stats.buckets, bar_rows);
+    if !buckets.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(
+            match (stats.span, buckets.len() < stats.buckets.len()) {
+                (StatsSpan::Year, _) => "Monthly Activity",
+                (StatsSpan::Month, false) => "Daily Activity",
+                (StatsSpan::Month, true) => "Activity",
+            },
+        ));
+    }
+
+    // Idle buckets leave the duration column blank.
+    let durations = buckets
+        .iter()
+        .map(|bucket| {
+            if bucket.total > Duration::zero() {
+                format_duration(bucket.total)
+            } else {
+                String::new()
+            }
+        })
+        .collect::<Vec<_>>();
+    let duration_width = durations
+        .iter()
+        .map(|duration| duration.len())
+        .max()
+        .unwrap_or(0);
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let max_seconds = buckets
+        .iter()
+        .map(|bucket| bucket.total.num_seconds())
+        .max()
+        .unwrap_or(0);
+    for (bucket, duration) in buckets.iter().zip(&durations) {
+        let label = format!("{} {duration:>duration_width$} ", bucket.label);
+        let bar_max_width = inner_width.saturating_sub(label.chars().count() + 1);
+        let bar_width = bar_width_for_duration(bucket.total, max_seconds, bar_max_width);
+        let mut spans = vec![Span::raw(label)];
+        spans.extend(
+            build_mixed_bar_line(0, bar_width, &bucket.project_mix, bucket.total, false).spans,
+        );
+        lines.push(Line::from(spans));
+    }
+
+    let title = match stats.span {
+        StatsSpan::Year => "Year Stats",
+        StatsSpan::Month => "Month Stats",
+    };
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(theme.border_style(false)),
     );
     frame.render_widget(panel, area);
 }
 
+/// Merges consecutive buckets so at most `rows` remain, labelling merged
+/// ones by their first and last day (`01-04`) or month (`Jan-Feb`).
+fn fit_stats_buckets(span: StatsSpan, buckets: &[StatsBucket], rows: usize) -> Vec<StatsBucket> {
+    if rows == 0 {
+        return Vec::new();
+    }
+    if buckets.len() <= rows {
+        return buckets.to_vec();
+    }
+    let format = match span {
+        StatsSpan::Month => "%d",
+        StatsSpan::Year => "%b",
+    };
+    buckets
+        .chunks(buckets.len().div_ceil(rows))
+        .map(|chunk| {
+            let first_day = chunk[0].first_day;
+            let last_day = chunk[chunk.len() - 1].last_day;
+            let mut mix = Vec::<ProjectSummaryRow>::new();
+            for row in chunk.iter().flat_map(|bucket| &bucket.project_mix) {
+                match mix.iter_mut().find(|merged| merged.name == row.name) {
+                    Some(merged) => merged.duration += row.duration,
+                    None => mix.push(row.clone()),
+                }
+            }
+            sort_summary_rows(&mut mix);
+            StatsBucket {
+                label: format!("{}-{}", first_day.format(format), last_day.format(format)),
+                first_day,
+                last_day,
+                total: chunk
+                    .iter()
+                    .fold(Duration::zero(), |acc, bucket| acc + bucket.total),
+                project_mix: mix,
+            }
+        })
+        .collect()
+}
+
 fn running_panel_height(row_count: usize) -> u16 {
-    let rows = row_count.min(4).max(1) as u16;
+    let rows = row_count.clamp(1, 4) as u16;
     rows + 2
 }
 
@@ -503,20 +931,19 @@ fn allocate_segment_widths(
         let seconds = segment.duration.num_seconds().max(0);
         let scaled = seconds as i128 * bar_width as i128;
         let width = (scaled / total_seconds as i128) as usize;
-        let remainder = (scaled % total_seconds as i128) as i128;
+        let remainder = scaled % total_seconds as i128;
         widths[index] = width;
         remainders.push((index, remainder, seconds));
         used += width;
     }
 
-    if used == 0 {
-        if let Some((index, _, _)) = remainders
+    if used == 0
+        && let Some((index, _, _)) = remainders
             .iter()
             .max_by(|left, right| left.2.cmp(&right.2).then_with(|| left.1.cmp(&right.1)))
-        {
-            widths[*index] = 1;
-            used = 1;
-        }
+    {
+        widths[*index] = 1;
+        used = 1;
     }
 
     let mut remaining = bar_width.saturating_sub(used);
@@ -553,9 +980,11 @@ fn allocate_segment_widths(
 fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
     let footer_lines = match &app.mode {
         InputMode::Normal => vec![
-            Line::from("Tab pane | arrows/hjkl navigate | Enter open/collapse (explorer) | q quit"),
             Line::from(
-                "space stop or start dialog (day+running+explorer) | d delete (day/explorer) | o new (context) | p projects | c categories | t task | e edit (day/explorer) | s session note (running/explorer) | g ledgers",
+                "Tab pane | arrows/hjkl navigate | Enter open/collapse (explorer) | ? help | q quit",
+            ),
+            Line::from(
+                "space stop or start dialog (day+running+explorer) | d delete (day/explorer) | o new (context) | p projects | c categories | t task | e edit (day/explorer) | s session note (running/explorer) | S stop all | r resume | v timeline | y stats period | f gaps | g ledgers",
             ),
             Line::from(format!(
                 "{}{}",
@@ -596,26 +1025,54 @@ fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
                 Line::from(app.status.clone()),
             ]
         }
+        InputMode::Help(help) => vec![
+            Line::from(help.title.clone()),
+            Line::from("j/k or arrows scroll | PgUp/PgDn page | Esc close"),
+        ],
+        InputMode::Detail(detail) => vec![
+            Line::from(detail.title.clone()),
+            Line::from("j/k or arrows scroll | PgUp/PgDn page | ? keys | Esc close"),
+        ],
     };
 
     let footer = Paragraph::new(footer_lines)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(app.theme.muted_style())
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .title("Shortcuts")
-                .border_style(Style::default().fg(INACTIVE_PANEL_BORDER_COLOR)),
+                .border_style(app.theme.border_style(false)),
         );
     frame.render_widget(footer, area);
 }
 
 fn render_day_row_item(
+    theme: &Theme,
     row: &DaySessionRow,
     show_project_header: bool,
     show_task_label: bool,
     selected_field: DayField,
     is_selected: bool,
 ) -> ListItem<'static> {
+    let time_line = day_row_timing_line(theme, row, show_task_label, selected_field, is_selected);
+    if show_project_header {
+        let header_line = Line::from(vec![Span::styled(
+            row.project_name.clone(),
+            row.project_style,
+        )]);
+        ListItem::new(vec![header_line, time_line])
+    } else {
+        ListItem::new(vec![time_line])
+    }
+}
+
+fn day_row_timing_line(
+    theme: &Theme,
+    row: &DaySessionRow,
+    show_task_label: bool,
+    selected_field: DayField,
+    is_selected: bool,
+) -> Line<'static> {
     let start_text = row
         .display_start
         .with_timezone(&Local)
@@ -629,28 +1086,28 @@ fn render_day_row_item(
 
     let start_style = if is_selected && selected_field == DayField::Start {
         Style::default()
-            .fg(Color::Yellow)
-            .add_modifier(Modifier::BOLD)
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
     } else if row.start_event_index.is_some() {
         Style::default()
     } else {
-        Style::default().fg(Color::DarkGray)
+        theme.muted_style()
     };
     let end_style = if is_selected && selected_field == DayField::End {
         Style::default()
-            .fg(Color::Yellow)
-            .add_modifier(Modifier::BOLD)
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
     } else if row.stop_event_index.is_some() {
         Style::default()
     } else {
-        Style::default().fg(Color::DarkGray)
+        theme.muted_style()
     };
 
     let mut timing_spans = Vec::new();
-    if row.stop_event_index.is_none() {
+    if row.stop_event_index.is_none() && !row.gap {
         timing_spans.push(Span::styled(
             "\u{f04b} ",
-            Style::default().fg(Color::LightGreen),
+            Style::default().fg(theme.running),
         ));
     }
     timing_spans.extend(vec![
@@ -668,25 +1125,13 @@ fn render_day_row_item(
     }
 
     if let Some(note) = &row.note {
-        timing_spans.push(Span::styled(
-            format!(" {note}"),
-            Style::default().fg(Color::DarkGray),
-        ));
+        timing_spans.push(Span::styled(format!(" {note}"), theme.muted_style()));
     }
 
-    let time_line = Line::from(timing_spans);
-    if show_project_header {
-        let header_line = Line::from(vec![Span::styled(
-            row.project_name.clone(),
-            row.project_style,
-        )]);
-        ListItem::new(vec![header_line, time_line])
-    } else {
-        ListItem::new(vec![time_line])
-    }
+    Line::from(timing_spans)
 }
 
-fn render_running_row_item(row: &RunningTaskRow) -> ListItem<'static> {
+fn render_running_row_item(theme: &Theme, row: &RunningTaskRow) -> ListItem<'static> {
     let started_text = row
         .started_at
         .with_timezone(&Local)
@@ -694,38 +1139,35 @@ fn render_running_row_item(row: &RunningTaskRow) -> ListItem<'static> {
         .to_string();
     let elapsed_text = format_duration(row.elapsed);
     let mut spans = vec![
-        Span::styled("\u{f04b} ", Style::default().fg(Color::LightGreen)),
+        Span::styled("\u{f04b} ", Style::default().fg(theme.running)),
         Span::raw(format!("{started_text} ")),
         Span::styled(
             format!("{elapsed_text} "),
-            Style::default().fg(Color::LightYellow),
+            Style::default().fg(theme.elapsed),
         ),
         Span::raw(row.task_title.clone()),
         Span::raw(" · "),
         Span::styled(row.project_name.clone(), row.project_style),
     ];
     if let Some(note) = &row.note {
-        spans.push(Span::styled(
-            format!(" {note}"),
-            Style::default().fg(Color::DarkGray),
-        ));
+        spans.push(Span::styled(format!(" {note}"), theme.muted_style()));
     }
     ListItem::new(Line::from(spans))
 }
 
-fn render_select_popup(frame: &mut Frame, select: &SelectState) {
+fn render_select_popup(frame: &mut Frame, theme: &Theme, select: &SelectState) -> ListHit {
     let area = centered_rect(62, 55, frame.area());
     frame.render_widget(Clear, area);
 
-    let items = if select.options.is_empty() {
-        vec![ListItem::new("(no choices)")]
-    } else {
-        select
-            .options
-            .iter()
-            .map(|option| ListItem::new(option.label.clone()).style(option.style))
-            .collect::<Vec<_>>()
-    };
+    let mut items = select
+        .options
+        .iter()
+        .map(|option| ListItem::new(option.label.clone()).style(option.style))
+        .collect::<Vec<_>>();
+    let heights = list_item_heights(&items);
+    if items.is_empty() {
+        items.push(ListItem::new("(no choices)"));
+    }
 
     let current = if select.options.is_empty() {
         0
@@ -737,10 +1179,11 @@ fn render_select_popup(frame: &mut Frame, select: &SelectState) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(format!("{} ({current}/{total})", select.title)),
+                .title(format!("{} ({current}/{total})", select.title))
+                .border_style(theme.border_style(true)),
         )
         .highlight_symbol(">> ")
-        .highlight_style(Style::default().bg(HIGHLIGHT_BACKGROUND_COLOR));
+        .highlight_style(theme.highlight_style());
 
     let mut state = ListState::default();
     if !select.options.is_empty() {
@@ -749,38 +1192,116 @@ fn render_select_popup(frame: &mut Frame, select: &SelectState) {
         ));
     }
     frame.render_stateful_widget(list, area, &mut state);
+    ListHit::panel(area, state.offset(), heights)
 }
 
-fn render_prompt_popup(frame: &mut Frame, prompt: &PromptState) {
-    let area = centered_rect(72, 60, frame.area());
+fn render_help_popup(frame: &mut Frame, theme: &Theme, help: &HelpState) -> ListHit {
+    let area = centered_rect(62, 70, frame.area());
     frame.render_widget(Clear, area);
 
-    let mut lines = Vec::new();
-    if prompt.input.is_empty() {
-        lines.push(Line::from(""));
+    let label_width = help
+        .rows
+        .iter()
+        .filter_map(|row| match row {
+            HelpRow::Binding { label, .. } => Some(label.chars().count()),
+            HelpRow::Group(_) => None,
+        })
+        .max()
+        .unwrap_or(0);
+    let mut items = help
+        .rows
+        .iter()
+        .map(|row| match row {
+            HelpRow::Group(group) => ListItem::new(Line::from(Span::styled(
+                group.to_string(),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ))),
+            HelpRow::Binding { label, description } => ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("  {label:<label_width$}  "),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(description.to_string()),
+            ])),
+        })
+        .collect::<Vec<_>>();
+    let heights = list_item_heights(&items);
+    if items.is_empty() {
+        items.push(ListItem::new("(no keys)"));
+    }
+
+    let current = if help.rows.is_empty() {
+        0
     } else {
-        for part in prompt.input.split('\n') {
-            lines.push(Line::from(part.to_string()));
-        }
-        if prompt.input.ends_with('\n') {
-            lines.push(Line::from(""));
-        }
+        help.selected.saturating_add(1)
+    };
+    let total = help.rows.len();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} ({current}/{total})", help.title))
+                .border_style(theme.border_style(true)),
+        )
+        .highlight_style(theme.highlight_style());
+
+    let mut state = ListState::default();
+    if !help.rows.is_empty() {
+        state.select(Some(help.selected.min(help.rows.len() - 1)));
+    }
+    frame.render_stateful_widget(list, area, &mut state);
+    ListHit::panel(area, state.offset(), heights)
+}
+
+fn render_detail_popup(frame: &mut Frame, theme: &Theme, detail: &DetailState) -> ListHit {
+    let area = centered_rect(76, 80, frame.area());
+    frame.render_widget(Clear, area);
+
+    let scroll = u16::try_from(detail.scroll).unwrap_or(u16::MAX);
+    let panel = Paragraph::new(detail.lines.clone())
+        .scroll((scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(detail.title.clone())
+                .border_style(theme.border_style(true)),
+        );
+    frame.render_widget(panel, area);
+    ListHit::panel(area, 0, Vec::new())
+}
+
+fn render_prompt_popup(frame: &mut Frame, theme: &Theme, prompt: &PromptState) -> ListHit {
+    let area = centered_rect(72, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    let mut lines = Vec::new();
+    if prompt.input.is_empty() {
+        lines.push(Line::from(""));
+    } else {
+        for part in prompt.input.split('\n') {
+            lines.push(Line::from(part.to_string()));
+        }
+        if prompt.input.ends_with('\n') {
+            lines.push(Line::from(""));
+        }
+    }
+
+    if let Some(preview) = prompt_time_preview(theme, prompt, Utc::now()) {
+        lines.push(preview);
     }
 
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "Enter confirm | Ctrl+J newline | Esc cancel",
-        Style::default().fg(Color::DarkGray),
+        theme.muted_style(),
     )));
 
     let block = Block::default()
         .borders(Borders::ALL)
         .title(prompt.title.clone())
-        .border_style(
-            Style::default()
-                .fg(FOCUSED_PANEL_BORDER_COLOR)
-                .add_modifier(Modifier::BOLD),
-        );
+        .border_style(theme.border_style(true));
     let inner = block.inner(area);
     let paragraph = Paragraph::new(lines).block(block);
     frame.render_widget(paragraph, area);
@@ -792,20 +1313,65 @@ fn render_prompt_popup(frame: &mut Frame, prompt: &PromptState) {
     if cursor_y < inner.y + inner.height {
         frame.set_cursor_position((cursor_x, cursor_y));
     }
+    ListHit::panel(area, 0, Vec::new())
+}
+
+/// Shows what a time prompt's current input resolves to, so relative forms
+/// like `-15m` or `yesterday 17:00` can be checked before confirming.
+fn prompt_time_preview(
+    theme: &Theme,
+    prompt: &PromptState,
+    now: DateTime<Utc>,
+) -> Option<Line<'static>> {
+    if prompt.input.trim().is_empty() {
+        return None;
+    }
+    let resolved = match &prompt.kind {
+        PromptKind::StartTaskCustomStart { flow }
+        | PromptKind::StartTaskCustomIntervalStart { flow } => {
+            parse_time_input(&prompt.input, "start time", flow.selected_day, now)
+                .map(|timestamp| describe_timestamp(timestamp, now))
+        }
+        PromptKind::StartTaskCustomIntervalEnd {
+            flow,
+            start_timestamp,
+        } => parse_end_time_input(
+            &prompt.input,
+            "end time",
+            *start_timestamp,
+            flow.selected_day,
+            now,
+        )
+        .map(|timestamp| {
+            format!(
+                "{} · {}",
+                describe_timestamp(timestamp, now),
+                format_duration(timestamp - *start_timestamp)
+            )
+        }),
+        PromptKind::SplitSessionAt { session_day, .. } => {
+            parse_time_input(&prompt.input, "split time", *session_day, now)
+                .map(|timestamp| describe_timestamp(timestamp, now))
+        }
+        _ => return None,
+    };
+    Some(match resolved {
+        Ok(text) => Line::from(Span::styled(
+            format!("= {text}"),
+            Style::default().fg(theme.accent),
+        )),
+        Err(err) => Line::from(Span::styled(err, theme.muted_style())),
+    })
 }
 
-fn render_edit_popup(frame: &mut Frame, edit: &EditState) {
+fn render_edit_popup(frame: &mut Frame, theme: &Theme, edit: &EditState) -> ListHit {
     let area = centered_rect(74, 70, frame.area());
     frame.render_widget(Clear, area);
 
     let block = Block::default()
         .borders(Borders::ALL)
         .title(edit.title.clone())
-        .border_style(
-            Style::default()
-                .fg(FOCUSED_PANEL_BORDER_COLOR)
-                .add_modifier(Modifier::BOLD),
-        );
+        .border_style(theme.border_style(true));
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
@@ -826,16 +1392,23 @@ fn render_edit_popup(frame: &mut Frame, edit: &EditState) {
             ListItem::new(line)
         })
         .collect::<Vec<_>>();
+    let heights = list_item_heights(&items);
     let list = List::new(items)
         .highlight_symbol(">> ")
-        .highlight_style(Style::default().bg(HIGHLIGHT_BACKGROUND_COLOR));
+        .highlight_style(theme.highlight_style());
     let mut state = ListState::default();
     if !edit.fields.is_empty() {
         state.select(Some(edit.selected.min(edit.fields.len().saturating_sub(1))));
     }
     frame.render_stateful_widget(list, content_layout[0], &mut state);
+    let hit = ListHit {
+        panel: area,
+        area: content_layout[0],
+        offset: state.offset(),
+        heights,
+    };
 
-    let hint_lines = build_edit_hint_lines(edit);
+    let hint_lines = build_edit_hint_lines(theme, edit);
     let hint_panel = Paragraph::new(hint_lines);
     frame.render_widget(hint_panel, content_layout[1]);
 
@@ -850,6 +1423,7 @@ fn render_edit_popup(frame: &mut Frame, edit: &EditState) {
             frame.set_cursor_position((cursor_x, cursor_y));
         }
     }
+    hit
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
@@ -873,15 +1447,23 @@ fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
 
 fn handle_normal_key(
     app: &mut App,
-    code: KeyCode,
+    key: KeyEvent,
     ledger: &mut Ledger,
-    ledger_path: &mut PathBuf,
+    ledger_path: &Path,
     snapshot: &LedgerSnapshot,
     view: &ViewModel,
 ) -> bool {
-    match code {
-        KeyCode::Char('q') => true,
-        KeyCode::Esc => {
+    let Some(action) = key_action(KeyContext::Normal, &key) else {
+        return false;
+    };
+
+    match action {
+        Action::Quit => true,
+        Action::Help => {
+            open_help(app);
+            false
+        }
+        Action::Back => {
             if !matches!(app.explorer_mode, ExplorerMode::Projects) {
                 app.explorer_mode = ExplorerMode::Projects;
                 app.explorer_index = 0;
@@ -889,17 +1471,17 @@ fn handle_normal_key(
             }
             false
         }
-        KeyCode::Tab => {
+        Action::NextPane => {
             app.focus = app.focus.next(!view.running_rows.is_empty());
             app.clear_day_edit_buffer();
             false
         }
-        KeyCode::BackTab => {
+        Action::PrevPane => {
             app.focus = app.focus.prev(!view.running_rows.is_empty());
             app.clear_day_edit_buffer();
             false
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        Action::MoveUp => {
             match app.focus {
                 FocusPane::Calendar => app.shift_selected_day(-7),
                 FocusPane::Day => app.move_day_selection(-1, view),
@@ -908,7 +1490,7 @@ fn handle_normal_key(
             }
             false
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        Action::MoveDown => {
             match app.focus {
                 FocusPane::Calendar => app.shift_selected_day(7),
                 FocusPane::Day => app.move_day_selection(1, view),
@@ -917,7 +1499,7 @@ fn handle_normal_key(
             }
             false
         }
-        KeyCode::Left | KeyCode::Char('h') => {
+        Action::MoveLeft => {
             match app.focus {
                 FocusPane::Calendar => app.shift_selected_day(-1),
                 FocusPane::Day => {
@@ -929,7 +1511,7 @@ fn handle_normal_key(
             }
             false
         }
-        KeyCode::Right | KeyCode::Char('l') => {
+        Action::MoveRight => {
             match app.focus {
                 FocusPane::Calendar => app.shift_selected_day(1),
                 FocusPane::Day => {
@@ -941,25 +1523,51 @@ fn handle_normal_key(
             }
             false
         }
-        KeyCode::Char('n') => {
+        Action::NextMonth => {
             app.shift_selected_month(1);
             false
         }
-        KeyCode::Char('N') => {
+        Action::ToggleDayLayout => {
+            app.day_layout = match app.day_layout {
+                DayLayout::List => DayLayout::Timeline,
+                DayLayout::Timeline => DayLayout::List,
+            };
+            app.status = match app.day_layout {
+                DayLayout::List => "Day view: list".to_string(),
+                DayLayout::Timeline => "Day view: timeline".to_string(),
+            };
+            false
+        }
+        Action::CycleStatsPeriod => {
+            app.stats_period = app.stats_period.next();
+            app.status = format!("Stats: {}", app.stats_period.label());
+            false
+        }
+        Action::PrevMonth => {
             app.shift_selected_month(-1);
             false
         }
-        KeyCode::Backspace => {
+        Action::DeleteBack => {
             if app.focus == FocusPane::Day {
                 app.day_edit_buffer.pop();
             }
             false
         }
-        KeyCode::Char(value) if value.is_ascii_digit() && app.focus == FocusPane::Day => {
-            handle_day_digit_input(app, value, ledger, ledger_path.as_path(), view);
+        Action::Nudge(minutes) => {
+            if app.focus == FocusPane::Day {
+                nudge_day_field(app, minutes, ledger, view);
+            }
+            false
+        }
+        Action::TypeDigit => {
+            if let KeyCode::Char(value) = key.code
+                && app.focus == FocusPane::Day
+            {
+                handle_day_digit_input(app, value, ledger, ledger_path, view);
+            }
             false
         }
-        KeyCode::Char('p') => {
+        Action::ShowProjects => {
             if !matches!(app.explorer_mode, ExplorerMode::Projects) {
                 app.explorer_mode = ExplorerMode::Projects;
                 app.explorer_index = 0;
@@ -970,14 +1578,14 @@ fn handle_normal_key(
             }
             false
         }
-        KeyCode::Char('c') => {
+        Action::ShowCategories => {
             app.explorer_mode = ExplorerMode::Categories;
             app.explorer_index = 0;
             app.focus = FocusPane::Explorer;
             app.status = "Categories".to_string();
             false
         }
-        KeyCode::Char('e') => {
+        Action::Edit => {
             match app.focus {
                 FocusPane::Day => {
                     let Some(row) = view.day_rows.get(app.day_index) else {
@@ -1003,14 +1611,14 @@ fn handle_normal_key(
             }
             false
         }
-        KeyCode::Char('t') => {
+        Action::NewTask => {
             match build_task_project_select(ledger) {
                 Ok(select) => app.mode = InputMode::Select(select),
                 Err(err) => app.status = err,
             }
             false
         }
-        KeyCode::Char('o') => {
+        Action::New => {
             match app.explorer_mode {
                 ExplorerMode::Projects => {
                     app.mode = InputMode::Prompt(PromptState::new(
@@ -1035,14 +1643,14 @@ fn handle_normal_key(
             }
             false
         }
-        KeyCode::Char('g') => {
-            match build_ledger_management_select(ledger_path.as_path()) {
+        Action::Ledgers => {
+            match build_ledger_management_select(ledger_path) {
                 Ok(select) => app.mode = InputMode::Select(select),
                 Err(err) => app.status = err,
             }
             false
         }
-        KeyCode::Char('s') => {
+        Action::SessionNote => {
             match app.focus {
                 FocusPane::Day => {
                     app.status = "Use e to edit session notes in Day view".to_string();
@@ -1078,7 +1686,101 @@ fn handle_normal_key(
             }
             false
         }
-        KeyCode::Char('d') => {
+        Action::ToggleGaps => {
+            app.show_gaps = !app.show_gaps;
+            app.focus = FocusPane::Day;
+            app.status = if app.show_gaps {
+                let (start, end) = ledger.working_hours();
+                format!(
+                    "Showing untracked gaps between {} and {}",
+                    start.format("%H:%M"),
+                    end.format("%H:%M")
+                )
+            } else {
+                "Untracked gaps hidden".to_string()
+            };
+            false
+        }
+        Action::AssignGap => {
+            open_assign_gap_select(app, ledger, view);
+            false
+        }
+        Action::WorkingHours => {
+            let (start, end) = ledger.working_hours();
+            let mut prompt =
+                PromptState::new("Working hours (HH:MM-HH:MM)", PromptKind::WorkingHours);
+            prompt.input = format!("{}-{}", start.format("%H:%M"), end.format("%H:%M"));
+            prompt.cursor = prompt.input.len();
+            app.mode = InputMode::Prompt(prompt);
+            false
+        }
+        Action::Split => {
+            if app.focus != FocusPane::Day {
+                app.status = "Focus the Day view to split a session".to_string();
+                return false;
+            }
+            let Some(row) = view.day_rows.get(app.day_index) else {
+                app.status = "No selected interval to split".to_string();
+                return false;
+            };
+            let Some(start_event_index) = row.start_event_index else {
+                app.status = "Selected interval cannot be split".to_string();
+                return false;
+            };
+
+            app.mode = InputMode::Prompt(PromptState::new(
+                format!(
+                    "Split {} {}-{} at (e.g. 1030 or -20m)",
+                    row.task_title,
+                    row.display_start.with_timezone(&Local).format("%H:%M"),
+                    row.display_stop.with_timezone(&Local).format("%H:%M")
+                ),
+                PromptKind::SplitSessionAt {
+                    start_event_index,
+                    task_id: row.task_id.clone(),
+                    session_day: row.start.with_timezone(&Local).date_naive(),
+                },
+            ));
+            false
+        }
+        Action::Merge => {
+            if app.focus != FocusPane::Day {
+                app.status = "Focus the Day view to merge sessions".to_string();
+                return false;
+            }
+            let Some(row) = view.day_rows.get(app.day_index) else {
+                app.status = "No selected interval to merge".to_string();
+                return false;
+            };
+            let Some(next_row) = view
+                .day_rows
+                .iter()
+                .skip(app.day_index + 1)
+                .find(|candidate| candidate.task_id == row.task_id)
+            else {
+                app.status = "No later session of this task on this day".to_string();
+                return false;
+            };
+            let (Some(start_event_index), Some(next_start_event_index)) =
+                (row.start_event_index, next_row.start_event_index)
+            else {
+                app.status = "Selected intervals cannot be merged".to_string();
+                return false;
+            };
+
+            app.status = match merge_sessions(
+                ledger,
+                ledger_path,
+                start_event_index,
+                next_start_event_index,
+                &row.task_title,
+            ) {
+                Ok(message) => message,
+                Err(err) => format!("error: {err}"),
+            };
+            false
+        }
+        Action::Delete => {
             if app.focus == FocusPane::Day {
                 let Some(row) = view.day_rows.get(app.day_index) else {
                     app.status = "No selected interval to delete".to_string();
@@ -1130,10 +1832,14 @@ fn handle_normal_key(
             }
             false
         }
-        KeyCode::Char(' ') => {
-            if let Some(task_id) = app.selected_task_id(view) {
+        Action::Toggle => {
+            if app.focus == FocusPane::Day
+                && view.day_rows.get(app.day_index).is_some_and(|row| row.gap)
+            {
+                open_assign_gap_select(app, ledger, view);
+            } else if let Some(task_id) = app.selected_task_id(view) {
                 let result = if snapshot.active_tasks.contains_key(&task_id) {
-                    stop_task(ledger, ledger_path.as_path(), &task_id, None)
+                    stop_task(ledger, ledger_path, &task_id, None)
                 } else {
                     app.mode = InputMode::Select(build_start_task_timing_select(
                         ledger,
@@ -1152,40 +1858,42 @@ fn handle_normal_key(
             }
             false
         }
-        KeyCode::Enter => {
+        Action::Confirm => {
             if app.focus == FocusPane::Explorer {
-                match app.selected_explorer_row_kind(view) {
-                    Some(ExplorerRowKind::Project {
-                        project_id,
-                        project_name,
-                    }) => {
-                        let project_style = style_from_project_color(
-                            ledger
-                                .project(&project_id)
-                                .and_then(|project| project.color.as_deref()),
-                        );
-                        app.explorer_mode = ExplorerMode::ProjectTasks {
-                            project_id,
-                            project_name,
-                            project_style,
-                        };
-                        app.explorer_index = 0;
-                    }
-                    Some(ExplorerRowKind::Category { key, .. }) => {
-                        if let ExplorerMode::ProjectTasks { .. } = app.explorer_mode {
-                            if app.explorer_collapsed_categories.contains(&key) {
-                                app.explorer_collapsed_categories.remove(&key);
-                            } else {
-                                app.explorer_collapsed_categories.insert(key);
-                            }
-                        }
-                    }
-                    Some(ExplorerRowKind::Task { .. }) => {
-                        app.status = "Press space to start/stop this task".to_string();
-                    }
-                    Some(ExplorerRowKind::Empty) | None => {}
-                }
+                open_explorer_row(app, ledger, view);
+            }
+            false
+        }
+        Action::StopAll => {
+            app.status = match stop_all_tasks(ledger, ledger_path) {
+                Ok(message) => message,
+                Err(err) => format!("error: {err}"),
+            };
+            false
+        }
+        Action::SwitchTo => {
+            let Some(task_id) = app.selected_task_id(view) else {
+                app.status = "Select a task to switch to".to_string();
                 return false;
+            };
+            app.status = match switch_to_task(ledger, ledger_path, &task_id) {
+                Ok(message) => message,
+                Err(err) => format!("error: {err}"),
+            };
+            false
+        }
+        Action::Resume => {
+            app.status = match resume_last_task(ledger, ledger_path) {
+                Ok(message) => message,
+                Err(err) => format!("error: {err}"),
+            };
+            false
+        }
+        Action::Details => {
+            if app.focus == FocusPane::Explorer {
+                open_selected_detail(app, ledger, view);
+            } else {
+                app.status = "Focus the Explorer to show details".to_string();
             }
             false
         }
@@ -1193,6 +1901,70 @@ fn handle_normal_key(
     }
 }
 
+fn open_explorer_row(app: &mut App, ledger: &Ledger, view: &ViewModel) {
+    match app.selected_explorer_row_kind(view) {
+        Some(ExplorerRowKind::Project {
+            project_id,
+            project_name,
+        }) => {
+            let project_style = style_from_project_color(
+                ledger
+                    .project(&project_id)
+                    .and_then(|project| project.color.as_deref()),
+            );
+            app.explorer_mode = ExplorerMode::ProjectTasks {
+                project_id,
+                project_name,
+                project_style,
+            };
+            app.explorer_index = 0;
+        }
+        Some(ExplorerRowKind::Category { key, .. }) => {
+            if let ExplorerMode::ProjectTasks { .. } = app.explorer_mode {
+                if app.explorer_collapsed_categories.contains(&key) {
+                    app.explorer_collapsed_categories.remove(&key);
+                } else {
+                    app.explorer_collapsed_categories.insert(key);
+                }
+            }
+        }
+        Some(ExplorerRowKind::Task { task_id, .. }) => {
+            open_detail(app, ledger, DetailTarget::Task(task_id));
+        }
+        Some(ExplorerRowKind::Empty) | None => {}
+    }
+}
+
+fn open_selected_detail(app: &mut App, ledger: &Ledger, view: &ViewModel) {
+    let target = match app.selected_explorer_row_kind(view) {
+        Some(ExplorerRowKind::Project { project_id, .. }) => DetailTarget::Project(project_id),
+        Some(ExplorerRowKind::Category { category_id, .. }) => DetailTarget::Category(category_id),
+        Some(ExplorerRowKind::Task { task_id, .. }) => DetailTarget::Task(task_id),
+        Some(ExplorerRowKind::Empty) | None => {
+            app.status = "Select a project, category, or task".to_string();
+            return;
+        }
+    };
+    open_detail(app, ledger, target);
+}
+
+fn open_detail(app: &mut App, ledger: &Ledger, target: DetailTarget) {
+    match build_detail(&app.theme, ledger, &target, Utc::now()) {
+        Ok(detail) => app.mode = InputMode::Detail(detail),
+        Err(err) => app.status = format!("error: {err}"),
+    }
+}
+
+fn open_assign_gap_select(app: &mut App, ledger: &Ledger, view: &ViewModel) {
+    match view.day_rows.get(app.day_index) {
+        Some(row) if row.gap => {
+            app.mode = InputMode::Select(build_assign_gap_select(ledger, row.start, row.stop));
+        }
+        Some(_) => app.status = "Select an untracked gap (press f to show gaps)".to_string(),
+        None => app.status = "No selected gap".to_string(),
+    }
+}
+
 fn open_start_note_prompt(
     app: &mut App,
     ledger: &Ledger,
@@ -1266,78 +2038,123 @@ fn handle_day_digit_input(
         }
     };
 
-    match app.day_field {
+    match set_day_field_timestamp(ledger, row, app.day_field, next_timestamp) {
+        Ok(message) => {
+            app.follow_start_event = row.start_event_index;
+            app.status = match persist(ledger_path, ledger) {
+                Ok(()) => message,
+                Err(err) => format!("error: {err}"),
+            };
+        }
+        Err(err) => app.status = err,
+    }
+}
+
+/// Moves the selected start/end by `minutes` in memory only. Held keys
+/// produce many nudges, so the ledger is written once by
+/// `flush_pending_persist` when the key is released or another key arrives.
+fn nudge_day_field(app: &mut App, minutes: i64, ledger: &mut Ledger, view: &ViewModel) {
+    app.clear_day_edit_buffer();
+    let Some(row) = view.day_rows.get(app.day_index) else {
+        app.status = "No sessions on selected day".to_string();
+        return;
+    };
+
+    let current = match app.day_field {
+        DayField::Start => row.start,
+        DayField::End => row.stop,
+    };
+    let next_timestamp = current + Duration::minutes(minutes);
+    match set_day_field_timestamp(ledger, row, app.day_field, next_timestamp) {
+        Ok(message) => {
+            app.follow_start_event = row.start_event_index;
+            app.pending_persist = true;
+            app.status = message;
+        }
+        Err(err) => app.status = err,
+    }
+}
+
+fn is_nudge_event(event: &CEvent) -> bool {
+    match event {
+        CEvent::Key(key) => matches!(key_action(KeyContext::Normal, key), Some(Action::Nudge(_))),
+        _ => false,
+    }
+}
+
+fn flush_pending_persist(app: &mut App, ledger: &Ledger, ledger_path: &Path) {
+    if !app.pending_persist {
+        return;
+    }
+    app.pending_persist = false;
+    if let Err(err) = persist(ledger_path, ledger) {
+        app.status = format!("error: {err}");
+    }
+}
+
+/// Validates and applies a new start or end timestamp for a day row. The
+/// caller is responsible for persisting the ledger.
+fn set_day_field_timestamp(
+    ledger: &mut Ledger,
+    row: &DaySessionRow,
+    field: DayField,
+    next_timestamp: DateTime<Utc>,
+) -> Result<String, String> {
+    match field {
         DayField::Start => {
             let Some(event_index) = row.start_event_index else {
-                app.status = "session start cannot be edited".to_string();
-                return;
+                return Err("session start cannot be edited".to_string());
             };
             if next_timestamp >= row.stop {
-                app.status = "start must be before end".to_string();
-                return;
+                return Err("start must be before end".to_string());
             }
-            if let Some(previous_stop) = previous_stop_for_task(ledger, &row.task_id, event_index) {
-                if next_timestamp < previous_stop {
-                    app.status = "start cannot be before previous stop for this task".to_string();
-                    return;
-                }
+            if let Some(previous_stop) = previous_stop_for_task(ledger, &row.task_id, event_index)
+                && next_timestamp < previous_stop
+            {
+                return Err("start cannot be before previous stop for this task".to_string());
             }
 
             if !matches!(
                 ledger.events.get(event_index).map(|event| &event.kind),
                 Some(EventKind::Start { .. })
             ) {
-                app.status = "unable to edit start: event mismatch".to_string();
-                return;
+                return Err("unable to edit start: event mismatch".to_string());
             }
 
             ledger.events[event_index].timestamp = next_timestamp;
-            if let Err(err) = persist(ledger_path, ledger) {
-                app.status = format!("error: {err}");
-                return;
-            }
-            app.status = format!(
+            Ok(format!(
                 "updated start to {}",
                 next_timestamp.with_timezone(&Local).format("%H:%M")
-            );
+            ))
         }
         DayField::End => {
             let Some(event_index) = row.stop_event_index else {
-                app.status = "session end cannot be edited while task is running".to_string();
-                return;
+                return Err("session end cannot be edited while task is running".to_string());
             };
             if next_timestamp <= row.start {
-                app.status = "end must be after start".to_string();
-                return;
+                return Err("end must be after start".to_string());
             }
             if next_timestamp > Utc::now() {
-                app.status = "end cannot be later than current time".to_string();
-                return;
+                return Err("end cannot be later than current time".to_string());
             }
-            if let Some(next_start) = next_start_for_task(ledger, &row.task_id, event_index) {
-                if next_timestamp > next_start {
-                    app.status = "end cannot be after following start for this task".to_string();
-                    return;
-                }
+            if let Some(next_start) = next_start_for_task(ledger, &row.task_id, event_index)
+                && next_timestamp > next_start
+            {
+                return Err("end cannot be after following start for this task".to_string());
             }
 
             if !matches!(
                 ledger.events.get(event_index).map(|event| &event.kind),
                 Some(EventKind::Stop { .. })
             ) {
-                app.status = "unable to edit end: event mismatch".to_string();
-                return;
+                return Err("unable to edit end: event mismatch".to_string());
             }
 
             ledger.events[event_index].timestamp = next_timestamp;
-            if let Err(err) = persist(ledger_path, ledger) {
-                app.status = format!("error: {err}");
-                return;
-            }
-            app.status = format!(
+            Ok(format!(
                 "updated end to {}",
                 next_timestamp.with_timezone(&Local).format("%H:%M")
-            );
+            ))
         }
     }
 }
@@ -1399,11 +2216,7 @@ fn sorted_task_events(ledger: &Ledger, task_id: &str) -> Vec<TaskEventRef> {
             _ => None,
         })
         .collect::<Vec<_>>();
-    events.sort_by(|left, right| {
-        left.timestamp
-            .cmp(&right.timestamp)
-            .then_with(|| left.index.cmp(&right.index))
-    });
+    events.sort_by_key(|entry| ledger.events[entry.index].replay_key());
     events
 }
 
@@ -1413,65 +2226,67 @@ fn handle_prompt_key(
     ledger: &mut Ledger,
     ledger_path: &mut PathBuf,
 ) -> bool {
-    match key.code {
-        KeyCode::Esc => {
+    let Some(action) = key_action(KeyContext::Prompt, &key) else {
+        if let KeyCode::Char(value) = key.code
+            && let InputMode::Prompt(prompt) = &mut app.mode
+        {
+            insert_char_at_cursor(&mut prompt.input, &mut prompt.cursor, value);
+        }
+        return false;
+    };
+
+    match action {
+        Action::Help => open_help(app),
+        Action::Cancel => {
             app.mode = InputMode::Normal;
             app.status = "Input cancelled".to_string();
         }
-        KeyCode::Backspace => {
+        Action::DeleteBack => {
             if let InputMode::Prompt(prompt) = &mut app.mode {
                 remove_char_before_cursor(&mut prompt.input, &mut prompt.cursor);
             }
         }
-        KeyCode::Delete => {
+        Action::DeleteForward => {
             if let InputMode::Prompt(prompt) = &mut app.mode {
                 remove_char_at_cursor(&mut prompt.input, &mut prompt.cursor);
             }
         }
-        KeyCode::Left => {
+        Action::MoveLeft => {
             if let InputMode::Prompt(prompt) = &mut app.mode {
                 move_cursor_left(&prompt.input, &mut prompt.cursor);
             }
         }
-        KeyCode::Right => {
+        Action::MoveRight => {
             if let InputMode::Prompt(prompt) = &mut app.mode {
                 move_cursor_right(&prompt.input, &mut prompt.cursor);
             }
         }
-        KeyCode::Up => {
+        Action::MoveUp => {
             if let InputMode::Prompt(prompt) = &mut app.mode {
                 move_cursor_vertical(&prompt.input, &mut prompt.cursor, -1);
             }
         }
-        KeyCode::Down => {
+        Action::MoveDown => {
             if let InputMode::Prompt(prompt) = &mut app.mode {
                 move_cursor_vertical(&prompt.input, &mut prompt.cursor, 1);
             }
         }
-        KeyCode::Home => {
+        Action::LineStart => {
             if let InputMode::Prompt(prompt) = &mut app.mode {
                 move_cursor_line_start(&prompt.input, &mut prompt.cursor);
             }
         }
-        KeyCode::End => {
+        Action::LineEnd => {
             if let InputMode::Prompt(prompt) = &mut app.mode {
                 move_cursor_line_end(&prompt.input, &mut prompt.cursor);
             }
         }
-        KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+        Action::InsertNewline => {
             if let InputMode::Prompt(prompt) = &mut app.mode {
                 insert_char_at_cursor(&mut prompt.input, &mut prompt.cursor, '\n');
             }
         }
-        KeyCode::Char('\n') | KeyCode::Char('\r') => {
-            submit_active_prompt(app, ledger, ledger_path);
-        }
-        KeyCode::Char(value) => {
-            if let InputMode::Prompt(prompt) = &mut app.mode {
-                insert_char_at_cursor(&mut prompt.input, &mut prompt.cursor, value);
-            }
-        }
-        KeyCode::Enter => {
+        Action::Confirm => {
             submit_active_prompt(app, ledger, ledger_path);
         }
         _ => {}
@@ -1483,7 +2298,13 @@ fn handle_prompt_key(
 fn submit_active_prompt(app: &mut App, ledger: &mut Ledger, ledger_path: &mut PathBuf) {
     let prompt = match std::mem::replace(&mut app.mode, InputMode::Normal) {
         InputMode::Prompt(prompt) => prompt,
-        InputMode::Normal | InputMode::Select(_) | InputMode::Edit(_) => return,
+        InputMode::Normal
+        | InputMode::Select(_)
+        | InputMode::Edit(_)
+        | InputMode::Help(_)
+        | InputMode::Detail(_) => {
+            return;
+        }
     };
 
     match submit_prompt(prompt.clone(), ledger, ledger_path) {
@@ -1505,142 +2326,161 @@ fn submit_active_prompt(app: &mut App, ledger: &mut Ledger, ledger_path: &mut Pa
 
 fn handle_select_key(
     app: &mut App,
-    code: KeyCode,
+    key: KeyEvent,
     ledger: &mut Ledger,
     ledger_path: &mut PathBuf,
 ) -> bool {
-    match code {
-        KeyCode::Esc => {
-            let select = match std::mem::replace(&mut app.mode, InputMode::Normal) {
-                InputMode::Select(select) => select,
-                _ => return false,
-            };
-            match select.kind {
-                SelectKind::IntervalTask { edit } => {
-                    app.mode = InputMode::Edit(edit);
-                    app.status = "Selection cancelled".to_string();
-                }
-                _ => {
-                    app.mode = InputMode::Normal;
-                    app.status = "Selection cancelled".to_string();
-                }
-            }
-        }
-        KeyCode::Up | KeyCode::Char('k') => {
+    let Some(action) = key_action(KeyContext::Select, &key) else {
+        return false;
+    };
+
+    match action {
+        Action::Help => open_help(app),
+        Action::Cancel => cancel_select(app),
+        Action::MoveUp => {
             if let InputMode::Select(select) = &mut app.mode {
                 select.move_selection(-1);
             }
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        Action::MoveDown => {
             if let InputMode::Select(select) = &mut app.mode {
                 select.move_selection(1);
             }
         }
-        KeyCode::Enter => {
-            let select = match std::mem::replace(&mut app.mode, InputMode::Normal) {
-                InputMode::Select(select) => select,
-                _ => return false,
-            };
-
-            match submit_select(select.clone(), ledger, ledger_path) {
-                Ok(SelectOutcome::NextPrompt(prompt)) => app.mode = InputMode::Prompt(prompt),
-                Ok(SelectOutcome::NextSelect(next_select)) => {
-                    app.mode = InputMode::Select(next_select)
-                }
-                Ok(SelectOutcome::NextEdit(edit)) => {
-                    app.mode = InputMode::Edit(edit);
-                }
-                Ok(SelectOutcome::Reload(message)) => {
-                    app.reset_for_ledger(ledger, Utc::now(), message);
-                }
-                Ok(SelectOutcome::Done(message)) => {
-                    app.mode = InputMode::Normal;
-                    app.status = message;
-                }
-                Err(err) => {
-                    app.mode = InputMode::Select(select);
-                    app.status = format!("error: {err}");
-                }
-            }
-        }
+        Action::Confirm => confirm_select(app, ledger, ledger_path),
         _ => {}
     }
 
     false
 }
 
-fn handle_edit_key(
-    app: &mut App,
-    key: KeyEvent,
-    ledger: &mut Ledger,
-    ledger_path: &mut PathBuf,
-) -> bool {
-    let mut cancel_edit = false;
-    let mut save_result: Option<Result<String, String>> = None;
-    let mut next_mode: Option<InputMode> = None;
-    let mut status_message: Option<String> = None;
-
-    {
-        let InputMode::Edit(edit) = &mut app.mode else {
-            return false;
-        };
-
+fn cancel_select(app: &mut App) {
+    let select = match std::mem::replace(&mut app.mode, InputMode::Normal) {
+        InputMode::Select(select) => select,
+        _ => return,
+    };
+    match select.kind {
+        SelectKind::IntervalTask { edit } => {
+            app.mode = InputMode::Edit(edit);
+            app.status = "Selection cancelled".to_string();
+        }
+        _ => {
+            app.mode = InputMode::Normal;
+            app.status = "Selection cancelled".to_string();
+        }
+    }
+}
+
+fn confirm_select(app: &mut App, ledger: &mut Ledger, ledger_path: &mut PathBuf) {
+    let select = match std::mem::replace(&mut app.mode, InputMode::Normal) {
+        InputMode::Select(select) => select,
+        _ => return,
+    };
+
+    match submit_select(select.clone(), ledger, ledger_path) {
+        Ok(SelectOutcome::NextPrompt(prompt)) => app.mode = InputMode::Prompt(prompt),
+        Ok(SelectOutcome::NextSelect(next_select)) => app.mode = InputMode::Select(next_select),
+        Ok(SelectOutcome::NextEdit(edit)) => {
+            app.mode = InputMode::Edit(edit);
+        }
+        Ok(SelectOutcome::Reload(message)) => {
+            app.reset_for_ledger(ledger, Utc::now(), message);
+        }
+        Ok(SelectOutcome::Done(message)) => {
+            app.mode = InputMode::Normal;
+            app.status = message;
+        }
+        Err(err) => {
+            app.mode = InputMode::Select(select);
+            app.status = format!("error: {err}");
+        }
+    }
+}
+
+fn handle_edit_key(app: &mut App, key: KeyEvent, ledger: &mut Ledger, ledger_path: &Path) -> bool {
+    let mut cancel_edit = false;
+    let mut save_result: Option<Result<String, String>> = None;
+    let mut next_mode: Option<InputMode> = None;
+    let mut status_message: Option<String> = None;
+
+    let context = match &app.mode {
+        InputMode::Edit(edit) if edit.editing => KeyContext::EditField,
+        InputMode::Edit(_) => KeyContext::EditList,
+        _ => return false,
+    };
+    let action = key_action(context, &key);
+    if action == Some(Action::Help) {
+        open_help(app);
+        return false;
+    }
+
+    {
+        let InputMode::Edit(edit) = &mut app.mode else {
+            return false;
+        };
+
         if edit.editing {
-            match key.code {
-                KeyCode::Esc => {
+            let Some(action) = action else {
+                if let KeyCode::Char(value) = key.code {
+                    insert_char_at_cursor(&mut edit.input, &mut edit.cursor, value);
+                }
+                return false;
+            };
+            match action {
+                Action::Cancel => {
                     edit.editing = false;
                     edit.input.clear();
                     edit.cursor = 0;
                 }
-                KeyCode::Backspace => {
+                Action::DeleteBack => {
                     remove_char_before_cursor(&mut edit.input, &mut edit.cursor);
                 }
-                KeyCode::Delete => {
+                Action::DeleteForward => {
                     remove_char_at_cursor(&mut edit.input, &mut edit.cursor);
                 }
-                KeyCode::Left => {
+                Action::MoveLeft => {
                     move_cursor_left(&edit.input, &mut edit.cursor);
                 }
-                KeyCode::Right => {
+                Action::MoveRight => {
                     move_cursor_right(&edit.input, &mut edit.cursor);
                 }
-                KeyCode::Up => {
+                Action::MoveUp => {
                     move_cursor_vertical(&edit.input, &mut edit.cursor, -1);
                 }
-                KeyCode::Down => {
+                Action::MoveDown => {
                     move_cursor_vertical(&edit.input, &mut edit.cursor, 1);
                 }
-                KeyCode::Home => {
+                Action::LineStart => {
                     move_cursor_line_start(&edit.input, &mut edit.cursor);
                 }
-                KeyCode::End => {
+                Action::LineEnd => {
                     move_cursor_line_end(&edit.input, &mut edit.cursor);
                 }
-                KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    if edit_selected_field_multiline(edit) {
-                        insert_char_at_cursor(&mut edit.input, &mut edit.cursor, '\n');
-                    }
-                }
-                KeyCode::Enter | KeyCode::Char('\n') | KeyCode::Char('\r') => {
-                    commit_edit_field_input(edit);
+                Action::InsertNewline if edit_selected_field_multiline(edit) => {
+                    insert_char_at_cursor(&mut edit.input, &mut edit.cursor, '\n');
                 }
-                KeyCode::Char(value) => {
-                    insert_char_at_cursor(&mut edit.input, &mut edit.cursor, value);
+                Action::Confirm => {
+                    if let Err(err) = commit_edit_field_input(edit) {
+                        status_message = Some(format!("error: {err}"));
+                    }
                 }
                 _ => {}
             }
         } else {
-            match key.code {
-                KeyCode::Esc => {
+            let Some(action) = action else {
+                return false;
+            };
+            match action {
+                Action::Cancel => {
                     cancel_edit = true;
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
+                Action::MoveUp => {
                     edit.move_selection(-1);
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
+                Action::MoveDown => {
                     edit.move_selection(1);
                 }
-                KeyCode::Left | KeyCode::Char('h') => {
+                Action::MoveLeft => {
                     if matches!(edit.entity, EditEntity::Interval { .. })
                         && matches!(
                             edit.fields.get(edit.selected).map(|field| field.id),
@@ -1663,7 +2503,7 @@ fn handle_edit_key(
                         }
                     }
                 }
-                KeyCode::Right | KeyCode::Char('l') => {
+                Action::MoveRight => {
                     if matches!(edit.entity, EditEntity::Interval { .. })
                         && matches!(
                             edit.fields.get(edit.selected).map(|field| field.id),
@@ -1686,7 +2526,7 @@ fn handle_edit_key(
                         }
                     }
                 }
-                KeyCode::Enter => {
+                Action::Confirm => {
                     if matches!(edit.entity, EditEntity::Interval { .. })
                         && matches!(
                             edit.fields.get(edit.selected).map(|field| field.id),
@@ -1709,7 +2549,7 @@ fn handle_edit_key(
                         }
                     }
                 }
-                KeyCode::Char(' ') => {
+                Action::Toggle => {
                     if matches!(edit.entity, EditEntity::Interval { .. })
                         && matches!(
                             edit.fields.get(edit.selected).map(|field| field.id),
@@ -1732,8 +2572,8 @@ fn handle_edit_key(
                         }
                     }
                 }
-                KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    save_result = Some(submit_edit(edit, ledger, ledger_path.as_path()));
+                Action::Save => {
+                    save_result = Some(submit_edit(edit, ledger, ledger_path));
                 }
                 _ => {}
             }
@@ -1778,6 +2618,15 @@ fn submit_prompt(
             let name = required_text(&prompt.input, "project name")?;
             Ok(PromptOutcome::Select(build_project_color_select(name)))
         }
+        PromptKind::AddProjectColor { name } => {
+            let color = parse_project_color_input(&prompt.input)?;
+            let created_name = name.clone();
+            ledger.add_project(name, color);
+            persist(ledger_path.as_path(), ledger)?;
+            Ok(PromptOutcome::Done(format!(
+                "created project: {created_name}"
+            )))
+        }
         PromptKind::AddCategoryName => {
             let name = required_text(&prompt.input, "category name")?;
             Ok(PromptOutcome::NextPrompt(PromptState::new(
@@ -1816,7 +2665,7 @@ fn submit_prompt(
         }
         PromptKind::StartTaskCustomStart { flow } => {
             let timestamp =
-                parse_time_input_on_day(&prompt.input, flow.selected_day, "start time")?;
+                parse_time_input(&prompt.input, "start time", flow.selected_day, Utc::now())?;
             validate_start_timestamp(timestamp, Utc::now())?;
             start_task_at(
                 ledger,
@@ -1829,11 +2678,11 @@ fn submit_prompt(
         }
         PromptKind::StartTaskCustomIntervalStart { flow } => {
             let timestamp =
-                parse_time_input_on_day(&prompt.input, flow.selected_day, "start time")?;
+                parse_time_input(&prompt.input, "start time", flow.selected_day, Utc::now())?;
             validate_start_timestamp(timestamp, Utc::now())?;
             Ok(PromptOutcome::NextPrompt(PromptState::new(
                 format!(
-                    "End time on {} (HHMM or HH:MM)",
+                    "End time on {} (e.g. 1730, +45m or 1h30)",
                     flow.selected_day.format("%Y-%m-%d")
                 ),
                 PromptKind::StartTaskCustomIntervalEnd {
@@ -1846,8 +2695,13 @@ fn submit_prompt(
             flow,
             start_timestamp,
         } => {
-            let end_timestamp =
-                parse_time_input_on_day(&prompt.input, flow.selected_day, "end time")?;
+            let end_timestamp = parse_end_time_input(
+                &prompt.input,
+                "end time",
+                start_timestamp,
+                flow.selected_day,
+                Utc::now(),
+            )?;
             validate_interval_bounds(start_timestamp, end_timestamp, Utc::now())?;
             log_task_interval(
                 ledger,
@@ -1882,6 +2736,32 @@ fn submit_prompt(
                 "updated session note: {task_title}"
             )))
         }
+        PromptKind::SplitSessionAt {
+            start_event_index,
+            task_id,
+            session_day,
+        } => {
+            let at = parse_time_input(&prompt.input, "split time", session_day, Utc::now())?;
+            if at > Utc::now() {
+                return Err("split time cannot be later than current time".to_string());
+            }
+            Ok(PromptOutcome::Select(build_split_task_select(
+                ledger,
+                start_event_index,
+                &task_id,
+                at,
+            )))
+        }
+        PromptKind::WorkingHours => {
+            let (start, end) = parse_working_hours(&prompt.input)?;
+            ledger.set_working_hours(start, end)?;
+            persist(ledger_path.as_path(), ledger)?;
+            Ok(PromptOutcome::Done(format!(
+                "working hours set to {}-{}",
+                start.format("%H:%M"),
+                end.format("%H:%M")
+            )))
+        }
         PromptKind::LedgerPath => {
             let selected_path = ledger_path_from_input(&prompt.input)?;
             open_or_create_ledger(ledger, ledger_path, selected_path).map(PromptOutcome::Reload)
@@ -1900,6 +2780,14 @@ fn submit_select(
         .ok_or_else(|| "no option selected".to_string())?;
 
     match select.kind {
+        SelectKind::ProjectColor { name }
+            if selected_value.as_deref() == Some(CUSTOM_COLOR_OPTION) =>
+        {
+            Ok(SelectOutcome::NextPrompt(PromptState::new(
+                "Project color (#rrggbb, 0-255 or a color name)",
+                PromptKind::AddProjectColor { name },
+            )))
+        }
         SelectKind::ProjectColor { name } => {
             let created_name = name.clone();
             ledger.add_project(name, selected_value);
@@ -1930,14 +2818,14 @@ fn submit_select(
                     .map(SelectOutcome::Done),
                 "start_time" => Ok(SelectOutcome::NextPrompt(PromptState::new(
                     format!(
-                        "Start time on {} (HHMM or HH:MM)",
+                        "Start time on {} (e.g. 0930, -15m or yesterday 17:00)",
                         flow.selected_day.format("%Y-%m-%d")
                     ),
                     PromptKind::StartTaskCustomStart { flow },
                 ))),
                 "start_end_time" => Ok(SelectOutcome::NextPrompt(PromptState::new(
                     format!(
-                        "Start time on {} (HHMM or HH:MM)",
+                        "Start time on {} (e.g. 0930, -15m or yesterday 17:00)",
                         flow.selected_day.format("%Y-%m-%d")
                     ),
                     PromptKind::StartTaskCustomIntervalStart { flow },
@@ -1959,6 +2847,9 @@ fn submit_select(
                 "forget" => Ok(SelectOutcome::NextSelect(build_ledger_forget_select(
                     current_path.as_path(),
                 )?)),
+                "copy_entities" => Ok(SelectOutcome::NextSelect(build_copy_source_select(
+                    current_path.as_path(),
+                )?)),
                 _ => Err(format!("unknown ledger action: {action}")),
             }
         }
@@ -1975,16 +2866,26 @@ fn submit_select(
             forget_recent_ledger(selected_path.as_path(), ledger_path.as_path())
                 .map(SelectOutcome::Done)
         }
+        SelectKind::CopyEntitiesSource => {
+            let source_path = selected_value
+                .map(PathBuf::from)
+                .ok_or_else(|| "selected ledger path is missing".to_string())?;
+            build_copy_entities_select(source_path).map(SelectOutcome::NextSelect)
+        }
+        SelectKind::CopyEntities { source_path } => {
+            let choice = selected_value.ok_or_else(|| "selected entity is missing".to_string())?;
+            copy_entities(ledger, ledger_path.as_path(), &source_path, &choice)
+                .map(SelectOutcome::Done)
+        }
         SelectKind::IntervalTask { mut edit } => {
             let task_id = selected_value.ok_or_else(|| "selected task is missing".to_string())?;
             if let Some(field) = edit
                 .fields
                 .iter_mut()
                 .find(|field| field.id == EditFieldId::Task)
+                && let EditFieldKind::Choice { value, .. } = &mut field.kind
             {
-                if let EditFieldKind::Choice { value, .. } = &mut field.kind {
-                    *value = Some(task_id.clone());
-                }
+                *value = Some(task_id.clone());
             }
             let label = ledger
                 .task(&task_id)
@@ -2014,6 +2915,25 @@ fn submit_select(
                 Ok(SelectOutcome::Done("Delete cancelled".to_string()))
             }
         }
+        SelectKind::SplitSessionTask {
+            start_event_index,
+            at,
+        } => {
+            let task_id = selected_value.ok_or_else(|| "selected task is missing".to_string())?;
+            split_session(
+                ledger,
+                ledger_path.as_path(),
+                start_event_index,
+                at,
+                &task_id,
+            )
+            .map(SelectOutcome::Done)
+        }
+        SelectKind::AssignGap { start, stop } => {
+            let task_id = selected_value.ok_or_else(|| "selected task is missing".to_string())?;
+            log_task_interval(ledger, ledger_path.as_path(), &task_id, start, stop, None)
+                .map(SelectOutcome::Done)
+        }
         SelectKind::DeleteTaskConfirm {
             task_id,
             task_title,
@@ -2111,10 +3031,10 @@ fn submit_edit(
             if ledger.project(&project_id).is_none() {
                 return Err(format!("project not found: {project_id}"));
             }
-            if let Some(category_id) = &category_id {
-                if ledger.category(category_id).is_none() {
-                    return Err(format!("category not found: {category_id}"));
-                }
+            if let Some(category_id) = &category_id
+                && ledger.category(category_id).is_none()
+            {
+                return Err(format!("category not found: {category_id}"));
             }
 
             let task = ledger
@@ -2212,6 +3132,11 @@ fn build_project_color_select(name: String) -> SelectState {
             color_block_style(color),
         ));
     }
+    options.push(SelectOption::new(
+        "Custom: #rrggbb or 0-255",
+        Some(CUSTOM_COLOR_OPTION.to_string()),
+        Style::default(),
+    ));
 
     SelectState::new(
         "Select project color",
@@ -2326,6 +3251,11 @@ fn build_ledger_management_select(current_path: &Path) -> Result<SelectState, St
             Some("forget".to_string()),
             Style::default(),
         ));
+        options.push(SelectOption::new(
+            "Copy projects and tasks from a recent ledger",
+            Some("copy_entities".to_string()),
+            Style::default(),
+        ));
     }
 
     Ok(SelectState::new(
@@ -2375,6 +3305,80 @@ fn build_ledger_forget_select(current_path: &Path) -> Result<SelectState, String
     ))
 }
 
+fn build_copy_source_select(current_path: &Path) -> Result<SelectState, String> {
+    let paths = recent_ledgers(100)
+        .map_err(|err| format!("failed to load recent ledgers: {err}"))?
+        .into_iter()
+        .filter(|path| path != current_path)
+        .collect::<Vec<_>>();
+    if paths.is_empty() {
+        return Err("no other recent ledgers to copy from".to_string());
+    }
+
+    Ok(SelectState::new(
+        "Copy from ledger",
+        SelectKind::CopyEntitiesSource,
+        build_recent_ledger_options(paths, ""),
+    ))
+}
+
+/// Offers everything active at once, or one project (with its active tasks)
+/// or category at a time.
+fn build_copy_entities_select(source_path: PathBuf) -> Result<SelectState, String> {
+    let source = load_ledger(&source_path).map_err(|err| err.to_string())?;
+    let mut options = vec![SelectOption::new(
+        "All active projects, categories and tasks",
+        Some("all".to_string()),
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+
+    let mut projects = source
+        .header
+        .projects
+        .iter()
+        .filter(|project| !project.archived)
+        .collect::<Vec<_>>();
+    projects.sort_by(|left, right| left.name.cmp(&right.name));
+    options.extend(projects.into_iter().map(|project| {
+        let tasks = source
+            .header
+            .tasks
+            .iter()
+            .filter(|task| task.project_id == project.id && !task.archived)
+            .count();
+        SelectOption::new(
+            format!(
+                "Project: {} ({tasks} {})",
+                project.name,
+                if tasks == 1 { "task" } else { "tasks" }
+            ),
+            Some(format!("project:{}", project.id)),
+            style_from_project_color(project.color.as_deref()),
+        )
+    }));
+
+    let mut categories = source
+        .header
+        .categories
+        .iter()
+        .filter(|category| !category.archived)
+        .collect::<Vec<_>>();
+    categories.sort_by(|left, right| left.name.cmp(&right.name));
+    options.extend(categories.into_iter().map(|category| {
+        SelectOption::new(
+            format!("Category: {}", category.name),
+            Some(format!("category:{}", category.id)),
+            Style::default(),
+        )
+    }));
+
+    Ok(SelectState::new(
+        format!("Copy from {}", source_path.display()),
+        SelectKind::CopyEntities { source_path },
+        options,
+    ))
+}
+
 fn build_recent_ledger_options(paths: Vec<PathBuf>, current_value: &str) -> Vec<SelectOption> {
     paths
         .into_iter()
@@ -2437,6 +3441,77 @@ fn build_delete_interval_select(row: &DaySessionRow, start_event_index: usize) -
     select
 }
 
+fn build_split_task_select(
+    ledger: &Ledger,
+    start_event_index: usize,
+    task_id: &str,
+    at: DateTime<Utc>,
+) -> SelectState {
+    let mut options = vec![SelectOption::new(
+        format!("Same task: {}", task_label(ledger, task_id)),
+        Some(task_id.to_string()),
+        task_style_for_id(ledger, task_id),
+    )];
+    options.extend(
+        build_all_task_select_options(ledger)
+            .into_iter()
+            .filter(|option| option.value.as_deref() != Some(task_id)),
+    );
+
+    SelectState::new(
+        format!(
+            "Book the part after {} to",
+            at.with_timezone(&Local).format("%H:%M")
+        ),
+        SelectKind::SplitSessionTask {
+            start_event_index,
+            at,
+        },
+        options,
+    )
+}
+
+fn build_assign_gap_select(
+    ledger: &Ledger,
+    start: DateTime<Utc>,
+    stop: DateTime<Utc>,
+) -> SelectState {
+    SelectState::new(
+        format!(
+            "Log {}-{} to",
+            start.with_timezone(&Local).format("%H:%M"),
+            stop.with_timezone(&Local).format("%H:%M")
+        ),
+        SelectKind::AssignGap { start, stop },
+        build_all_task_select_options(ledger),
+    )
+}
+
+/// Every active task, labelled `Project · Task` and sorted by project.
+fn build_all_task_select_options(ledger: &Ledger) -> Vec<SelectOption> {
+    let mut tasks = ledger
+        .header
+        .tasks
+        .iter()
+        .filter(|task| !task.archived)
+        .map(|task| {
+            let (_, project_name, task_title) = task_project_and_title(ledger, &task.id);
+            (project_name, task_title, task.id.as_str())
+        })
+        .collect::<Vec<_>>();
+    tasks.sort();
+    tasks
+        .into_iter()
+        .map(|(project_name, task_title, task_id)| {
+            SelectOption::new(
+                format!("{project_name} · {task_title}"),
+                Some(task_id.to_string()),
+                task_style_for_id(ledger, task_id),
+            )
+        })
+        .collect()
+}
+
 fn build_delete_task_select(task_id: String, task_title: String) -> SelectState {
     let title = format!("Delete task? {task_title}");
     let options = vec![
@@ -2696,14 +3771,13 @@ fn build_interval_task_select(ledger: &Ledger, edit: &EditState) -> Result<Selec
         SelectKind::IntervalTask { edit: edit.clone() },
         options,
     );
-    if let Ok(Some(task_id)) = edit_field_choice_value(edit, EditFieldId::Task) {
-        if let Some(index) = select
+    if let Ok(Some(task_id)) = edit_field_choice_value(edit, EditFieldId::Task)
+        && let Some(index) = select
             .options
             .iter()
             .position(|option| option.value.as_deref() == Some(task_id.as_str()))
-        {
-            select.selected = index;
-        }
+    {
+        select.selected = index;
     }
     Ok(select)
 }
@@ -2827,85 +3901,49 @@ fn build_view(
     let sessions = collect_sessions(ledger, now);
     let daily_task_totals = build_daily_task_totals(ledger, &sessions);
     let calendar_active_days = daily_task_totals.keys().copied().collect::<HashSet<_>>();
-    let day_rows = build_day_rows(app.selected_day, ledger, &sessions);
+    let day_bounds = ledger.day_bounds_utc(app.selected_day);
+    let day_rows = build_day_rows(app.selected_day, ledger, &sessions, app.show_gaps, now);
     let running_rows = build_running_rows(ledger, &sessions, now);
     let week_stats = build_week_stats(app.selected_day, ledger, &daily_task_totals);
+    let period_stats = app
+        .stats_period
+        .span()
+        .map(|span| build_period_stats(span, app.selected_day, ledger, &daily_task_totals));
     let explorer_rows = build_explorer_rows(app, ledger, snapshot, &week_stats);
 
     ViewModel {
         calendar_active_days,
+        day_bounds,
         day_rows,
         running_rows,
         week_stats,
+        period_stats,
         explorer_rows,
+        now,
     }
 }
 
 fn collect_sessions(ledger: &Ledger, now: DateTime<Utc>) -> Vec<SessionRecord> {
-    let mut indexed_events = ledger
-        .events
-        .iter()
-        .enumerate()
-        .collect::<Vec<(usize, &TimeEvent)>>();
-    indexed_events.sort_by(|left, right| {
-        left.1
-            .timestamp
-            .cmp(&right.1.timestamp)
-            .then_with(|| left.0.cmp(&right.0))
-    });
-
-    let mut active: HashMap<String, ActiveSessionRef> = HashMap::new();
-    let mut sessions = Vec::new();
-
-    for (index, event) in indexed_events {
-        match &event.kind {
-            EventKind::Start { task_id, note } => {
-                active.insert(
-                    task_id.clone(),
-                    ActiveSessionRef {
-                        started_at: event.timestamp,
-                        note: note.clone(),
-                        start_event_index: index,
-                    },
-                );
-            }
-            EventKind::Stop { task_id, .. } => {
-                if let Some(active_session) = active.remove(task_id) {
-                    if event.timestamp > active_session.started_at {
-                        sessions.push(SessionRecord {
-                            task_id: task_id.clone(),
-                            start: active_session.started_at,
-                            stop: event.timestamp,
-                            note: active_session.note,
-                            start_event_index: Some(active_session.start_event_index),
-                            stop_event_index: Some(index),
-                        });
-                    }
-                }
-            }
-        }
-    }
-
-    for (task_id, active_session) in active {
-        if now > active_session.started_at {
-            sessions.push(SessionRecord {
-                task_id,
-                start: active_session.started_at,
-                stop: now,
-                note: active_session.note,
-                start_event_index: Some(active_session.start_event_index),
-                stop_event_index: None,
-            });
-        }
-    }
-
-    sessions
+    ledger
+        .sessions(now)
+        .into_iter()
+        .map(|session| SessionRecord {
+            task_id: session.task_id,
+            start: session.start,
+            stop: session.stop,
+            note: session.note,
+            start_event_index: Some(session.start_event_index),
+            stop_event_index: session.stop_event_index,
+        })
+        .collect()
 }
 
 fn build_day_rows(
     selected_day: NaiveDate,
     ledger: &Ledger,
     sessions: &[SessionRecord],
+    show_gaps: bool,
+    now: DateTime<Utc>,
 ) -> Vec<DaySessionRow> {
     let (day_start, day_end) = ledger.day_bounds_utc(selected_day);
 
@@ -2947,9 +3985,13 @@ fn build_day_rows(
                 display_stop,
                 start_event_index: session.start_event_index,
                 stop_event_index: session.stop_event_index,
+                gap: false,
             })
         })
         .collect::<Vec<_>>();
+    if show_gaps {
+        rows.extend(build_gap_rows(selected_day, ledger, sessions, now));
+    }
 
     rows.sort_by(|left, right| {
         left.display_start
@@ -2961,6 +4003,68 @@ fn build_day_rows(
     rows
 }
 
+/// Untracked stretches of the ledger's working hours on `day`, as pseudo-rows
+/// for the day view. Time after `now` is never reported as a gap.
+fn build_gap_rows(
+    day: NaiveDate,
+    ledger: &Ledger,
+    sessions: &[SessionRecord],
+    now: DateTime<Utc>,
+) -> Vec<DaySessionRow> {
+    let (work_start, work_end) = ledger.working_hours();
+    let end_day = if work_end <= work_start {
+        day.succ_opt().unwrap_or(day)
+    } else {
+        day
+    };
+    let (Ok(window_start), Ok(window_end)) = (
+        local_clock_on_date_to_utc(day, work_start.hour(), work_start.minute()),
+        local_clock_on_date_to_utc(end_day, work_end.hour(), work_end.minute()),
+    ) else {
+        return Vec::new();
+    };
+    let window_end = window_end.min(now);
+
+    let mut covered = sessions
+        .iter()
+        .filter(|session| session.start < window_end && session.stop > window_start)
+        .map(|session| {
+            (
+                session.start.max(window_start),
+                session.stop.min(window_end),
+            )
+        })
+        .collect::<Vec<_>>();
+    covered.sort();
+
+    let mut gaps = Vec::new();
+    let mut cursor = window_start;
+    for (start, stop) in covered.into_iter().chain([(window_end, window_end)]) {
+        if start - cursor >= Duration::minutes(MIN_GAP_MINUTES) {
+            gaps.push((cursor, start));
+        }
+        cursor = cursor.max(stop);
+    }
+
+    gaps.into_iter()
+        .map(|(start, stop)| DaySessionRow {
+            task_id: String::new(),
+            project_id: String::new(),
+            project_name: "Untracked".to_string(),
+            task_title: "press a to assign".to_string(),
+            project_style: Style::default().add_modifier(Modifier::DIM | Modifier::ITALIC),
+            note: None,
+            start,
+            stop,
+            display_start: start,
+            display_stop: stop,
+            start_event_index: None,
+            stop_event_index: None,
+            gap: true,
+        })
+        .collect()
+}
+
 fn build_running_rows(
     ledger: &Ledger,
     sessions: &[SessionRecord],
@@ -3036,75 +4140,562 @@ fn build_daily_task_totals(
     daily_task_totals
 }
 
-fn build_week_stats(
-    selected_day: NaiveDate,
-    ledger: &Ledger,
-    daily_task_totals: &BTreeMap<NaiveDate, HashMap<String, Duration>>,
-) -> WeekStatsView {
-    let week_start = start_of_week(selected_day);
-    let mut daily = Vec::new();
-    let mut total = Duration::zero();
-    let mut max_day = Duration::zero();
-    let mut active_days = 0usize;
-    let mut project_totals: HashMap<String, Duration> = HashMap::new();
-    let mut daily_project_mix = Vec::new();
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DetailTarget {
+    Task(String),
+    Project(String),
+    /// `None` stands for the uncategorized tasks.
+    Category(Option<String>),
+}
 
-    for offset in 0..7 {
-        let day = week_start + Duration::days(offset);
-        let durations = daily_task_totals.get(&day).cloned().unwrap_or_default();
-        let day_total = durations
-            .values()
-            .fold(Duration::zero(), |acc, value| acc + *value);
+struct DetailStats {
+    sessions: usize,
+    total: Duration,
+    longest: Duration,
+    first: Option<DateTime<Utc>>,
+    last: Option<DateTime<Utc>>,
+    active_days: usize,
+    busiest_day: Option<(NaiveDate, Duration)>,
+    /// Every ledger day of the sparkline window, including idle ones.
+    daily: Vec<(NaiveDate, Duration)>,
+    /// Tracked time per task, longest first.
+    task_totals: Vec<(String, Duration)>,
+}
 
-        if day_total > Duration::zero() {
-            active_days += 1;
-        }
-        if day_total > max_day {
-            max_day = day_total;
+impl DetailStats {
+    fn average(&self) -> Duration {
+        if self.sessions == 0 {
+            Duration::zero()
+        } else {
+            self.total / self.sessions as i32
         }
-        total += day_total;
-        daily.push((day, day_total));
+    }
+}
 
-        let mut day_project_totals: HashMap<String, Duration> = HashMap::new();
-        for (task_id, duration) in durations {
-            if let Some(task) = ledger.task(&task_id) {
-                *project_totals
-                    .entry(task.project_id.clone())
-                    .or_insert_with(Duration::zero) += duration;
-                *day_project_totals
+fn build_detail_stats(ledger: &Ledger, sessions: &[SessionRecord]) -> DetailStats {
+    let mut total = Duration::zero();
+    let mut longest = Duration::zero();
+    let mut task_totals = HashMap::<String, Duration>::new();
+    for session in sessions {
+        let length = session.stop - session.start;
+        total += length;
+        longest = longest.max(length);
+        *task_totals
+            .entry(session.task_id.clone())
+            .or_insert_with(Duration::zero) += length;
+    }
+    let mut task_totals = task_totals.into_iter().collect::<Vec<_>>();
+    task_totals.sort_by(|left, right| right.1.cmp(&left.1).then_with(|| left.0.cmp(&right.0)));
+
+    let day_totals = build_daily_task_totals(ledger, sessions)
+        .into_iter()
+        .map(|(day, totals)| {
+            (
+                day,
+                totals.into_values().fold(Duration::zero(), |a, b| a + b),
+            )
+        })
+        .filter(|(_, duration)| *duration > Duration::zero())
+        .collect::<BTreeMap<_, _>>();
+    let busiest_day = day_totals
+        .iter()
+        .max_by(|left, right| left.1.cmp(right.1).then_with(|| right.0.cmp(left.0)))
+        .map(|(day, duration)| (*day, *duration));
+
+    let mut daily = Vec::new();
+    if let (Some(first_day), Some(last_day)) = (
+        day_totals.keys().next().copied(),
+        day_totals.keys().next_back().copied(),
+    ) {
+        let mut day = first_day.max(last_day - Duration::days(DETAIL_SPARKLINE_DAYS - 1));
+        while day <= last_day {
+            let duration = day_totals.get(&day).copied().unwrap_or_else(Duration::zero);
+            daily.push((day, duration));
+            day = day.succ_opt().expect("next day should exist");
+        }
+    }
+
+    DetailStats {
+        sessions: sessions.len(),
+        total,
+        longest,
+        first: sessions.iter().map(|session| session.start).min(),
+        last: sessions.iter().map(|session| session.stop).max(),
+        active_days: day_totals.len(),
+        busiest_day,
+        daily,
+        task_totals,
+    }
+}
+
+/// One block character per day, scaled to the busiest day; idle days stay blank.
+fn sparkline_text(daily: &[(NaiveDate, Duration)]) -> String {
+    let max_seconds = daily
+        .iter()
+        .map(|(_, duration)| duration.num_seconds())
+        .max()
+        .unwrap_or(0);
+    daily
+        .iter()
+        .map(|(_, duration)| {
+            let seconds = duration.num_seconds();
+            if seconds <= 0 || max_seconds <= 0 {
+                return ' ';
+            }
+            let levels = SPARKLINE_LEVELS.len() as i64;
+            let level = (seconds * levels + max_seconds - 1) / max_seconds;
+            SPARKLINE_LEVELS[(level.clamp(1, levels) - 1) as usize]
+        })
+        .collect()
+}
+
+fn build_detail(
+    theme: &Theme,
+    ledger: &Ledger,
+    target: &DetailTarget,
+    now: DateTime<Utc>,
+) -> Result<DetailState, String> {
+    let heading = |text: &str| {
+        Line::from(Span::styled(
+            text.to_string(),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+    let mut lines = Vec::new();
+
+    let (title, task_ids) = match target {
+        DetailTarget::Task(task_id) => {
+            let task = ledger
+                .task(task_id)
+                .ok_or_else(|| format!("task not found: {task_id}"))?;
+            let project = ledger.project(&task.project_id);
+            let project_name = project
+                .map(|project| project.name.clone())
+                .unwrap_or_else(|| "Unknown project".to_string());
+            lines.push(Line::from(vec![
+                Span::raw("Project: "),
+                Span::styled(
+                    project_name.clone(),
+                    style_from_project_color(project.and_then(|project| project.color.as_deref())),
+                ),
+            ]));
+            lines.push(Line::from(format!(
+                "Category: {}",
+                task.category_id
+                    .as_deref()
+                    .map(|category_id| category_label(ledger, category_id))
+                    .unwrap_or_else(|| "(none)".to_string())
+            )));
+            if task.archived {
+                lines.push(Line::from("Archived"));
+            }
+            lines.push(Line::from(""));
+            lines.push(heading("Description"));
+            push_text_lines(&mut lines, &task.description, "(no description)");
+            (
+                format!("Task: {project_name} · {}", task.short_description()),
+                HashSet::from([task.id.clone()]),
+            )
+        }
+        DetailTarget::Project(project_id) => {
+            let project = ledger
+                .project(project_id)
+                .ok_or_else(|| format!("project not found: {project_id}"))?;
+            let tasks = ledger
+                .header
+                .tasks
+                .iter()
+                .filter(|task| task.project_id == project.id)
+                .collect::<Vec<_>>();
+            let archived = tasks.iter().filter(|task| task.archived).count();
+            lines.push(Line::from(format!(
+                "Tasks: {} open, {archived} archived",
+                tasks.len() - archived
+            )));
+            if let Some(color) = &project.color {
+                lines.push(Line::from(vec![
+                    Span::raw("Color: "),
+                    Span::styled(color.clone(), color_block_style(color)),
+                ]));
+            }
+            if project.archived {
+                lines.push(Line::from("Archived"));
+            }
+            (
+                format!("Project: {}", project.name),
+                tasks.iter().map(|task| task.id.clone()).collect(),
+            )
+        }
+        DetailTarget::Category(category_id) => {
+            let (title, description) = match category_id {
+                Some(category_id) => {
+                    let category = ledger
+                        .category(category_id)
+                        .ok_or_else(|| format!("category not found: {category_id}"))?;
+                    if category.archived {
+                        lines.push(Line::from("Archived"));
+                    }
+                    (
+                        format!("Category: {}", category.name),
+                        category.description.clone(),
+                    )
+                }
+                None => ("Uncategorized tasks".to_string(), None),
+            };
+            let tasks = ledger
+                .header
+                .tasks
+                .iter()
+                .filter(|task| task.category_id == *category_id)
+                .collect::<Vec<_>>();
+            lines.push(Line::from(format!("Tasks: {}", tasks.len())));
+            if category_id.is_some() {
+                lines.push(Line::from(""));
+                lines.push(heading("Description"));
+                push_text_lines(
+                    &mut lines,
+                    description.as_deref().unwrap_or_default(),
+                    "(no description)",
+                );
+            }
+            (title, tasks.iter().map(|task| task.id.clone()).collect())
+        }
+    };
+
+    let sessions = collect_sessions(ledger, now)
+        .into_iter()
+        .filter(|session| task_ids.contains(&session.task_id))
+        .collect::<Vec<_>>();
+    let stats = build_detail_stats(ledger, &sessions);
+    let show_tasks = !matches!(target, DetailTarget::Task(_));
+    let local_time = |timestamp: DateTime<Utc>| {
+        timestamp
+            .with_timezone(&Local)
+            .format("%a %d %b %Y %H:%M")
+            .to_string()
+    };
+
+    lines.push(Line::from(""));
+    lines.push(heading("Summary"));
+    if stats.sessions == 0 {
+        lines.push(Line::from("No sessions tracked yet"));
+    } else {
+        lines.push(Line::from(format!("Sessions: {}", stats.sessions)));
+        lines.push(Line::from(format!(
+            "Total: {}",
+            format_duration(stats.total)
+        )));
+        lines.push(Line::from(format!(
+            "Average session: {}",
+            format_duration(stats.average())
+        )));
+        lines.push(Line::from(format!(
+            "Longest session: {}",
+            format_duration(stats.longest)
+        )));
+        lines.push(Line::from(format!("Active days: {}", stats.active_days)));
+        if let Some(first) = stats.first {
+            lines.push(Line::from(format!("First tracked: {}", local_time(first))));
+        }
+        if let Some(last) = stats.last {
+            let running = sessions
+                .iter()
+                .any(|session| session.stop_event_index.is_none());
+            lines.push(Line::from(format!(
+                "Last tracked: {}{}",
+                local_time(last),
+                if running { " (running)" } else { "" }
+            )));
+        }
+        if let Some((day, duration)) = stats.busiest_day {
+            lines.push(Line::from(format!(
+                "Busiest day: {} ({})",
+                day.format("%a %d %b %Y"),
+                format_duration(duration)
+            )));
+        }
+    }
+
+    if let (Some((from, _)), Some((to, _))) = (stats.daily.first(), stats.daily.last()) {
+        lines.push(Line::from(""));
+        lines.push(heading("Daily time"));
+        lines.push(Line::from(Span::styled(
+            sparkline_text(&stats.daily),
+            Style::default().fg(theme.accent),
+        )));
+        lines.push(Line::from(Span::styled(
+            format!("{} – {}", from.format("%d %b %Y"), to.format("%d %b %Y")),
+            theme.muted_style(),
+        )));
+    }
+
+    if show_tasks && !stats.task_totals.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(heading("Tasks"));
+        for (task_id, duration) in &stats.task_totals {
+            let (_, project_name, task_title) = task_project_and_title(ledger, task_id);
+            let label = match target {
+                DetailTarget::Project(_) => task_title,
+                _ => format!("{project_name} · {task_title}"),
+            };
+            lines.push(Line::from(vec![
+                Span::raw(format!("{}  ", format_duration(*duration))),
+                Span::styled(label, task_style_for_id(ledger, task_id)),
+            ]));
+        }
+    }
+
+    if !sessions.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(heading("Sessions"));
+    }
+    for session in sessions.iter().rev() {
+        let start = session.start.with_timezone(&Local);
+        let stop = session.stop.with_timezone(&Local);
+        let stop_label = if session.stop_event_index.is_none() {
+            "running".to_string()
+        } else if stop.date_naive() == start.date_naive() {
+            stop.format("%H:%M").to_string()
+        } else {
+            stop.format("%d %b %H:%M").to_string()
+        };
+        let mut spans = vec![Span::raw(format!(
+            "{} – {stop_label}  {}",
+            local_time(session.start),
+            format_duration(session.stop - session.start)
+        ))];
+        if show_tasks {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(
+                task_label(ledger, &session.task_id),
+                task_style_for_id(ledger, &session.task_id),
+            ));
+        }
+        lines.push(Line::from(spans));
+        if let Some(note) = session
+            .note
+            .as_deref()
+            .filter(|note| !note.trim().is_empty())
+        {
+            for part in note.lines() {
+                lines.push(Line::from(Span::styled(
+                    format!("    {part}"),
+                    theme.muted_style(),
+                )));
+            }
+        }
+    }
+
+    Ok(DetailState {
+        title,
+        lines,
+        scroll: 0,
+    })
+}
+
+fn push_text_lines(lines: &mut Vec<Line<'static>>, text: &str, empty: &str) {
+    if text.trim().is_empty() {
+        lines.push(Line::from(empty.to_string()));
+    } else {
+        lines.extend(text.lines().map(|part| Line::from(part.to_string())));
+    }
+}
+
+fn build_week_stats(
+    selected_day: NaiveDate,
+    ledger: &Ledger,
+    daily_task_totals: &BTreeMap<NaiveDate, HashMap<String, Duration>>,
+) -> WeekStatsView {
+    let week_start = start_of_week(selected_day);
+    let mut daily = Vec::new();
+    let mut total = Duration::zero();
+    let mut max_day = Duration::zero();
+    let mut active_days = 0usize;
+    let mut project_totals: HashMap<String, Duration> = HashMap::new();
+    let mut daily_project_mix = Vec::new();
+
+    for offset in 0..7 {
+        let day = week_start + Duration::days(offset);
+        let durations = daily_task_totals.get(&day).cloned().unwrap_or_default();
+        let day_total = durations
+            .values()
+            .fold(Duration::zero(), |acc, value| acc + *value);
+
+        if day_total > Duration::zero() {
+            active_days += 1;
+        }
+        if day_total > max_day {
+            max_day = day_total;
+        }
+        total += day_total;
+        daily.push((day, day_total));
+
+        let mut day_project_totals: HashMap<String, Duration> = HashMap::new();
+        for (task_id, duration) in durations {
+            if let Some(task) = ledger.task(&task_id) {
+                *project_totals
+                    .entry(task.project_id.clone())
+                    .or_insert_with(Duration::zero) += duration;
+                *day_project_totals
                     .entry(task.project_id.clone())
                     .or_insert_with(Duration::zero) += duration;
             }
         }
 
-        let mut day_projects = day_project_totals
-            .iter()
-            .map(|(project_id, duration)| {
-                let project = ledger.project(project_id);
-                let name = project
-                    .map(|project| project.name.clone())
-                    .unwrap_or_else(|| "Unknown project".to_string());
-                let style =
-                    style_from_project_color(project.and_then(|project| project.color.as_deref()));
-                ProjectSummaryRow {
-                    name,
-                    style,
-                    duration: *duration,
-                }
-            })
-            .collect::<Vec<_>>();
-        day_projects.sort_by(|left, right| {
-            right
-                .duration
-                .cmp(&left.duration)
-                .then_with(|| left.name.cmp(&right.name))
-        });
-        daily_project_mix.push(day_projects);
+        daily_project_mix.push(project_summary_rows(ledger, &day_project_totals));
     }
 
     let avg_per_day = Duration::seconds(total.num_seconds() / 7);
 
-    let mut top_projects = project_totals
+    let top_projects = project_summary_rows(ledger, &project_totals);
+
+    WeekStatsView {
+        week_start,
+        daily,
+        total,
+        avg_per_day,
+        max_day,
+        active_days,
+        top_projects,
+        daily_project_mix,
+    }
+}
+
+fn build_period_stats(
+    span: StatsSpan,
+    selected_day: NaiveDate,
+    ledger: &Ledger,
+    daily_task_totals: &BTreeMap<NaiveDate, HashMap<String, Duration>>,
+) -> PeriodStatsView {
+    let (start, end, previous_start, title, previous_title) = match span {
+        StatsSpan::Year => {
+            let year = selected_day.year();
+            let first = |year| NaiveDate::from_ymd_opt(year, 1, 1).expect("January 1 must exist");
+            (
+                first(year),
+                first(year + 1),
+                first(year - 1),
+                year.to_string(),
+                (year - 1).to_string(),
+            )
+        }
+        StatsSpan::Month => {
+            let start = first_day_of_month(selected_day);
+            let previous_start = shift_month(start, -1);
+            (
+                start,
+                shift_month(start, 1),
+                previous_start,
+                start.format("%B %Y").to_string(),
+                previous_start.format("%B %Y").to_string(),
+            )
+        }
+    };
+
+    let mut buckets = Vec::<StatsBucket>::new();
+    let mut bucket_projects = Vec::<HashMap<String, Duration>>::new();
+    let mut project_totals = HashMap::<String, Duration>::new();
+    let mut category_totals = HashMap::<Option<String>, Duration>::new();
+    let mut total = Duration::zero();
+    let mut active_days = 0usize;
+    let mut longest_streak = 0usize;
+    let mut streak = 0usize;
+    let mut busiest_day: Option<(NaiveDate, Duration)> = None;
+
+    let mut day = start;
+    while day < end {
+        let label = match span {
+            StatsSpan::Year => day.format("%b").to_string(),
+            StatsSpan::Month => day.format("%d %a").to_string(),
+        };
+        if buckets.last().is_none_or(|bucket| bucket.label != label) {
+            buckets.push(StatsBucket {
+                label,
+                first_day: day,
+                last_day: day,
+                total: Duration::zero(),
+                project_mix: Vec::new(),
+            });
+            bucket_projects.push(HashMap::new());
+        }
+        let bucket = buckets.last_mut().expect("bucket was just pushed");
+        bucket.last_day = day;
+        let projects = bucket_projects.last_mut().expect("bucket was just pushed");
+
+        let mut day_total = Duration::zero();
+        for (task_id, duration) in daily_task_totals.get(&day).into_iter().flatten() {
+            day_total += *duration;
+            if let Some(task) = ledger.task(task_id) {
+                for totals in [&mut *projects, &mut project_totals] {
+                    *totals
+                        .entry(task.project_id.clone())
+                        .or_insert_with(Duration::zero) += *duration;
+                }
+                *category_totals
+                    .entry(task.category_id.clone())
+                    .or_insert_with(Duration::zero) += *duration;
+            }
+        }
+        bucket.total += day_total;
+        total += day_total;
+
+        if day_total > Duration::zero() {
+            active_days += 1;
+            streak += 1;
+            longest_streak = longest_streak.max(streak);
+            if busiest_day.is_none_or(|(_, busiest)| day_total > busiest) {
+                busiest_day = Some((day, day_total));
+            }
+        } else {
+            streak = 0;
+        }
+        day = day.succ_opt().expect("next day should exist");
+    }
+
+    for (bucket, projects) in buckets.iter_mut().zip(&bucket_projects) {
+        bucket.project_mix = project_summary_rows(ledger, projects);
+    }
+
+    let previous_total = daily_task_totals
+        .range(previous_start..start)
+        .flat_map(|(_, totals)| totals.values())
+        .fold(Duration::zero(), |acc, value| acc + *value);
+
+    let mut top_categories = category_totals
+        .into_iter()
+        .map(|(category_id, duration)| ProjectSummaryRow {
+            name: category_id
+                .as_deref()
+                .map(|category_id| category_label(ledger, category_id))
+                .unwrap_or_else(|| "(uncategorized)".to_string()),
+            style: Style::default(),
+            duration,
+        })
+        .collect::<Vec<_>>();
+    sort_summary_rows(&mut top_categories);
+
+    PeriodStatsView {
+        span,
+        title,
+        previous_title,
+        buckets,
+        total,
+        previous_total,
+        active_days,
+        longest_streak,
+        busiest_day,
+        top_projects: project_summary_rows(ledger, &project_totals),
+        top_categories,
+    }
+}
+
+fn project_summary_rows(
+    ledger: &Ledger,
+    project_totals: &HashMap<String, Duration>,
+) -> Vec<ProjectSummaryRow> {
+    let mut rows = project_totals
         .iter()
         .map(|(project_id, duration)| {
             let project = ledger.project(project_id);
@@ -3120,23 +4711,35 @@ fn build_week_stats(
             }
         })
         .collect::<Vec<_>>();
-    top_projects.sort_by(|left, right| {
+    sort_summary_rows(&mut rows);
+    rows
+}
+
+fn sort_summary_rows(rows: &mut [ProjectSummaryRow]) {
+    rows.sort_by(|left, right| {
         right
             .duration
             .cmp(&left.duration)
             .then_with(|| left.name.cmp(&right.name))
     });
+}
 
-    WeekStatsView {
-        week_start,
-        daily,
-        total,
-        avg_per_day,
-        max_day,
-        active_days,
-        top_projects,
-        daily_project_mix,
+/// `vs September 2026: 10:00:00 (+02:00:00, +20%)`; the change is left out
+/// when the previous period has no time to compare against.
+fn period_comparison(previous_title: &str, total: Duration, previous_total: Duration) -> String {
+    let mut text = format!("vs {previous_title}: {}", format_duration(previous_total));
+    let previous_seconds = previous_total.num_seconds();
+    if previous_seconds > 0 {
+        let delta = total - previous_total;
+        let sign = if delta < Duration::zero() { '-' } else { '+' };
+        let percent = (delta.num_seconds() as f64 * 100.0 / previous_seconds as f64).round();
+        text.push_str(&format!(
+            " ({sign}{}, {sign}{}%)",
+            format_duration(delta.abs()),
+            percent.abs()
+        ));
     }
+    text
 }
 
 fn build_explorer_rows(
@@ -3345,7 +4948,7 @@ fn start_task_at(
     note: Option<String>,
 ) -> Result<String, String> {
     let task = task_label(ledger, task_id);
-    ledger.start_task(task_id, timestamp, note)?;
+    ledger.start_task(task_id, timestamp, note, Utc::now())?;
     persist(ledger_path, ledger)?;
     Ok(format!("started: {task}"))
 }
@@ -3357,11 +4960,51 @@ fn stop_task(
     note: Option<String>,
 ) -> Result<String, String> {
     let task = task_label(ledger, task_id);
-    ledger.stop_task(task_id, Utc::now(), note)?;
+    let now = Utc::now();
+    ledger.stop_task(task_id, now, note, now)?;
     persist(ledger_path, ledger)?;
     Ok(format!("stopped: {task}"))
 }
 
+fn stop_all_tasks(ledger: &mut Ledger, ledger_path: &Path) -> Result<String, String> {
+    let mut updated = ledger.clone();
+    let now = Utc::now();
+    let stopped = updated.stop_all(now, now)?;
+    if stopped.is_empty() {
+        return Ok("nothing running".to_string());
+    }
+    persist(ledger_path, &updated)?;
+    *ledger = updated;
+    Ok(format!("stopped {} running task(s)", stopped.len()))
+}
+
+fn switch_to_task(
+    ledger: &mut Ledger,
+    ledger_path: &Path,
+    task_id: &str,
+) -> Result<String, String> {
+    let task = task_label(ledger, task_id);
+    let mut updated = ledger.clone();
+    let now = Utc::now();
+    let stopped = updated.switch_to(task_id, now, None, now)?;
+    persist(ledger_path, &updated)?;
+    *ledger = updated;
+    Ok(format!("switched to: {task} (stopped {})", stopped.len()))
+}
+
+fn resume_last_task(ledger: &mut Ledger, ledger_path: &Path) -> Result<String, String> {
+    let (task_id, note) = ledger
+        .last_stopped_session()
+        .ok_or_else(|| "no stopped task to resume".to_string())?;
+    let task = task_label(ledger, &task_id);
+    let mut updated = ledger.clone();
+    let now = Utc::now();
+    updated.start_task(&task_id, now, note, now)?;
+    persist(ledger_path, &updated)?;
+    *ledger = updated;
+    Ok(format!("resumed: {task}"))
+}
+
 fn log_task_interval(
     ledger: &mut Ledger,
     ledger_path: &Path,
@@ -3372,8 +5015,7 @@ fn log_task_interval(
 ) -> Result<String, String> {
     let task = task_label(ledger, task_id);
     let mut updated = ledger.clone();
-    updated.start_task(task_id, start_timestamp, note)?;
-    updated.stop_task(task_id, end_timestamp, None)?;
+    updated.log_session(task_id, start_timestamp, end_timestamp, note, Utc::now())?;
     persist(ledger_path, &updated)?;
     *ledger = updated;
     Ok(format!("logged: {task}"))
@@ -3412,10 +5054,10 @@ fn delete_interval(
     }
 
     let mut indices = vec![start_event_index];
-    if let Some(stop_index) = stop_event_index {
-        if stop_index != start_event_index {
-            indices.push(stop_index);
-        }
+    if let Some(stop_index) = stop_event_index
+        && stop_index != start_event_index
+    {
+        indices.push(stop_index);
     }
     indices.sort_unstable_by(|left, right| right.cmp(left));
     for index in indices {
@@ -3426,11 +5068,38 @@ fn delete_interval(
     Ok(format!("deleted interval: {task_title}"))
 }
 
-fn delete_task(
+fn split_session(
     ledger: &mut Ledger,
     ledger_path: &Path,
-    task_id: &str,
-    task_title: &str,
+    start_event_index: usize,
+    at: DateTime<Utc>,
+    second_task_id: &str,
+) -> Result<String, String> {
+    ledger.split_session(start_event_index, at, Some(second_task_id))?;
+    persist(ledger_path, ledger)?;
+    Ok(format!(
+        "split session at {}",
+        at.with_timezone(&Local).format("%H:%M")
+    ))
+}
+
+fn merge_sessions(
+    ledger: &mut Ledger,
+    ledger_path: &Path,
+    first_start_index: usize,
+    second_start_index: usize,
+    task_title: &str,
+) -> Result<String, String> {
+    ledger.merge_sessions(first_start_index, second_start_index)?;
+    persist(ledger_path, ledger)?;
+    Ok(format!("merged sessions: {task_title}"))
+}
+
+fn delete_task(
+    ledger: &mut Ledger,
+    ledger_path: &Path,
+    task_id: &str,
+    task_title: &str,
 ) -> Result<String, String> {
     ledger.delete_task(task_id)?;
     persist(ledger_path, ledger)?;
@@ -3506,6 +5175,52 @@ fn open_or_create_ledger(
     }
 }
 
+fn copy_entities(
+    ledger: &mut Ledger,
+    ledger_path: &Path,
+    source_path: &Path,
+    choice: &str,
+) -> Result<String, String> {
+    let source = load_ledger(source_path).map_err(|err| err.to_string())?;
+    let mut selection = EntitySelection::default();
+    if let Some(project_id) = choice.strip_prefix("project:") {
+        selection.projects.push(project_id.to_string());
+    } else if let Some(category_id) = choice.strip_prefix("category:") {
+        selection.categories.push(category_id.to_string());
+    }
+
+    let report = ledger.copy_entities_from(&source, &selection)?;
+    if report.copied() > 0 {
+        persist(ledger_path, ledger)?;
+    }
+    Ok(copy_report_message(&report))
+}
+
+fn copy_report_message(report: &CopyReport) -> String {
+    let copied = [
+        (report.projects.len(), "project"),
+        (report.categories.len(), "category"),
+        (report.tasks.len(), "task"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, noun)| match (count, noun) {
+        (1, noun) => format!("1 {noun}"),
+        (count, "category") => format!("{count} categories"),
+        (count, noun) => format!("{count} {noun}s"),
+    })
+    .collect::<Vec<_>>();
+    let copied = if copied.is_empty() {
+        "nothing new".to_string()
+    } else {
+        copied.join(", ")
+    };
+    match report.already_present {
+        0 => format!("copied {copied}"),
+        present => format!("copied {copied} ({present} already present)"),
+    }
+}
+
 fn forget_recent_ledger(path: &Path, current_path: &Path) -> Result<String, String> {
     match forget_ledger(path).map_err(|err| err.to_string())? {
         true if path == current_path => Ok(format!(
@@ -3539,26 +5254,16 @@ fn optional_text(input: &str) -> Option<String> {
     }
 }
 
-fn parse_time_input_on_day(
-    input: &str,
-    day: NaiveDate,
-    field_name: &str,
-) -> Result<DateTime<Utc>, String> {
-    let value = required_text(input, field_name)?;
-    let compact = value.replace(':', "");
-    if compact.len() != 4 || !compact.chars().all(|ch| ch.is_ascii_digit()) {
-        return Err(format!(
-            "invalid {field_name} '{value}', expected HHMM or HH:MM"
-        ));
-    }
-
-    let hour = compact[0..2]
-        .parse::<u32>()
-        .map_err(|_| format!("invalid {field_name} '{value}', expected HHMM or HH:MM"))?;
-    let minute = compact[2..4]
-        .parse::<u32>()
-        .map_err(|_| format!("invalid {field_name} '{value}', expected HHMM or HH:MM"))?;
-    local_clock_on_date_to_utc(day, hour, minute)
+fn parse_working_hours(input: &str) -> Result<(NaiveTime, NaiveTime), String> {
+    let value = required_text(input, "working hours")?;
+    let parse = |part: &str| {
+        NaiveTime::parse_from_str(part.trim(), "%H:%M")
+            .map_err(|_| format!("invalid working hours '{value}', expected HH:MM-HH:MM"))
+    };
+    let (start, end) = value
+        .split_once('-')
+        .ok_or_else(|| format!("invalid working hours '{value}', expected HH:MM-HH:MM"))?;
+    Ok((parse(start)?, parse(end)?))
 }
 
 fn validate_start_timestamp(timestamp: DateTime<Utc>, now: DateTime<Utc>) -> Result<(), String> {
@@ -3718,12 +5423,10 @@ fn cursor_from_line_col_with_ranges(
         return input.len();
     };
     let slice = &input[range.start..range.end];
-    let mut count = 0usize;
-    for (offset, _) in slice.char_indices() {
+    for (count, (offset, _)) in slice.char_indices().enumerate() {
         if count == col {
             return range.start + offset;
         }
-        count += 1;
     }
     range.end
 }
@@ -3806,7 +5509,7 @@ fn edit_field_display(field: &EditField) -> (String, Style) {
     }
 }
 
-fn build_edit_hint_lines(edit: &EditState) -> Vec<Line<'static>> {
+fn build_edit_hint_lines(theme: &Theme, edit: &EditState) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
     if edit.editing {
         let label = edit
@@ -3815,6 +5518,16 @@ fn build_edit_hint_lines(edit: &EditState) -> Vec<Line<'static>> {
             .map(|field| field.label.clone())
             .unwrap_or_else(|| "Field".to_string());
         lines.push(Line::from(format!("Editing: {label}")));
+        if edit
+            .fields
+            .get(edit.selected)
+            .is_some_and(|field| field.id == EditFieldId::Color)
+        {
+            lines.push(Line::from(Span::styled(
+                "Color name, #rrggbb or 0-255; empty for none",
+                theme.muted_style(),
+            )));
+        }
 
         if edit.input.is_empty() {
             lines.push(Line::from(""));
@@ -3830,11 +5543,11 @@ fn build_edit_hint_lines(edit: &EditState) -> Vec<Line<'static>> {
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "Arrows move cursor | Enter save field",
-            Style::default().fg(Color::DarkGray),
+            theme.muted_style(),
         )));
         lines.push(Line::from(Span::styled(
             "Ctrl+J newline | Esc cancel",
-            Style::default().fg(Color::DarkGray),
+            theme.muted_style(),
         )));
     } else {
         lines.push(Line::from(
@@ -3855,6 +5568,12 @@ fn activate_edit_field(edit: &mut EditState) {
             edit.input = value.clone();
             edit.cursor = edit.input.len();
         }
+        // Named colors cycle with Left/Right; Enter types any color value.
+        EditFieldKind::Choice { value, .. } if field.id == EditFieldId::Color => {
+            edit.editing = true;
+            edit.input = value.clone().unwrap_or_default();
+            edit.cursor = edit.input.len();
+        }
         EditFieldKind::Bool { .. } | EditFieldKind::Choice { .. } => {
             cycle_edit_field(edit, 1);
         }
@@ -3916,17 +5635,16 @@ fn refresh_interval_task_options(edit: &mut EditState, ledger: &Ledger) {
         return;
     }
 
-    if let Some(current_task) = current_task {
-        if options
+    if let Some(current_task) = current_task
+        && options
             .iter()
             .any(|option| option.value.as_deref() == Some(current_task.as_str()))
-        {
-            *value = Some(current_task);
-            if let Some(selected_task) = value.as_ref() {
-                edit.title = format!("Edit interval: {}", task_label(ledger, selected_task));
-            }
-            return;
+    {
+        *value = Some(current_task);
+        if let Some(selected_task) = value.as_ref() {
+            edit.title = format!("Edit interval: {}", task_label(ledger, selected_task));
         }
+        return;
     }
 
     *value = options.first().and_then(|option| option.value.clone());
@@ -3935,16 +5653,35 @@ fn refresh_interval_task_options(edit: &mut EditState, ledger: &Ledger) {
     }
 }
 
-fn commit_edit_field_input(edit: &mut EditState) {
+fn commit_edit_field_input(edit: &mut EditState) -> Result<(), String> {
     let Some(field) = edit.fields.get_mut(edit.selected) else {
-        return;
+        return Ok(());
     };
-    if let EditFieldKind::Text { value, .. } = &mut field.kind {
-        *value = edit.input.clone();
+    match &mut field.kind {
+        EditFieldKind::Text { value, .. } => *value = edit.input.clone(),
+        EditFieldKind::Choice { value, .. } if field.id == EditFieldId::Color => {
+            *value = parse_project_color_input(&edit.input)?;
+        }
+        EditFieldKind::Bool { .. } | EditFieldKind::Choice { .. } => {}
     }
     edit.editing = false;
     edit.input.clear();
     edit.cursor = 0;
+    Ok(())
+}
+
+/// Empty input clears the color.
+fn parse_project_color_input(input: &str) -> Result<Option<String>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    if parse_color(input).is_none() {
+        return Err(format!(
+            "invalid color '{input}': use a terminal color name, #rrggbb, or 0-255"
+        ));
+    }
+    Ok(Some(input.to_string()))
 }
 
 fn edit_selected_field_multiline(edit: &EditState) -> bool {
@@ -4044,97 +5781,1740 @@ fn color_block_style(color_name: &str) -> Style {
         .unwrap_or_default()
 }
 
-fn color_from_name(color_name: &str) -> Option<Color> {
-    match color_name {
-        "black" => Some(Color::Black),
-        "red" => Some(Color::Red),
-        "green" => Some(Color::Green),
-        "yellow" => Some(Color::Yellow),
-        "blue" => Some(Color::Blue),
-        "magenta" => Some(Color::Magenta),
-        "cyan" => Some(Color::Cyan),
-        "gray" => Some(Color::Gray),
-        "dark_gray" => Some(Color::DarkGray),
-        "light_red" => Some(Color::LightRed),
-        "light_green" => Some(Color::LightGreen),
-        "light_yellow" => Some(Color::LightYellow),
-        "light_blue" => Some(Color::LightBlue),
-        "light_magenta" => Some(Color::LightMagenta),
-        "light_cyan" => Some(Color::LightCyan),
-        "white" => Some(Color::White),
-        _ => None,
-    }
-}
-
-fn border_style(focused: bool) -> Style {
-    if focused {
-        Style::default()
-            .fg(FOCUSED_PANEL_BORDER_COLOR)
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(INACTIVE_PANEL_BORDER_COLOR)
+fn color_from_name(color_name: &str) -> Option<Color> {
+    parse_color(color_name)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let first_of_next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1).expect("next year date should be valid")
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1).expect("next month date should be valid")
+    };
+    (first_of_next - Duration::days(1)).day()
+}
+
+fn first_day_of_month(day: NaiveDate) -> NaiveDate {
+    NaiveDate::from_ymd_opt(day.year(), day.month(), 1).expect("first day of month must be valid")
+}
+
+fn start_of_week(day: NaiveDate) -> NaiveDate {
+    let days_from_monday = day.weekday().number_from_monday() as i64 - 1;
+    day - Duration::days(days_from_monday)
+}
+
+fn local_naive_to_utc(naive: chrono::NaiveDateTime) -> Option<DateTime<Utc>> {
+    match Local.from_local_datetime(&naive) {
+        LocalResult::Single(local_datetime) => Some(local_datetime.with_timezone(&Utc)),
+        LocalResult::Ambiguous(first, second) => Some(first.min(second).with_timezone(&Utc)),
+        LocalResult::None => None,
+    }
+}
+
+fn local_clock_on_date_to_utc(
+    day: NaiveDate,
+    hour: u32,
+    minute: u32,
+) -> Result<DateTime<Utc>, String> {
+    let naive = day
+        .and_hms_opt(hour, minute, 0)
+        .ok_or_else(|| "invalid clock time".to_string())?;
+    local_naive_to_utc(naive).ok_or_else(|| "selected local time does not exist".to_string())
+}
+
+fn shift_month(day: NaiveDate, delta: i32) -> NaiveDate {
+    let mut year = day.year();
+    let mut month = day.month() as i32 + delta;
+    while month > 12 {
+        year += 1;
+        month -= 12;
+    }
+    while month < 1 {
+        year -= 1;
+        month += 12;
+    }
+    let month_u32 = month as u32;
+    let max_day = days_in_month(year, month_u32);
+    let target_day = day.day().min(max_day);
+    NaiveDate::from_ymd_opt(year, month_u32, target_day).expect("shifted month date must be valid")
+}
+
+fn explorer_category_key(project_id: &str, category_id: Option<&str>) -> String {
+    match category_id {
+        Some(category_id) => format!("{project_id}:{category_id}"),
+        None => format!("{project_id}:__uncategorized"),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Quit,
+    Help,
+    Back,
+    Cancel,
+    Confirm,
+    Save,
+    Toggle,
+    NextPane,
+    PrevPane,
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    PageUp,
+    PageDown,
+    LineStart,
+    LineEnd,
+    NextMonth,
+    PrevMonth,
+    DeleteBack,
+    DeleteForward,
+    InsertNewline,
+    TypeDigit,
+    ShowProjects,
+    ShowCategories,
+    Edit,
+    New,
+    NewTask,
+    Ledgers,
+    SessionNote,
+    Delete,
+    ToggleDayLayout,
+    CycleStatsPeriod,
+    Nudge(i64),
+    Split,
+    Merge,
+    ToggleGaps,
+    AssignGap,
+    WorkingHours,
+    StopAll,
+    SwitchTo,
+    Resume,
+    Details,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyContext {
+    Normal,
+    Prompt,
+    Select,
+    EditList,
+    EditField,
+    Help,
+    Detail,
+}
+
+impl KeyContext {
+    fn for_mode(mode: &InputMode) -> Self {
+        match mode {
+            InputMode::Normal => KeyContext::Normal,
+            InputMode::Prompt(_) => KeyContext::Prompt,
+            InputMode::Select(_) => KeyContext::Select,
+            InputMode::Edit(edit) if edit.editing => KeyContext::EditField,
+            InputMode::Edit(_) => KeyContext::EditList,
+            InputMode::Help(_) => KeyContext::Help,
+            InputMode::Detail(_) => KeyContext::Detail,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum KeyMatch {
+    Key(KeyCode),
+    Ctrl(char),
+    Alt(char),
+    Digit,
+}
+
+impl KeyMatch {
+    fn matches(self, key: &KeyEvent) -> bool {
+        match self {
+            KeyMatch::Key(code) => key.code == code,
+            KeyMatch::Ctrl(value) => {
+                key.code == KeyCode::Char(value) && key.modifiers.contains(KeyModifiers::CONTROL)
+            }
+            KeyMatch::Alt(value) => {
+                key.code == KeyCode::Char(value) && key.modifiers.contains(KeyModifiers::ALT)
+            }
+            KeyMatch::Digit => matches!(key.code, KeyCode::Char(value) if value.is_ascii_digit()),
+        }
+    }
+}
+
+/// One row of the key registry. Handlers resolve keys through
+/// `key_action`, and the help overlay lists the same rows, so a binding only
+/// has to be declared once. Several rows may share keys within a context to
+/// describe pane-specific behavior, but they must map to the same action.
+struct KeyBinding {
+    context: KeyContext,
+    keys: &'static [KeyMatch],
+    label: &'static str,
+    group: &'static str,
+    description: &'static str,
+    action: Action,
+    available: fn(&App) -> bool,
+}
+
+const KEY_BINDINGS: &[KeyBinding] = &[
+    // Normal mode
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Tab)],
+        label: "Tab",
+        group: "Panes",
+        description: "Focus next pane",
+        action: Action::NextPane,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::BackTab)],
+        label: "Shift+Tab",
+        group: "Panes",
+        description: "Focus previous pane",
+        action: Action::PrevPane,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[
+            KeyMatch::Key(KeyCode::Up),
+            KeyMatch::Key(KeyCode::Char('k')),
+        ],
+        label: "k / Up",
+        group: "Calendar",
+        description: "Previous week",
+        action: Action::MoveUp,
+        available: focus_calendar,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[
+            KeyMatch::Key(KeyCode::Down),
+            KeyMatch::Key(KeyCode::Char('j')),
+        ],
+        label: "j / Down",
+        group: "Calendar",
+        description: "Next week",
+        action: Action::MoveDown,
+        available: focus_calendar,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[
+            KeyMatch::Key(KeyCode::Left),
+            KeyMatch::Key(KeyCode::Char('h')),
+        ],
+        label: "h / Left",
+        group: "Calendar",
+        description: "Previous day",
+        action: Action::MoveLeft,
+        available: focus_calendar,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[
+            KeyMatch::Key(KeyCode::Right),
+            KeyMatch::Key(KeyCode::Char('l')),
+        ],
+        label: "l / Right",
+        group: "Calendar",
+        description: "Next day",
+        action: Action::MoveRight,
+        available: focus_calendar,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('n'))],
+        label: "n",
+        group: "Calendar",
+        description: "Next month",
+        action: Action::NextMonth,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('N'))],
+        label: "N",
+        group: "Calendar",
+        description: "Previous month",
+        action: Action::PrevMonth,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[
+            KeyMatch::Key(KeyCode::Up),
+            KeyMatch::Key(KeyCode::Char('k')),
+        ],
+        label: "k / Up",
+        group: "Day view",
+        description: "Previous session",
+        action: Action::MoveUp,
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[
+            KeyMatch::Key(KeyCode::Down),
+            KeyMatch::Key(KeyCode::Char('j')),
+        ],
+        label: "j / Down",
+        group: "Day view",
+        description: "Next session",
+        action: Action::MoveDown,
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[
+            KeyMatch::Key(KeyCode::Left),
+            KeyMatch::Key(KeyCode::Char('h')),
+        ],
+        label: "h / Left",
+        group: "Day view",
+        description: "Select start time",
+        action: Action::MoveLeft,
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[
+            KeyMatch::Key(KeyCode::Right),
+            KeyMatch::Key(KeyCode::Char('l')),
+        ],
+        label: "l / Right",
+        group: "Day view",
+        description: "Select end time",
+        action: Action::MoveRight,
+        available: focus_day,
+    },
+    // Alt rows come first: plain key patterns ignore modifiers.
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Alt('='), KeyMatch::Alt('+')],
+        label: "Alt =",
+        group: "Day view",
+        description: "Move the selected time 15 minutes later",
+        action: Action::Nudge(15),
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Alt('-'), KeyMatch::Alt('_')],
+        label: "Alt -",
+        group: "Day view",
+        description: "Move the selected time 15 minutes earlier",
+        action: Action::Nudge(-15),
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('='))],
+        label: "=",
+        group: "Day view",
+        description: "Move the selected time 1 minute later",
+        action: Action::Nudge(1),
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('-'))],
+        label: "-",
+        group: "Day view",
+        description: "Move the selected time 1 minute earlier",
+        action: Action::Nudge(-1),
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('+'))],
+        label: "+",
+        group: "Day view",
+        description: "Move the selected time 5 minutes later",
+        action: Action::Nudge(5),
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('_'))],
+        label: "_",
+        group: "Day view",
+        description: "Move the selected time 5 minutes earlier",
+        action: Action::Nudge(-5),
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Digit],
+        label: "0-9",
+        group: "Day view",
+        description: "Type HHMM to set the selected time",
+        action: Action::TypeDigit,
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Backspace)],
+        label: "Backspace",
+        group: "Day view",
+        description: "Remove last typed digit",
+        action: Action::DeleteBack,
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('e'))],
+        label: "e",
+        group: "Day view",
+        description: "Edit session task and note",
+        action: Action::Edit,
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('d'))],
+        label: "d",
+        group: "Day view",
+        description: "Delete session",
+        action: Action::Delete,
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('f'))],
+        label: "f",
+        group: "Day view",
+        description: "Show or hide untracked gaps",
+        action: Action::ToggleGaps,
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('a'))],
+        label: "a",
+        group: "Day view",
+        description: "Assign the selected gap to a task",
+        action: Action::AssignGap,
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('w'))],
+        label: "w",
+        group: "Day view",
+        description: "Set working hours for gaps",
+        action: Action::WorkingHours,
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('x'))],
+        label: "x",
+        group: "Day view",
+        description: "Split session at a time",
+        action: Action::Split,
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('m'))],
+        label: "m",
+        group: "Day view",
+        description: "Merge with the task's next session",
+        action: Action::Merge,
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char(' '))],
+        label: "Space",
+        group: "Day view",
+        description: "Stop the session's task, or start it again",
+        action: Action::Toggle,
+        available: focus_day,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[
+            KeyMatch::Key(KeyCode::Up),
+            KeyMatch::Key(KeyCode::Char('k')),
+        ],
+        label: "k / Up",
+        group: "Running tasks",
+        description: "Previous running task",
+        action: Action::MoveUp,
+        available: focus_running,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[
+            KeyMatch::Key(KeyCode::Down),
+            KeyMatch::Key(KeyCode::Char('j')),
+        ],
+        label: "j / Down",
+        group: "Running tasks",
+        description: "Next running task",
+        action: Action::MoveDown,
+        available: focus_running,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char(' '))],
+        label: "Space",
+        group: "Running tasks",
+        description: "Stop task",
+        action: Action::Toggle,
+        available: focus_running,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('s'))],
+        label: "s",
+        group: "Running tasks",
+        description: "Edit session note",
+        action: Action::SessionNote,
+        available: focus_running,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('S'))],
+        label: "S",
+        group: "General",
+        description: "Stop all running tasks",
+        action: Action::StopAll,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('i'))],
+        label: "i",
+        group: "General",
+        description: "Switch to the selected task, stopping all others",
+        action: Action::SwitchTo,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('r'))],
+        label: "r",
+        group: "General",
+        description: "Resume last stopped task",
+        action: Action::Resume,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[
+            KeyMatch::Key(KeyCode::Up),
+            KeyMatch::Key(KeyCode::Char('k')),
+        ],
+        label: "k / Up",
+        group: "Explorer",
+        description: "Previous row",
+        action: Action::MoveUp,
+        available: focus_explorer,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[
+            KeyMatch::Key(KeyCode::Down),
+            KeyMatch::Key(KeyCode::Char('j')),
+        ],
+        label: "j / Down",
+        group: "Explorer",
+        description: "Next row",
+        action: Action::MoveDown,
+        available: focus_explorer,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Enter)],
+        label: "Enter",
+        group: "Explorer",
+        description: "Open project",
+        action: Action::Confirm,
+        available: explorer_projects,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Enter)],
+        label: "Enter",
+        group: "Explorer",
+        description: "Collapse or expand category, or show task details",
+        action: Action::Confirm,
+        available: explorer_project_tasks,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('I'))],
+        label: "I",
+        group: "Explorer",
+        description: "Show details and history of the selected row",
+        action: Action::Details,
+        available: focus_explorer,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char(' '))],
+        label: "Space",
+        group: "Explorer",
+        description: "Start task (opens timing dialog) or stop it",
+        action: Action::Toggle,
+        available: explorer_project_tasks,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('s'))],
+        label: "s",
+        group: "Explorer",
+        description: "Start task with a session note",
+        action: Action::SessionNote,
+        available: explorer_project_tasks,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('e'))],
+        label: "e",
+        group: "Explorer",
+        description: "Edit selected project, category, or task",
+        action: Action::Edit,
+        available: focus_explorer,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('d'))],
+        label: "d",
+        group: "Explorer",
+        description: "Delete selected task",
+        action: Action::Delete,
+        available: explorer_project_tasks,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('d'))],
+        label: "d",
+        group: "Explorer",
+        description: "Delete selected category",
+        action: Action::Delete,
+        available: explorer_categories,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('o'))],
+        label: "o",
+        group: "Explorer",
+        description: "New project",
+        action: Action::New,
+        available: explorer_projects,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('o'))],
+        label: "o",
+        group: "Explorer",
+        description: "New category",
+        action: Action::New,
+        available: explorer_categories,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('o'))],
+        label: "o",
+        group: "Explorer",
+        description: "New task in this project",
+        action: Action::New,
+        available: explorer_project_tasks,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Esc)],
+        label: "Esc",
+        group: "Explorer",
+        description: "Back to projects",
+        action: Action::Back,
+        available: explorer_not_projects,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('p'))],
+        label: "p",
+        group: "General",
+        description: "Show projects in Explorer",
+        action: Action::ShowProjects,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('c'))],
+        label: "c",
+        group: "General",
+        description: "Show categories in Explorer",
+        action: Action::ShowCategories,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('t'))],
+        label: "t",
+        group: "General",
+        description: "New task (pick project and category)",
+        action: Action::NewTask,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('v'))],
+        label: "v",
+        group: "General",
+        description: "Switch day view between list and timeline",
+        action: Action::ToggleDayLayout,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('y'))],
+        label: "y",
+        group: "General",
+        description: "Switch stats panel between week, month and year",
+        action: Action::CycleStatsPeriod,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('g'))],
+        label: "g",
+        group: "General",
+        description: "Ledger management",
+        action: Action::Ledgers,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[
+            KeyMatch::Key(KeyCode::Char('?')),
+            KeyMatch::Key(KeyCode::F(1)),
+        ],
+        label: "? / F1",
+        group: "General",
+        description: "Show this help",
+        action: Action::Help,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Normal,
+        keys: &[KeyMatch::Key(KeyCode::Char('q'))],
+        label: "q",
+        group: "General",
+        description: "Quit",
+        action: Action::Quit,
+        available: always,
+    },
+    // Text prompts
+    KeyBinding {
+        context: KeyContext::Prompt,
+        keys: &[KeyMatch::Ctrl('j')],
+        label: "Ctrl+J",
+        group: "Prompt",
+        description: "Insert newline",
+        action: Action::InsertNewline,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Prompt,
+        keys: &[
+            KeyMatch::Key(KeyCode::Enter),
+            KeyMatch::Key(KeyCode::Char('\n')),
+            KeyMatch::Key(KeyCode::Char('\r')),
+        ],
+        label: "Enter",
+        group: "Prompt",
+        description: "Confirm",
+        action: Action::Confirm,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Prompt,
+        keys: &[KeyMatch::Key(KeyCode::Esc)],
+        label: "Esc",
+        group: "Prompt",
+        description: "Cancel",
+        action: Action::Cancel,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Prompt,
+        keys: &[KeyMatch::Key(KeyCode::Left)],
+        label: "Left",
+        group: "Cursor",
+        description: "Move cursor left",
+        action: Action::MoveLeft,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Prompt,
+        keys: &[KeyMatch::Key(KeyCode::Right)],
+        label: "Right",
+        group: "Cursor",
+        description: "Move cursor right",
+        action: Action::MoveRight,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Prompt,
+        keys: &[KeyMatch::Key(KeyCode::Up)],
+        label: "Up",
+        group: "Cursor",
+        description: "Move cursor to previous line",
+        action: Action::MoveUp,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Prompt,
+        keys: &[KeyMatch::Key(KeyCode::Down)],
+        label: "Down",
+        group: "Cursor",
+        description: "Move cursor to next line",
+        action: Action::MoveDown,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Prompt,
+        keys: &[KeyMatch::Key(KeyCode::Home)],
+        label: "Home",
+        group: "Cursor",
+        description: "Start of line",
+        action: Action::LineStart,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Prompt,
+        keys: &[KeyMatch::Key(KeyCode::End)],
+        label: "End",
+        group: "Cursor",
+        description: "End of line",
+        action: Action::LineEnd,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Prompt,
+        keys: &[KeyMatch::Key(KeyCode::Backspace)],
+        label: "Backspace",
+        group: "Cursor",
+        description: "Delete character before cursor",
+        action: Action::DeleteBack,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Prompt,
+        keys: &[KeyMatch::Key(KeyCode::Delete)],
+        label: "Delete",
+        group: "Cursor",
+        description: "Delete character under cursor",
+        action: Action::DeleteForward,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Prompt,
+        keys: &[KeyMatch::Key(KeyCode::F(1))],
+        label: "F1",
+        group: "Prompt",
+        description: "Show this help",
+        action: Action::Help,
+        available: always,
+    },
+    // Select popups
+    KeyBinding {
+        context: KeyContext::Select,
+        keys: &[
+            KeyMatch::Key(KeyCode::Up),
+            KeyMatch::Key(KeyCode::Char('k')),
+        ],
+        label: "k / Up",
+        group: "Select",
+        description: "Previous option",
+        action: Action::MoveUp,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Select,
+        keys: &[
+            KeyMatch::Key(KeyCode::Down),
+            KeyMatch::Key(KeyCode::Char('j')),
+        ],
+        label: "j / Down",
+        group: "Select",
+        description: "Next option",
+        action: Action::MoveDown,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Select,
+        keys: &[KeyMatch::Key(KeyCode::Enter)],
+        label: "Enter",
+        group: "Select",
+        description: "Choose option",
+        action: Action::Confirm,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Select,
+        keys: &[KeyMatch::Key(KeyCode::Esc)],
+        label: "Esc",
+        group: "Select",
+        description: "Cancel",
+        action: Action::Cancel,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Select,
+        keys: &[
+            KeyMatch::Key(KeyCode::Char('?')),
+            KeyMatch::Key(KeyCode::F(1)),
+        ],
+        label: "? / F1",
+        group: "Select",
+        description: "Show this help",
+        action: Action::Help,
+        available: always,
+    },
+    // Edit popup field list
+    KeyBinding {
+        context: KeyContext::EditList,
+        keys: &[KeyMatch::Ctrl('s')],
+        label: "Ctrl+S",
+        group: "Edit",
+        description: "Save changes",
+        action: Action::Save,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditList,
+        keys: &[
+            KeyMatch::Key(KeyCode::Up),
+            KeyMatch::Key(KeyCode::Char('k')),
+        ],
+        label: "k / Up",
+        group: "Edit",
+        description: "Previous field",
+        action: Action::MoveUp,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditList,
+        keys: &[
+            KeyMatch::Key(KeyCode::Down),
+            KeyMatch::Key(KeyCode::Char('j')),
+        ],
+        label: "j / Down",
+        group: "Edit",
+        description: "Next field",
+        action: Action::MoveDown,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditList,
+        keys: &[
+            KeyMatch::Key(KeyCode::Left),
+            KeyMatch::Key(KeyCode::Char('h')),
+        ],
+        label: "h / Left",
+        group: "Edit",
+        description: "Previous choice",
+        action: Action::MoveLeft,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditList,
+        keys: &[
+            KeyMatch::Key(KeyCode::Right),
+            KeyMatch::Key(KeyCode::Char('l')),
+        ],
+        label: "l / Right",
+        group: "Edit",
+        description: "Next choice",
+        action: Action::MoveRight,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditList,
+        keys: &[KeyMatch::Key(KeyCode::Char(' '))],
+        label: "Space",
+        group: "Edit",
+        description: "Toggle or cycle field",
+        action: Action::Toggle,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditList,
+        keys: &[KeyMatch::Key(KeyCode::Enter)],
+        label: "Enter",
+        group: "Edit",
+        description: "Edit text field, toggle or cycle others",
+        action: Action::Confirm,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditList,
+        keys: &[KeyMatch::Key(KeyCode::Esc)],
+        label: "Esc",
+        group: "Edit",
+        description: "Discard changes",
+        action: Action::Cancel,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditList,
+        keys: &[
+            KeyMatch::Key(KeyCode::Char('?')),
+            KeyMatch::Key(KeyCode::F(1)),
+        ],
+        label: "? / F1",
+        group: "Edit",
+        description: "Show this help",
+        action: Action::Help,
+        available: always,
+    },
+    // Edit popup text field
+    KeyBinding {
+        context: KeyContext::EditField,
+        keys: &[KeyMatch::Ctrl('j')],
+        label: "Ctrl+J",
+        group: "Field",
+        description: "Insert newline (multiline fields)",
+        action: Action::InsertNewline,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditField,
+        keys: &[
+            KeyMatch::Key(KeyCode::Enter),
+            KeyMatch::Key(KeyCode::Char('\n')),
+            KeyMatch::Key(KeyCode::Char('\r')),
+        ],
+        label: "Enter",
+        group: "Field",
+        description: "Keep field value",
+        action: Action::Confirm,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditField,
+        keys: &[KeyMatch::Key(KeyCode::Esc)],
+        label: "Esc",
+        group: "Field",
+        description: "Discard field edits",
+        action: Action::Cancel,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditField,
+        keys: &[KeyMatch::Key(KeyCode::Left)],
+        label: "Left",
+        group: "Cursor",
+        description: "Move cursor left",
+        action: Action::MoveLeft,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditField,
+        keys: &[KeyMatch::Key(KeyCode::Right)],
+        label: "Right",
+        group: "Cursor",
+        description: "Move cursor right",
+        action: Action::MoveRight,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditField,
+        keys: &[KeyMatch::Key(KeyCode::Up)],
+        label: "Up",
+        group: "Cursor",
+        description: "Move cursor to previous line",
+        action: Action::MoveUp,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditField,
+        keys: &[KeyMatch::Key(KeyCode::Down)],
+        label: "Down",
+        group: "Cursor",
+        description: "Move cursor to next line",
+        action: Action::MoveDown,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditField,
+        keys: &[KeyMatch::Key(KeyCode::Home)],
+        label: "Home",
+        group: "Cursor",
+        description: "Start of line",
+        action: Action::LineStart,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditField,
+        keys: &[KeyMatch::Key(KeyCode::End)],
+        label: "End",
+        group: "Cursor",
+        description: "End of line",
+        action: Action::LineEnd,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditField,
+        keys: &[KeyMatch::Key(KeyCode::Backspace)],
+        label: "Backspace",
+        group: "Cursor",
+        description: "Delete character before cursor",
+        action: Action::DeleteBack,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditField,
+        keys: &[KeyMatch::Key(KeyCode::Delete)],
+        label: "Delete",
+        group: "Cursor",
+        description: "Delete character under cursor",
+        action: Action::DeleteForward,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::EditField,
+        keys: &[KeyMatch::Key(KeyCode::F(1))],
+        label: "F1",
+        group: "Field",
+        description: "Show this help",
+        action: Action::Help,
+        available: always,
+    },
+    // Help overlay
+    KeyBinding {
+        context: KeyContext::Help,
+        keys: &[
+            KeyMatch::Key(KeyCode::Up),
+            KeyMatch::Key(KeyCode::Char('k')),
+        ],
+        label: "k / Up",
+        group: "Help",
+        description: "Scroll up",
+        action: Action::MoveUp,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Help,
+        keys: &[
+            KeyMatch::Key(KeyCode::Down),
+            KeyMatch::Key(KeyCode::Char('j')),
+        ],
+        label: "j / Down",
+        group: "Help",
+        description: "Scroll down",
+        action: Action::MoveDown,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Help,
+        keys: &[KeyMatch::Key(KeyCode::PageUp)],
+        label: "PgUp",
+        group: "Help",
+        description: "Scroll up one page",
+        action: Action::PageUp,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Help,
+        keys: &[KeyMatch::Key(KeyCode::PageDown)],
+        label: "PgDn",
+        group: "Help",
+        description: "Scroll down one page",
+        action: Action::PageDown,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Help,
+        keys: &[
+            KeyMatch::Key(KeyCode::Home),
+            KeyMatch::Key(KeyCode::Char('g')),
+        ],
+        label: "g / Home",
+        group: "Help",
+        description: "Jump to top",
+        action: Action::LineStart,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Help,
+        keys: &[
+            KeyMatch::Key(KeyCode::End),
+            KeyMatch::Key(KeyCode::Char('G')),
+        ],
+        label: "G / End",
+        group: "Help",
+        description: "Jump to bottom",
+        action: Action::LineEnd,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Help,
+        keys: &[
+            KeyMatch::Key(KeyCode::Esc),
+            KeyMatch::Key(KeyCode::Char('q')),
+            KeyMatch::Key(KeyCode::Char('?')),
+            KeyMatch::Key(KeyCode::F(1)),
+        ],
+        label: "Esc / ? / q",
+        group: "Help",
+        description: "Close help",
+        action: Action::Cancel,
+        available: always,
+    },
+    // Detail popup
+    KeyBinding {
+        context: KeyContext::Detail,
+        keys: &[
+            KeyMatch::Key(KeyCode::Up),
+            KeyMatch::Key(KeyCode::Char('k')),
+        ],
+        label: "k / Up",
+        group: "Details",
+        description: "Scroll up",
+        action: Action::MoveUp,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Detail,
+        keys: &[
+            KeyMatch::Key(KeyCode::Down),
+            KeyMatch::Key(KeyCode::Char('j')),
+        ],
+        label: "j / Down",
+        group: "Details",
+        description: "Scroll down",
+        action: Action::MoveDown,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Detail,
+        keys: &[KeyMatch::Key(KeyCode::PageUp)],
+        label: "PgUp",
+        group: "Details",
+        description: "Scroll up one page",
+        action: Action::PageUp,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Detail,
+        keys: &[KeyMatch::Key(KeyCode::PageDown)],
+        label: "PgDn",
+        group: "Details",
+        description: "Scroll down one page",
+        action: Action::PageDown,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Detail,
+        keys: &[
+            KeyMatch::Key(KeyCode::Home),
+            KeyMatch::Key(KeyCode::Char('g')),
+        ],
+        label: "g / Home",
+        group: "Details",
+        description: "Jump to top",
+        action: Action::LineStart,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Detail,
+        keys: &[
+            KeyMatch::Key(KeyCode::End),
+            KeyMatch::Key(KeyCode::Char('G')),
+        ],
+        label: "G / End",
+        group: "Details",
+        description: "Jump to bottom",
+        action: Action::LineEnd,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Detail,
+        keys: &[
+            KeyMatch::Key(KeyCode::Char('?')),
+            KeyMatch::Key(KeyCode::F(1)),
+        ],
+        label: "? / F1",
+        group: "Details",
+        description: "Show this help",
+        action: Action::Help,
+        available: always,
+    },
+    KeyBinding {
+        context: KeyContext::Detail,
+        keys: &[
+            KeyMatch::Key(KeyCode::Esc),
+            KeyMatch::Key(KeyCode::Enter),
+            KeyMatch::Key(KeyCode::Char('q')),
+            KeyMatch::Key(KeyCode::Char('I')),
+        ],
+        label: "Esc / Enter / q",
+        group: "Details",
+        description: "Close details",
+        action: Action::Cancel,
+        available: always,
+    },
+];
+
+fn key_action(context: KeyContext, key: &KeyEvent) -> Option<Action> {
+    KEY_BINDINGS
+        .iter()
+        .filter(|binding| binding.context == context)
+        .find(|binding| binding.keys.iter().any(|pattern| pattern.matches(key)))
+        .map(|binding| binding.action)
+}
+
+fn always(_: &App) -> bool {
+    true
+}
+
+fn focus_calendar(app: &App) -> bool {
+    app.focus == FocusPane::Calendar
+}
+
+fn focus_day(app: &App) -> bool {
+    app.focus == FocusPane::Day
+}
+
+fn focus_running(app: &App) -> bool {
+    app.focus == FocusPane::Running
+}
+
+fn focus_explorer(app: &App) -> bool {
+    app.focus == FocusPane::Explorer
+}
+
+fn explorer_projects(app: &App) -> bool {
+    focus_explorer(app) && matches!(app.explorer_mode, ExplorerMode::Projects)
+}
+
+fn explorer_categories(app: &App) -> bool {
+    focus_explorer(app) && matches!(app.explorer_mode, ExplorerMode::Categories)
+}
+
+fn explorer_project_tasks(app: &App) -> bool {
+    focus_explorer(app) && matches!(app.explorer_mode, ExplorerMode::ProjectTasks { .. })
+}
+
+fn explorer_not_projects(app: &App) -> bool {
+    !matches!(app.explorer_mode, ExplorerMode::Projects)
+}
+
+fn open_help(app: &mut App) {
+    let return_mode = std::mem::replace(&mut app.mode, InputMode::Normal);
+    let context = KeyContext::for_mode(&return_mode);
+    let title = match context {
+        KeyContext::Normal => match app.focus {
+            FocusPane::Calendar => "Keys: Calendar".to_string(),
+            FocusPane::Day => "Keys: Day view".to_string(),
+            FocusPane::Running => "Keys: Running tasks".to_string(),
+            FocusPane::Explorer => match &app.explorer_mode {
+                ExplorerMode::Projects => "Keys: Explorer (projects)".to_string(),
+                ExplorerMode::Categories => "Keys: Explorer (categories)".to_string(),
+                ExplorerMode::ProjectTasks { project_name, .. } => {
+                    format!("Keys: Explorer ({project_name})")
+                }
+            },
+        },
+        KeyContext::Prompt => "Keys: Prompt".to_string(),
+        KeyContext::Select => "Keys: Select".to_string(),
+        KeyContext::EditList => "Keys: Edit".to_string(),
+        KeyContext::EditField => "Keys: Edit field".to_string(),
+        KeyContext::Help => "Keys: Help".to_string(),
+        KeyContext::Detail => "Keys: Details".to_string(),
+    };
+    let rows = build_help_rows(app, context);
+    app.mode = InputMode::Help(HelpState {
+        title,
+        rows,
+        selected: 0,
+        return_mode: Box::new(return_mode),
+    });
+}
+
+fn build_help_rows(app: &App, context: KeyContext) -> Vec<HelpRow> {
+    let mut groups: Vec<(&'static str, Vec<&KeyBinding>)> = Vec::new();
+    for binding in KEY_BINDINGS
+        .iter()
+        .filter(|binding| binding.context == context && (binding.available)(app))
+    {
+        match groups.iter_mut().find(|(group, _)| *group == binding.group) {
+            Some((_, bindings)) => bindings.push(binding),
+            None => groups.push((binding.group, vec![binding])),
+        }
+    }
+
+    let mut rows = Vec::new();
+    for (group, bindings) in groups {
+        rows.push(HelpRow::Group(group));
+        for binding in bindings {
+            rows.push(HelpRow::Binding {
+                label: binding.label,
+                description: binding.description,
+            });
+        }
+    }
+    rows
+}
+
+fn handle_help_key(app: &mut App, key: KeyEvent) -> bool {
+    let Some(action) = key_action(KeyContext::Help, &key) else {
+        return false;
+    };
+    let InputMode::Help(help) = &mut app.mode else {
+        return false;
+    };
+
+    let last = help.rows.len().saturating_sub(1);
+    match action {
+        Action::MoveUp => help.selected = help.selected.saturating_sub(1),
+        Action::MoveDown => help.selected = (help.selected + 1).min(last),
+        Action::PageUp => help.selected = help.selected.saturating_sub(HELP_PAGE_ROWS),
+        Action::PageDown => help.selected = (help.selected + HELP_PAGE_ROWS).min(last),
+        Action::LineStart => help.selected = 0,
+        Action::LineEnd => help.selected = last,
+        Action::Cancel => {
+            let InputMode::Help(help) = std::mem::replace(&mut app.mode, InputMode::Normal) else {
+                return false;
+            };
+            app.mode = *help.return_mode;
+        }
+        _ => {}
+    }
+
+    false
+}
+
+fn handle_detail_key(app: &mut App, key: KeyEvent) -> bool {
+    let Some(action) = key_action(KeyContext::Detail, &key) else {
+        return false;
+    };
+    if action == Action::Help {
+        open_help(app);
+        return false;
+    }
+    let InputMode::Detail(detail) = &mut app.mode else {
+        return false;
+    };
+
+    let last = detail.lines.len().saturating_sub(1);
+    match action {
+        Action::MoveUp => detail.scroll = detail.scroll.saturating_sub(1),
+        Action::MoveDown => detail.scroll = (detail.scroll + 1).min(last),
+        Action::PageUp => detail.scroll = detail.scroll.saturating_sub(HELP_PAGE_ROWS),
+        Action::PageDown => detail.scroll = (detail.scroll + HELP_PAGE_ROWS).min(last),
+        Action::LineStart => detail.scroll = 0,
+        Action::LineEnd => detail.scroll = last,
+        Action::Cancel => app.mode = InputMode::Normal,
+        _ => {}
+    }
+
+    false
+}
+
+/// Screen regions recorded while drawing, so mouse events can be mapped back
+/// onto the pane, row or popup option under the pointer.
+#[derive(Debug, Clone, Default)]
+struct DashboardHits {
+    calendar: Rect,
+    day: ListHit,
+    timeline: Option<TimelineHit>,
+    running: Option<ListHit>,
+    explorer: ListHit,
+    popup: Option<ListHit>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ListHit {
+    panel: Rect,
+    area: Rect,
+    offset: usize,
+    heights: Vec<u16>,
+}
+
+impl ListHit {
+    fn panel(panel: Rect, offset: usize, heights: Vec<u16>) -> Self {
+        Self {
+            panel,
+            area: panel.inner(Margin::new(1, 1)),
+            offset,
+            heights,
+        }
+    }
+
+    fn index_at(&self, position: Position) -> Option<usize> {
+        if !self.area.contains(position) {
+            return None;
+        }
+
+        let mut top = self.area.y;
+        for (index, height) in self.heights.iter().enumerate().skip(self.offset) {
+            let bottom = top.saturating_add(*height);
+            if position.y < bottom {
+                return Some(index);
+            }
+            top = bottom;
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct TimelineHit {
+    segments: Vec<(Rect, usize)>,
+}
+
+impl TimelineHit {
+    fn index_at(&self, position: Position) -> Option<usize> {
+        self.segments
+            .iter()
+            .find(|(area, _)| area.contains(position))
+            .map(|(_, index)| *index)
+    }
+}
+
+fn list_item_heights(items: &[ListItem]) -> Vec<u16> {
+    items.iter().map(|item| item.height() as u16).collect()
+}
+
+fn calendar_day_at(app: &App, calendar: Rect, position: Position) -> Option<NaiveDate> {
+    let inner = calendar.inner(Margin::new(1, 1));
+    if !inner.contains(position) {
+        return None;
+    }
+
+    // Rows 0 and 1 hold the month title and weekday header; each day cell is
+    // three columns wide, matching `render_calendar_panel`.
+    let week = usize::from(position.y - inner.y).checked_sub(2)?;
+    let weekday = usize::from((position.x - inner.x) / 3);
+    if weekday >= 7 {
+        return None;
     }
+
+    let month = app.calendar_month;
+    let first_weekday = month.weekday().number_from_monday() as usize - 1;
+    let day = (week * 7 + weekday).checked_sub(first_weekday)? + 1;
+    NaiveDate::from_ymd_opt(month.year(), month.month(), day as u32)
 }
 
-fn days_in_month(year: i32, month: u32) -> u32 {
-    let first_of_next = if month == 12 {
-        NaiveDate::from_ymd_opt(year + 1, 1, 1).expect("next year date should be valid")
-    } else {
-        NaiveDate::from_ymd_opt(year, month + 1, 1).expect("next month date should be valid")
+fn handle_mouse(
+    app: &mut App,
+    mouse: MouseEvent,
+    ledger: &mut Ledger,
+    ledger_path: &mut PathBuf,
+    snapshot: &LedgerSnapshot,
+    view: &ViewModel,
+    hits: &DashboardHits,
+) {
+    let position = Position::new(mouse.column, mouse.row);
+    let scroll = match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => None,
+        MouseEventKind::ScrollUp => Some(-1),
+        MouseEventKind::ScrollDown => Some(1),
+        _ => return,
     };
-    (first_of_next - Duration::days(1)).day()
-}
 
-fn first_day_of_month(day: NaiveDate) -> NaiveDate {
-    NaiveDate::from_ymd_opt(day.year(), day.month(), 1).expect("first day of month must be valid")
+    match (&mut app.mode, scroll) {
+        (InputMode::Normal, Some(delta)) => scroll_dashboard(app, position, delta, view, hits),
+        (InputMode::Normal, None) => {
+            click_dashboard(app, position, ledger, ledger_path, snapshot, view, hits)
+        }
+        (InputMode::Select(select), Some(delta)) => select.move_selection(delta),
+        (InputMode::Select(select), None) => {
+            let Some(popup) = &hits.popup else {
+                return;
+            };
+            if let Some(index) = popup.index_at(position) {
+                select.selected = index;
+                confirm_select(app, ledger, ledger_path);
+            } else if !popup.panel.contains(position) {
+                cancel_select(app);
+            }
+        }
+        (InputMode::Edit(edit), Some(delta)) if !edit.editing => edit.move_selection(delta),
+        (InputMode::Edit(edit), None) if !edit.editing => {
+            if let Some(index) = hits
+                .popup
+                .as_ref()
+                .and_then(|popup| popup.index_at(position))
+            {
+                edit.selected = index;
+            }
+        }
+        (InputMode::Help(help), Some(delta)) => {
+            let last = help.rows.len().saturating_sub(1);
+            help.selected = if delta < 0 {
+                help.selected.saturating_sub(1)
+            } else {
+                (help.selected + 1).min(last)
+            };
+        }
+        (InputMode::Help(help), None) => {
+            let Some(popup) = &hits.popup else {
+                return;
+            };
+            if let Some(index) = popup.index_at(position) {
+                help.selected = index;
+            } else if !popup.panel.contains(position) {
+                let InputMode::Help(help) = std::mem::replace(&mut app.mode, InputMode::Normal)
+                else {
+                    return;
+                };
+                app.mode = *help.return_mode;
+            }
+        }
+        (InputMode::Detail(detail), Some(delta)) => {
+            let last = detail.lines.len().saturating_sub(1);
+            detail.scroll = if delta < 0 {
+                detail.scroll.saturating_sub(1)
+            } else {
+                (detail.scroll + 1).min(last)
+            };
+        }
+        (InputMode::Detail(_), None)
+            if hits
+                .popup
+                .as_ref()
+                .is_some_and(|popup| !popup.panel.contains(position)) =>
+        {
+            app.mode = InputMode::Normal;
+        }
+        _ => {}
+    }
 }
 
-fn start_of_week(day: NaiveDate) -> NaiveDate {
-    let days_from_monday = day.weekday().number_from_monday() as i64 - 1;
-    day - Duration::days(days_from_monday)
+fn pane_at(hits: &DashboardHits, position: Position) -> Option<FocusPane> {
+    if hits.calendar.contains(position) {
+        Some(FocusPane::Calendar)
+    } else if hits.day.panel.contains(position) {
+        Some(FocusPane::Day)
+    } else if hits
+        .running
+        .as_ref()
+        .is_some_and(|running| running.panel.contains(position))
+    {
+        Some(FocusPane::Running)
+    } else if hits.explorer.panel.contains(position) {
+        Some(FocusPane::Explorer)
+    } else {
+        None
+    }
 }
 
-fn local_naive_to_utc(naive: chrono::NaiveDateTime) -> Option<DateTime<Utc>> {
-    match Local.from_local_datetime(&naive) {
-        LocalResult::Single(local_datetime) => Some(local_datetime.with_timezone(&Utc)),
-        LocalResult::Ambiguous(first, second) => Some(first.min(second).with_timezone(&Utc)),
-        LocalResult::None => None,
+fn scroll_dashboard(
+    app: &mut App,
+    position: Position,
+    delta: i32,
+    view: &ViewModel,
+    hits: &DashboardHits,
+) {
+    match pane_at(hits, position) {
+        Some(FocusPane::Calendar) => app.shift_selected_day(i64::from(delta) * 7),
+        Some(FocusPane::Day) => app.move_day_selection(delta, view),
+        Some(FocusPane::Running) => app.move_running_selection(delta, view),
+        Some(FocusPane::Explorer) => app.move_explorer_selection(delta, view),
+        None => {}
     }
 }
 
-fn local_clock_on_date_to_utc(
-    day: NaiveDate,
-    hour: u32,
-    minute: u32,
-) -> Result<DateTime<Utc>, String> {
-    let naive = day
-        .and_hms_opt(hour, minute, 0)
-        .ok_or_else(|| "invalid clock time".to_string())?;
-    local_naive_to_utc(naive).ok_or_else(|| "selected local time does not exist".to_string())
-}
+fn click_dashboard(
+    app: &mut App,
+    position: Position,
+    ledger: &mut Ledger,
+    ledger_path: &Path,
+    snapshot: &LedgerSnapshot,
+    view: &ViewModel,
+    hits: &DashboardHits,
+) {
+    let Some(pane) = pane_at(hits, position) else {
+        return;
+    };
+    if app.focus != pane {
+        app.focus = pane;
+        app.clear_day_edit_buffer();
+    }
 
-fn shift_month(day: NaiveDate, delta: i32) -> NaiveDate {
-    let mut year = day.year();
-    let mut month = day.month() as i32 + delta;
-    while month > 12 {
-        year += 1;
-        month -= 12;
+    let index = match pane {
+        FocusPane::Calendar => {
+            if let Some(date) = calendar_day_at(app, hits.calendar, position)
+                && date != app.selected_day
+            {
+                app.shift_selected_day((date - app.selected_day).num_days());
+            }
+            app.last_click = None;
+            return;
+        }
+        FocusPane::Day => hits.day.index_at(position).or_else(|| {
+            hits.timeline
+                .as_ref()
+                .and_then(|timeline| timeline.index_at(position))
+        }),
+        FocusPane::Running => hits
+            .running
+            .as_ref()
+            .and_then(|running| running.index_at(position)),
+        FocusPane::Explorer => hits.explorer.index_at(position),
+    };
+    let Some(index) = index else {
+        app.last_click = None;
+        return;
+    };
+
+    match pane {
+        FocusPane::Day => app.move_day_selection(index as i32 - app.day_index as i32, view),
+        FocusPane::Running => {
+            app.move_running_selection(index as i32 - app.running_index as i32, view)
+        }
+        FocusPane::Explorer => {
+            app.move_explorer_selection(index as i32 - app.explorer_index as i32, view)
+        }
+        FocusPane::Calendar => {}
     }
-    while month < 1 {
-        year -= 1;
-        month += 12;
+
+    let now = Instant::now();
+    let double_click = app.last_click.is_some_and(|(at, last_pane, last_index)| {
+        last_pane == pane && last_index == index && now.duration_since(at) <= DOUBLE_CLICK_WINDOW
+    });
+    if !double_click {
+        app.last_click = Some((now, pane, index));
+        return;
     }
-    let month_u32 = month as u32;
-    let max_day = days_in_month(year, month_u32);
-    let target_day = day.day().min(max_day);
-    NaiveDate::from_ymd_opt(year, month_u32, target_day).expect("shifted month date must be valid")
-}
 
-fn explorer_category_key(project_id: &str, category_id: Option<&str>) -> String {
-    match category_id {
-        Some(category_id) => format!("{project_id}:{category_id}"),
-        None => format!("{project_id}:__uncategorized"),
+    app.last_click = None;
+    if pane == FocusPane::Explorer
+        && !matches!(
+            app.selected_explorer_row_kind(view),
+            Some(ExplorerRowKind::Task { .. })
+        )
+    {
+        open_explorer_row(app, ledger, view);
+        return;
+    }
+    if pane == FocusPane::Day && view.day_rows.get(app.day_index).is_some_and(|row| row.gap) {
+        open_assign_gap_select(app, ledger, view);
+        return;
     }
+
+    let Some(task_id) = app.selected_task_id(view) else {
+        return;
+    };
+    let result = if snapshot.active_tasks.contains_key(&task_id) {
+        stop_task(ledger, ledger_path, &task_id, None)
+    } else {
+        start_task(ledger, ledger_path, &task_id, None)
+    };
+    app.status = match result {
+        Ok(message) => message,
+        Err(err) => format!("error: {err}"),
+    };
 }
 
 #[derive(Debug, Clone)]
@@ -4374,6 +7754,9 @@ impl EditState {
 #[derive(Debug, Clone)]
 enum PromptKind {
     AddProjectName,
+    AddProjectColor {
+        name: String,
+    },
     AddCategoryName,
     AddCategoryDescription {
         name: String,
@@ -4399,6 +7782,12 @@ enum PromptKind {
         event_index: usize,
         task_title: String,
     },
+    SplitSessionAt {
+        start_event_index: usize,
+        task_id: String,
+        session_day: NaiveDate,
+    },
+    WorkingHours,
     LedgerPath,
 }
 
@@ -4419,6 +7808,10 @@ enum SelectKind {
     },
     LedgerSwitch,
     LedgerForget,
+    CopyEntitiesSource,
+    CopyEntities {
+        source_path: PathBuf,
+    },
     IntervalTask {
         edit: EditState,
     },
@@ -4427,6 +7820,14 @@ enum SelectKind {
         stop_event_index: Option<usize>,
         task_title: String,
     },
+    SplitSessionTask {
+        start_event_index: usize,
+        at: DateTime<Utc>,
+    },
+    AssignGap {
+        start: DateTime<Utc>,
+        stop: DateTime<Utc>,
+    },
     DeleteTaskConfirm {
         task_id: String,
         task_title: String,
@@ -4477,6 +7878,53 @@ impl FocusPane {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DayLayout {
+    List,
+    Timeline,
+}
+
+/// Period shown in the stats panel; it always contains the selected day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StatsPeriod {
+    Week,
+    Month,
+    Year,
+}
+
+/// The periods drawn by the month/year stats panel; the week has its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StatsSpan {
+    Month,
+    Year,
+}
+
+impl StatsPeriod {
+    fn span(self) -> Option<StatsSpan> {
+        match self {
+            StatsPeriod::Week => None,
+            StatsPeriod::Month => Some(StatsSpan::Month),
+            StatsPeriod::Year => Some(StatsSpan::Year),
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            StatsPeriod::Week => StatsPeriod::Month,
+            StatsPeriod::Month => StatsPeriod::Year,
+            StatsPeriod::Year => StatsPeriod::Week,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            StatsPeriod::Week => "week",
+            StatsPeriod::Month => "month",
+            StatsPeriod::Year => "year",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum DayField {
     Start,
@@ -4500,6 +7948,34 @@ enum InputMode {
     Prompt(PromptState),
     Select(SelectState),
     Edit(EditState),
+    Help(HelpState),
+    Detail(DetailState),
+}
+
+#[derive(Debug, Clone)]
+struct HelpState {
+    title: String,
+    rows: Vec<HelpRow>,
+    selected: usize,
+    return_mode: Box<InputMode>,
+}
+
+/// Read-only history of a task, project or category, rendered as prebuilt
+/// lines so scrolling does not rescan the ledger.
+#[derive(Debug, Clone)]
+struct DetailState {
+    title: String,
+    lines: Vec<Line<'static>>,
+    scroll: usize,
+}
+
+#[derive(Debug, Clone)]
+enum HelpRow {
+    Group(&'static str),
+    Binding {
+        label: &'static str,
+        description: &'static str,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -4510,12 +7986,19 @@ struct App {
     day_index: usize,
     day_field: DayField,
     day_edit_buffer: String,
+    day_layout: DayLayout,
+    stats_period: StatsPeriod,
+    show_gaps: bool,
+    follow_start_event: Option<usize>,
+    pending_persist: bool,
     running_index: usize,
     explorer_mode: ExplorerMode,
     explorer_index: usize,
     explorer_collapsed_categories: HashSet<String>,
     mode: InputMode,
     status: String,
+    theme: Theme,
+    last_click: Option<(Instant, FocusPane, usize)>,
 }
 
 impl Default for App {
@@ -4528,18 +8011,25 @@ impl Default for App {
             day_index: 0,
             day_field: DayField::Start,
             day_edit_buffer: String::new(),
+            day_layout: DayLayout::List,
+            stats_period: StatsPeriod::Week,
+            show_gaps: false,
+            follow_start_event: None,
+            pending_persist: false,
             running_index: 0,
             explorer_mode: ExplorerMode::Projects,
             explorer_index: 0,
             explorer_collapsed_categories: HashSet::new(),
             mode: InputMode::Normal,
             status: "Ready".to_string(),
+            theme: Theme::new(ThemeName::Dark, false),
+            last_click: None,
         }
     }
 }
 
 impl App {
-    fn new(ledger: &Ledger, now: DateTime<Utc>) -> Self {
+    fn new(ledger: &Ledger, now: DateTime<Utc>, theme: Theme) -> Self {
         let today = ledger.day_for_timestamp(now);
         Self {
             focus: FocusPane::Explorer,
@@ -4548,22 +8038,43 @@ impl App {
             day_index: 0,
             day_field: DayField::Start,
             day_edit_buffer: String::new(),
+            day_layout: DayLayout::List,
+            stats_period: StatsPeriod::Week,
+            show_gaps: false,
+            follow_start_event: None,
+            pending_persist: false,
             running_index: 0,
             explorer_mode: ExplorerMode::Projects,
             explorer_index: 0,
             explorer_collapsed_categories: HashSet::new(),
             mode: InputMode::Normal,
             status: "Ready".to_string(),
+            theme,
+            last_click: None,
         }
     }
 
     fn reset_for_ledger(&mut self, ledger: &Ledger, now: DateTime<Utc>, status: String) {
-        let mut next = Self::new(ledger, now);
+        let mut next = Self::new(ledger, now, self.theme);
+        next.day_layout = self.day_layout;
+        next.stats_period = self.stats_period;
+        next.show_gaps = self.show_gaps;
         next.status = status;
         *self = next;
     }
 
     fn clamp_selection(&mut self, view: &ViewModel) {
+        // Edited sessions can change position in the time-sorted day list;
+        // keep the cursor on the session that was just changed.
+        if let Some(event_index) = self.follow_start_event.take()
+            && let Some(index) = view
+                .day_rows
+                .iter()
+                .position(|row| row.start_event_index == Some(event_index))
+        {
+            self.day_index = index;
+        }
+
         if view.day_rows.is_empty() {
             self.day_index = 0;
         } else {
@@ -4654,6 +8165,7 @@ impl App {
             FocusPane::Day => view
                 .day_rows
                 .get(self.day_index)
+                .filter(|row| !row.gap)
                 .map(|row| row.task_id.clone()),
             FocusPane::Running => view
                 .running_rows
@@ -4695,7 +8207,7 @@ impl App {
             "end"
         };
         if self.day_edit_buffer.is_empty() {
-            return format!("Edit {field}: type HHMM");
+            return format!("Edit {field}: type HHMM or =/- to nudge");
         }
 
         let mut pending = self.day_edit_buffer.clone();
@@ -4708,10 +8220,14 @@ impl App {
 
 struct ViewModel {
     calendar_active_days: HashSet<NaiveDate>,
+    day_bounds: (DateTime<Utc>, DateTime<Utc>),
     day_rows: Vec<DaySessionRow>,
     running_rows: Vec<RunningTaskRow>,
     week_stats: WeekStatsView,
+    /// Month or year stats; `None` while the panel shows the week.
+    period_stats: Option<PeriodStatsView>,
     explorer_rows: Vec<ExplorerRow>,
+    now: DateTime<Utc>,
 }
 
 #[derive(Clone)]
@@ -4728,6 +8244,7 @@ struct DaySessionRow {
     display_stop: DateTime<Utc>,
     start_event_index: Option<usize>,
     stop_event_index: Option<usize>,
+    gap: bool,
 }
 
 #[derive(Clone)]
@@ -4754,6 +8271,31 @@ struct WeekStatsView {
     daily_project_mix: Vec<Vec<ProjectSummaryRow>>,
 }
 
+#[derive(Clone)]
+struct PeriodStatsView {
+    span: StatsSpan,
+    title: String,
+    previous_title: String,
+    buckets: Vec<StatsBucket>,
+    total: Duration,
+    previous_total: Duration,
+    active_days: usize,
+    longest_streak: usize,
+    busiest_day: Option<(NaiveDate, Duration)>,
+    top_projects: Vec<ProjectSummaryRow>,
+    top_categories: Vec<ProjectSummaryRow>,
+}
+
+/// One bar of the month (per day) or year (per month) stats panel.
+#[derive(Clone)]
+struct StatsBucket {
+    label: String,
+    first_day: NaiveDate,
+    last_day: NaiveDate,
+    total: Duration,
+    project_mix: Vec<ProjectSummaryRow>,
+}
+
 #[derive(Clone)]
 struct ProjectSummaryRow {
     name: String,
@@ -4793,12 +8335,6 @@ enum ExplorerRowKind {
     },
 }
 
-struct ActiveSessionRef {
-    started_at: DateTime<Utc>,
-    note: Option<String>,
-    start_event_index: usize,
-}
-
 struct SessionRecord {
     task_id: String,
     start: DateTime<Utc>,
@@ -4842,26 +8378,430 @@ impl StartTaskFlow {
     }
 }
 
-pub fn print_event_log(ledger: &Ledger, limit: usize) {
-    for event in ledger.events.iter().rev().take(limit) {
-        let line = match &event.kind {
-            EventKind::Start { task_id, note } => format!(
-                "{} start {}{}",
-                event.timestamp.to_rfc3339(),
-                task_label(ledger, task_id),
-                note.as_ref()
-                    .map(|value| format!(" note={value}"))
-                    .unwrap_or_default()
-            ),
-            EventKind::Stop { task_id, note } => format!(
-                "{} stop {}{}",
-                event.timestamp.to_rfc3339(),
-                task_label(ledger, task_id),
-                note.as_ref()
-                    .map(|value| format!(" note={value}"))
-                    .unwrap_or_default()
-            ),
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::layout::{Position, Rect};
+
+    use super::{
+        Action, App, DashboardHits, EditFieldId, EditFieldKind, FocusPane, HelpRow, InputMode,
+        KEY_BINDINGS, KeyContext, KeyMatch, ListHit, SessionRecord, StatsSpan, TimelineTrack,
+        activate_edit_field, build_detail_stats, build_gap_rows, build_help_rows,
+        build_period_stats, build_project_edit_state, build_view, calendar_day_at, click_dashboard,
+        collect_sessions, commit_edit_field_input, fit_stats_buckets, flush_pending_persist,
+        is_nudge_event, key_action, local_clock_on_date_to_utc, nudge_day_field, period_comparison,
+        render_period_stats_panel, sparkline_text,
+    };
+    use crate::domain::Ledger;
+    use crate::storage::load_ledger;
+    use crate::theme::{Theme, ThemeName};
+
+    #[test]
+    fn key_bindings_sharing_a_key_resolve_to_one_action() {
+        for binding in KEY_BINDINGS {
+            for pattern in binding.keys {
+                let key = match pattern {
+                    KeyMatch::Key(code) => KeyEvent::new(*code, KeyModifiers::NONE),
+                    KeyMatch::Ctrl(value) => {
+                        KeyEvent::new(KeyCode::Char(*value), KeyModifiers::CONTROL)
+                    }
+                    KeyMatch::Alt(value) => KeyEvent::new(KeyCode::Char(*value), KeyModifiers::ALT),
+                    KeyMatch::Digit => KeyEvent::new(KeyCode::Char('5'), KeyModifiers::NONE),
+                };
+                assert_eq!(
+                    key_action(binding.context, &key),
+                    Some(binding.action),
+                    "{:?} '{}' is shadowed by another binding",
+                    binding.context,
+                    binding.label
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn list_hits_account_for_row_heights_and_scroll_offset() {
+        let hit = ListHit::panel(Rect::new(0, 0, 20, 8), 1, vec![2, 1, 2, 1]);
+
+        assert_eq!(hit.index_at(Position::new(3, 0)), None);
+        assert_eq!(hit.index_at(Position::new(3, 1)), Some(1));
+        assert_eq!(hit.index_at(Position::new(3, 2)), Some(2));
+        assert_eq!(hit.index_at(Position::new(3, 3)), Some(2));
+        assert_eq!(hit.index_at(Position::new(3, 4)), Some(3));
+        assert_eq!(hit.index_at(Position::new(3, 5)), None);
+    }
+
+    #[test]
+    fn calendar_clicks_map_to_month_days() {
+        let app = App {
+            calendar_month: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            ..App::default()
+        };
+        let calendar = Rect::new(10, 5, 22, 11);
+
+        // October 2026 starts on a Thursday, the fourth column.
+        assert_eq!(
+            calendar_day_at(&app, calendar, Position::new(11 + 3 * 3, 8)),
+            NaiveDate::from_ymd_opt(2026, 10, 1)
+        );
+        assert_eq!(
+            calendar_day_at(&app, calendar, Position::new(12, 9)),
+            NaiveDate::from_ymd_opt(2026, 10, 5)
+        );
+        assert_eq!(calendar_day_at(&app, calendar, Position::new(11, 8)), None);
+        assert_eq!(calendar_day_at(&app, calendar, Position::new(11, 7)), None);
+    }
+
+    #[test]
+    fn timeline_spans_cover_sessions_and_keep_short_ones_visible() {
+        let day_start = Utc.with_ymd_and_hms(2026, 10, 18, 4, 0, 0).unwrap();
+        let track = TimelineTrack {
+            day_start,
+            day_seconds: 24 * 3600,
+            width: 48,
+        };
+
+        let hour = |value: i64| day_start + chrono::Duration::hours(value);
+        assert_eq!(track.span(hour(2), hour(5)), (4, 10));
+        assert_eq!(
+            track.span(hour(6), hour(6) + chrono::Duration::seconds(30)),
+            (12, 13)
+        );
+        assert_eq!(track.span(hour(23), hour(24)), (46, 48));
+        assert_eq!(track.column(hour(30)), 47);
+    }
+
+    #[test]
+    fn gap_rows_fill_working_hours_around_sessions() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        let clock = |hour, minute| local_clock_on_date_to_utc(day, hour, minute).unwrap();
+        let session = |start, stop| SessionRecord {
+            task_id: "t".to_string(),
+            start,
+            stop,
+            note: None,
+            start_event_index: None,
+            stop_event_index: None,
+        };
+        let sessions = [
+            session(clock(8, 0), clock(10, 0)),
+            session(clock(11, 0), clock(12, 0)),
+            session(clock(11, 30), clock(13, 0)),
+            session(clock(13, 0) + chrono::Duration::seconds(30), clock(14, 0)),
+        ];
+
+        let gaps = build_gap_rows(day, &Ledger::new(), &sessions, clock(16, 0))
+            .into_iter()
+            .map(|row| (row.start, row.stop))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            gaps,
+            [(clock(10, 0), clock(11, 0)), (clock(14, 0), clock(16, 0))]
+        );
+    }
+
+    #[test]
+    fn detail_stats_summarize_sessions_and_fill_idle_days() {
+        let day = |day| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+        let clock = |date, hour| local_clock_on_date_to_utc(day(date), hour, 0).unwrap();
+        let mut ledger = Ledger::new();
+        let project_id = ledger.add_project("Alpha".to_string(), None);
+        let docs = ledger
+            .add_task(project_id.clone(), None, "Docs\nwith details".to_string())
+            .unwrap();
+        let review = ledger
+            .add_task(project_id, None, "Review".to_string())
+            .unwrap();
+        let now = clock(14, 18);
+        ledger
+            .log_session(&docs, clock(12, 9), clock(12, 10), None, now)
+            .unwrap();
+        ledger
+            .log_session(&review, clock(12, 11), clock(12, 12), None, now)
+            .unwrap();
+        ledger
+            .log_session(
+                &docs,
+                clock(14, 9),
+                clock(14, 12),
+                Some("long".to_string()),
+                clock(14, 18),
+            )
+            .unwrap();
+
+        let sessions = collect_sessions(&ledger, now);
+        let stats = build_detail_stats(&ledger, &sessions);
+        let hours = chrono::Duration::hours;
+        assert_eq!(stats.sessions, 3);
+        assert_eq!(stats.total, hours(5));
+        assert_eq!(stats.average(), chrono::Duration::minutes(100));
+        assert_eq!(stats.longest, hours(3));
+        assert_eq!(stats.active_days, 2);
+        assert_eq!(stats.first, Some(clock(12, 9)));
+        assert_eq!(stats.last, Some(clock(14, 12)));
+        assert_eq!(stats.busiest_day, Some((day(14), hours(3))));
+        assert_eq!(
+            stats.daily,
+            [
+                (day(12), hours(2)),
+                (day(13), chrono::Duration::zero()),
+                (day(14), hours(3)),
+            ]
+        );
+        assert_eq!(stats.task_totals[0], (docs, hours(4)));
+        assert_eq!(sparkline_text(&stats.daily), "▆ █");
+    }
+
+    #[test]
+    fn period_stats_cover_the_month_and_year_of_the_selected_day() {
+        let day = |month, day| NaiveDate::from_ymd_opt(2026, month, day).unwrap();
+        let hours = chrono::Duration::hours;
+        let mut ledger = Ledger::new();
+        let alpha = ledger.add_project("Alpha".to_string(), None);
+        let beta = ledger.add_project("Beta".to_string(), None);
+        let ops = ledger.add_category("Ops".to_string(), None);
+        let docs = ledger
+            .add_task(alpha, Some(ops), "Docs".to_string())
+            .unwrap();
+        let review = ledger.add_task(beta, None, "Review".to_string()).unwrap();
+        let totals = [
+            (day(9, 30), &docs, hours(4)),
+            (day(10, 5), &docs, hours(2)),
+            (day(10, 6), &review, hours(1)),
+            (day(10, 7), &docs, hours(3)),
+            (day(10, 20), &review, hours(2)),
+        ];
+        let mut daily_task_totals = std::collections::BTreeMap::new();
+        for (date, task_id, duration) in totals {
+            daily_task_totals
+                .entry(date)
+                .or_insert_with(std::collections::HashMap::new)
+                .insert(task_id.clone(), duration);
+        }
+
+        let month = build_period_stats(StatsSpan::Month, day(10, 18), &ledger, &daily_task_totals);
+        assert_eq!(month.title, "October 2026");
+        assert_eq!(month.buckets.len(), 31);
+        assert_eq!(month.buckets[4].label, "05 Mon");
+        assert_eq!(month.total, hours(8));
+        assert_eq!(month.previous_total, hours(4));
+        assert_eq!(month.active_days, 4);
+        assert_eq!(month.longest_streak, 3);
+        assert_eq!(month.busiest_day, Some((day(10, 7), hours(3))));
+        let fitted = fit_stats_buckets(StatsSpan::Month, &month.buckets, 10);
+        assert_eq!(fitted.len(), 8);
+        assert_eq!(fitted[1].label, "05-08");
+        assert_eq!(fitted[1].total, hours(6));
+        assert_eq!(fitted[7].label, "29-31");
+        assert_eq!(
+            fitted[1]
+                .project_mix
+                .iter()
+                .fold(chrono::Duration::zero(), |acc, row| acc + row.duration),
+            hours(6)
+        );
+        assert_eq!(month.top_projects[0].name, "Alpha");
+        assert_eq!(month.top_categories[0].name, "Ops");
+        assert_eq!(month.top_categories[1].name, "(uncategorized)");
+        assert_eq!(
+            period_comparison(&month.previous_title, month.total, month.previous_total),
+            "vs September 2026: 04:00:00 (+04:00:00, +100%)"
+        );
+
+        let year = build_period_stats(StatsSpan::Year, day(10, 18), &ledger, &daily_task_totals);
+        assert_eq!(year.buckets.len(), 12);
+        assert_eq!(year.buckets[8].total, hours(4));
+        assert_eq!(year.buckets[9].total, hours(8));
+        assert_eq!(year.total, hours(12));
+        assert_eq!(year.previous_total, chrono::Duration::zero());
+        assert_eq!(
+            period_comparison(&year.previous_title, year.total, year.previous_total),
+            "vs 2025: 00:00:00"
+        );
+    }
+
+    #[test]
+    fn color_field_accepts_typed_hex_and_palette_values() {
+        let mut ledger = Ledger::new();
+        let project_id = ledger.add_project("Alpha".to_string(), Some("red".to_string()));
+        let mut edit = build_project_edit_state(&ledger, &project_id).unwrap();
+        edit.selected = edit
+            .fields
+            .iter()
+            .position(|field| field.id == EditFieldId::Color)
+            .unwrap();
+        let color = |edit: &super::EditState| match &edit.fields[edit.selected].kind {
+            EditFieldKind::Choice { value, .. } => value.clone(),
+            _ => unreachable!("color is a choice field"),
+        };
+
+        activate_edit_field(&mut edit);
+        assert!(edit.editing);
+        assert_eq!(edit.input, "red");
+        edit.input = "#33669".to_string();
+        assert!(commit_edit_field_input(&mut edit).is_err());
+        assert!(edit.editing);
+        assert_eq!(color(&edit).as_deref(), Some("red"));
+
+        edit.input = " #336699 ".to_string();
+        commit_edit_field_input(&mut edit).unwrap();
+        assert!(!edit.editing);
+        assert_eq!(color(&edit).as_deref(), Some("#336699"));
+
+        activate_edit_field(&mut edit);
+        edit.input = "208".to_string();
+        commit_edit_field_input(&mut edit).unwrap();
+        assert_eq!(color(&edit).as_deref(), Some("208"));
+    }
+
+    #[test]
+    fn nudges_stop_at_the_session_end_and_persist_once() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        let clock = |hour, minute| local_clock_on_date_to_utc(day, hour, minute).unwrap();
+        let now = clock(18, 0);
+        let mut ledger = Ledger::new();
+        let project_id = ledger.add_project("Alpha".to_string(), None);
+        let task_id = ledger
+            .add_task(project_id, None, "Docs".to_string())
+            .unwrap();
+        ledger
+            .log_session(&task_id, clock(9, 0), clock(9, 3), None, now)
+            .unwrap();
+        let mut app = App {
+            focus: FocusPane::Day,
+            selected_day: day,
+            ..App::default()
+        };
+        let nudge = |app: &mut App, ledger: &mut Ledger, minutes| {
+            let view = build_view(app, ledger, &ledger.snapshot(now), now);
+            nudge_day_field(app, minutes, ledger, &view);
+        };
+        let session_start = |ledger: &Ledger| collect_sessions(ledger, now)[0].start;
+
+        let key = |value, modifiers| KeyEvent::new(KeyCode::Char(value), modifiers);
+        for (value, modifiers, minutes) in [
+            ('=', KeyModifiers::NONE, 1),
+            ('-', KeyModifiers::NONE, -1),
+            ('+', KeyModifiers::SHIFT, 5),
+            ('_', KeyModifiers::SHIFT, -5),
+            ('=', KeyModifiers::ALT, 15),
+            ('_', KeyModifiers::ALT | KeyModifiers::SHIFT, -15),
+        ] {
+            let event = key(value, modifiers);
+            assert_eq!(
+                key_action(KeyContext::Normal, &event),
+                Some(Action::Nudge(minutes))
+            );
+            assert!(is_nudge_event(&Event::Key(event)));
+        }
+        assert!(!is_nudge_event(&Event::Key(key('d', KeyModifiers::NONE))));
+
+        nudge(&mut app, &mut ledger, 1);
+        nudge(&mut app, &mut ledger, 1);
+        assert_eq!(session_start(&ledger), clock(9, 2));
+        assert!(app.pending_persist);
+
+        // Five more minutes would move the start past the 09:03 end.
+        nudge(&mut app, &mut ledger, 5);
+        assert_eq!(session_start(&ledger), clock(9, 2));
+        assert_eq!(app.status, "start must be before end");
+
+        let path = std::env::temp_dir().join(format!("nudge-{}.ledger", std::process::id()));
+        flush_pending_persist(&mut app, &ledger, &path);
+        assert!(!app.pending_persist);
+        let saved = load_ledger(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(collect_sessions(&saved, now)[0].start, clock(9, 2));
+    }
+
+    #[test]
+    fn double_clicking_a_gap_opens_the_task_chooser() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        let clock = |hour, minute| local_clock_on_date_to_utc(day, hour, minute).unwrap();
+        let now = clock(18, 0);
+        let mut ledger = Ledger::new();
+        let project_id = ledger.add_project("Alpha".to_string(), None);
+        let task_id = ledger
+            .add_task(project_id, None, "Docs".to_string())
+            .unwrap();
+        ledger
+            .log_session(&task_id, clock(10, 0), clock(11, 0), None, now)
+            .unwrap();
+        let mut app = App {
+            focus: FocusPane::Day,
+            selected_day: day,
+            show_gaps: true,
+            ..App::default()
         };
-        println!("{line}");
+        let snapshot = ledger.snapshot(now);
+        let view = build_view(&app, &ledger, &snapshot, now);
+        assert!(view.day_rows[0].gap);
+        let hits = DashboardHits {
+            day: ListHit::panel(Rect::new(0, 0, 40, 10), 0, vec![1; view.day_rows.len()]),
+            ..DashboardHits::default()
+        };
+
+        let unused_path = std::env::temp_dir().join("unused.ledger");
+        for _ in 0..2 {
+            click_dashboard(
+                &mut app,
+                Position::new(3, 1),
+                &mut ledger,
+                &unused_path,
+                &snapshot,
+                &view,
+                &hits,
+            );
+        }
+        assert!(matches!(app.mode, InputMode::Select(_)));
+    }
+
+    #[test]
+    fn global_stop_and_resume_keys_are_listed_outside_the_running_pane() {
+        let app = App {
+            focus: FocusPane::Explorer,
+            ..App::default()
+        };
+        let labels = build_help_rows(&app, KeyContext::Normal)
+            .into_iter()
+            .filter_map(|row| match row {
+                HelpRow::Binding { label, .. } => Some(label),
+                HelpRow::Group(_) => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(labels.contains(&"S"));
+        assert!(labels.contains(&"r"));
+    }
+
+    #[test]
+    fn month_stats_keep_the_top_lists_on_short_terminals() {
+        let ledger = Ledger::new();
+        let selected_day = chrono::NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let stats = build_period_stats(
+            StatsSpan::Month,
+            selected_day,
+            &ledger,
+            &std::collections::BTreeMap::new(),
+        );
+        let theme = Theme::new(ThemeName::NoColor, false);
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(40, 30)).unwrap();
+        terminal
+            .draw(|frame| render_period_stats_panel(frame, frame.area(), &theme, &stats))
+            .unwrap();
+        let screen = terminal
+            .backend()
+            .buffer()
+            .content()
+            .chunks(40)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>();
+        let row_of = |text: &str| screen.iter().position(|row| row.contains(text));
+        assert!(row_of("Top Categories").is_some());
+        let activity = row_of("Activity").expect("bars should be drawn");
+        assert!(row_of("Top Projects") < Some(activity));
+        assert!(row_of("01-0").is_some_and(|row| row > activity));
+        assert!(row_of("31-31").is_some(), "{screen:#?}");
     }
 }